@@ -0,0 +1,209 @@
+use std::ops::BitAnd;
+use std::ops::BitOr;
+use std::ops::BitXor;
+use std::ops::Not;
+
+/// A boolean for use in constant-time code.  This is stored as a
+/// byte holding 0 or 1, and all operations on it are branch-free.
+///
+/// Code that needs to avoid data-dependent control flow should carry
+/// a Choice around rather than a bool, and only convert to a bool
+/// once the result is no longer secret.
+#[derive(Copy, Clone, Debug)]
+pub struct Choice(u8);
+
+impl Choice {
+    /// Get the underlying value, either 0 or 1.
+    pub fn unwrap_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for Choice {
+    /// Create a Choice from a byte, which must be 0 or 1.
+    fn from(input: u8) -> Choice {
+        Choice(input & 0x1)
+    }
+}
+
+impl From<bool> for Choice {
+    fn from(input: bool) -> Choice {
+        Choice(input as u8)
+    }
+}
+
+impl From<Choice> for bool {
+    fn from(input: Choice) -> bool {
+        input.0 != 0
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Choice;
+
+    fn bitand(self, rhs: Choice) -> Choice {
+        Choice(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Choice;
+
+    fn bitor(self, rhs: Choice) -> Choice {
+        Choice(self.0 | rhs.0)
+    }
+}
+
+impl BitXor for Choice {
+    type Output = Choice;
+
+    fn bitxor(self, rhs: Choice) -> Choice {
+        Choice(self.0 ^ rhs.0)
+    }
+}
+
+impl Not for Choice {
+    type Output = Choice;
+
+    fn not(self) -> Choice {
+        Choice(self.0 ^ 0x1)
+    }
+}
+
+/// Things that can be selected between without branching.
+pub trait ConditionallySelectable : Copy {
+    /// Return a if choice is 0, and b if choice is 1.
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self;
+
+    /// Assign other to self if choice is 1.
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        *self = Self::conditional_select(self, other, choice);
+    }
+
+    /// Swap a and b if choice is 1.
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        let t = *a;
+
+        a.conditional_assign(b, choice);
+        b.conditional_assign(&t, choice);
+    }
+}
+
+/// Things that can be negated without branching.
+pub trait ConditionallyNegatable {
+    /// Negate self in place if choice is 1.
+    fn conditional_negate(&mut self, choice: Choice);
+}
+
+/// Things that can be compared for equality in constant time.
+pub trait ConstantTimeEq {
+    /// Determine whether self and other are equal.
+    fn ct_eq(&self, other: &Self) -> Choice;
+}
+
+/// An optional value, where whether the value is present is a
+/// Choice.  Unlike Option, this always holds a value, so producing
+/// one does not branch on whether it is present.
+#[derive(Copy, Clone, Debug)]
+pub struct CtOption<T> {
+    value: T,
+    is_some: Choice
+}
+
+impl<T> CtOption<T> {
+    /// Create a value, which is present if is_some is 1.
+    pub fn new(value: T, is_some: Choice) -> CtOption<T> {
+        CtOption { value, is_some }
+    }
+
+    /// Get 1 if the value is present.
+    pub fn is_some(&self) -> Choice {
+        self.is_some
+    }
+
+    /// Get 1 if the value is absent.
+    pub fn is_none(&self) -> Choice {
+        !self.is_some
+    }
+
+    /// Get the value, panicking if it is absent.
+    pub fn unwrap(self) -> T {
+        assert!(bool::from(self.is_some));
+
+        self.value
+    }
+
+    /// Convert into an Option.  This branches on whether the value
+    /// is present, so only use it once that is no longer secret.
+    pub fn into_option(self) -> Option<T> {
+        if bool::from(self.is_some) {
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T : ConditionallySelectable> CtOption<T> {
+    /// Get the value if it is present, or def otherwise.
+    pub fn unwrap_or(self, def: T) -> T {
+        T::conditional_select(&def, &self.value, self.is_some)
+    }
+}
+
+/// Get 1 if the word is zero, and 0 otherwise.
+pub fn is_zero_i64(word: i64) -> Choice {
+    let nonzero = ((word | word.wrapping_neg()) >> 63) & 0x1;
+
+    Choice((nonzero ^ 0x1) as u8)
+}
+
+/// Get 1 if the word is zero, and 0 otherwise.
+pub fn is_zero_u32(word: u32) -> Choice {
+    let nonzero = (word | word.wrapping_neg()) >> 31;
+
+    Choice((nonzero ^ 0x1) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::*;
+
+    #[test]
+    fn test_ops() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+
+        assert!(bool::from(t & t));
+        assert!(!bool::from(t & f));
+        assert!(bool::from(t | f));
+        assert!(!bool::from(f | f));
+        assert!(bool::from(t ^ f));
+        assert!(!bool::from(t ^ t));
+        assert!(bool::from(!f));
+        assert!(!bool::from(!t));
+    }
+
+    #[test]
+    fn test_is_zero() {
+        assert!(bool::from(is_zero_i64(0)));
+        assert!(!bool::from(is_zero_i64(1)));
+        assert!(!bool::from(is_zero_i64(-1)));
+        assert!(!bool::from(is_zero_i64(i64::min_value())));
+        assert!(bool::from(is_zero_u32(0)));
+        assert!(!bool::from(is_zero_u32(1)));
+        assert!(!bool::from(is_zero_u32(0x80000000)));
+    }
+
+    #[test]
+    fn test_ct_option() {
+        let some = CtOption::new(3u8, Choice::from(1));
+        let none = CtOption::new(3u8, Choice::from(0));
+
+        assert!(bool::from(some.is_some()));
+        assert!(bool::from(none.is_none()));
+        assert_eq!(3, some.unwrap());
+        assert_eq!(Some(3), some.into_option());
+        assert_eq!(None, none.into_option());
+    }
+}
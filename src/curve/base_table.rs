@@ -0,0 +1,251 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::is_zero_u32;
+use curve::edwards::*;
+use curve::group::Group;
+use curve::point::Point;
+use scalar::scalar_field::ScalarField;
+
+/// A precomputed table for fixed-base scalar multiplication.
+///
+/// The scalar is written in signed radix 16, with digits from -8 to
+/// 7, and window i of the table holds 16^i P, 2 16^i P, ..., 8 16^i P.
+/// Multiplying then takes one addition per digit and no doublings,
+/// which is several times faster than the ladder.  Each lookup reads
+/// every entry of the window, so the time taken does not depend on
+/// the scalar.
+#[derive(Clone)]
+pub struct BaseTable<C : EdwardsCurve> {
+    windows: Vec<[EdwardsExtended<C>; 8]>
+}
+
+/// Get the number of radix 16 digits of a scalar.  The last digit
+/// only holds the carry out of the one before it.
+fn ndigits<S : ScalarField>() -> usize {
+    S::nbits().div_ceil(4) + 1
+}
+
+/// Write a scalar in signed radix 16, with each digit in [-8, 8).
+fn recode<S : ScalarField>(scalar: &S) -> Vec<i8> {
+    let nbits = S::nbits();
+    let mut digits = vec![0i8; ndigits::<S>()];
+    let mut carry = 0i8;
+
+    for (i, digit) in digits.iter_mut().enumerate() {
+        let mut nibble = carry;
+
+        for j in 0..4 {
+            if 4 * i + j < nbits {
+                nibble += (scalar.bit(4 * i + j) as i8) << j;
+            }
+        }
+
+        // Move [8, 16] down to [-8, 0], and carry 16 to the next digit.
+        carry = (nibble + 8) >> 4;
+        *digit = nibble - (carry << 4);
+    }
+
+    digits
+}
+
+/// Get digit times the point for the window, in constant time.
+fn lookup<C : EdwardsCurve>(window: &[EdwardsExtended<C>; 8], digit: i8)
+                            -> EdwardsExtended<C> {
+    let negative = (digit as u8) >> 7;
+    let abs = ((digit ^ -(negative as i8)) + negative as i8) as u32;
+    let mut out = EdwardsExtended::zero();
+
+    for (i, point) in window.iter().enumerate() {
+        out.conditional_assign(point, is_zero_u32((i as u32 + 1) ^ abs));
+    }
+
+    out.conditional_negate(Choice::from(negative));
+    out
+}
+
+impl<C : EdwardsCurve> BaseTable<C> {
+    /// Build the table for a fixed point.
+    pub fn from_point(point: &EdwardsExtended<C>) -> Self {
+        let mut windows = Vec::with_capacity(ndigits::<C::Scalar>());
+        let mut base = *point;
+
+        for _ in 0..ndigits::<C::Scalar>() {
+            let mut window = [base; 8];
+
+            for i in 1..8 {
+                window[i] = window[i - 1] + base;
+            }
+
+            base = window[7].doubled();
+            windows.push(window);
+        }
+
+        BaseTable { windows }
+    }
+
+    /// Multiply the fixed point by a scalar.
+    pub fn mult(&self, scalar: &C::Scalar) -> EdwardsExtended<C> {
+        let mut out = EdwardsExtended::zero();
+
+        for (window, digit) in self.windows.iter().zip(recode(scalar)) {
+            out += &lookup(window, digit);
+        }
+
+        out
+    }
+}
+
+impl<C : EdwardsCurve + Group<EdwardsExtended<C>>> BaseTable<C> {
+    /// Build the table for the base point of the curve.
+    pub fn new() -> Self {
+        BaseTable::from_point(&C::base())
+    }
+}
+
+impl<C : EdwardsCurve + Group<EdwardsExtended<C>>> Default for BaseTable<C> {
+    fn default() -> Self {
+        BaseTable::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::ConstantTimeEq;
+    use curve::base_table::*;
+    use curve::curve1174::Curve1174;
+    use curve::curve41417::Curve41417;
+    use curve::e_222::E222;
+    use curve::e_382::E382;
+    use curve::e_521::E521;
+    use curve::ed25519::Ed25519;
+    use curve::ed448::Ed448;
+    use rand::Rng;
+    use rand::thread_rng;
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    fn ladder<C : EdwardsCurve>(point: &EdwardsExtended<C>,
+                                scalar: &C::Scalar) -> EdwardsExtended<C> {
+        let mut out = *point;
+
+        out.scalar_mult(scalar);
+        out
+    }
+
+    fn check_digits<C : EdwardsCurve>() {
+        let mut rng = thread_rng();
+
+        for _ in 0..16 {
+            let scalar: C::Scalar = rng.gen();
+            let digits = recode(&scalar);
+            let last = digits.len() - 1;
+
+            assert!(digits[0..last].iter().all(|d| (-8..8).contains(d)));
+            assert!(digits[last] == 0 || digits[last] == 1);
+        }
+    }
+
+    #[test]
+    fn test_digits() {
+        check_digits::<E222>();
+        check_digits::<Curve1174>();
+        check_digits::<Ed25519>();
+        check_digits::<E382>();
+        check_digits::<Curve41417>();
+        check_digits::<Ed448>();
+        check_digits::<E521>();
+    }
+
+    fn check_base_mult<C : EdwardsCurve + Group<EdwardsExtended<C>>>() {
+        let mut rng = thread_rng();
+        let base = C::base();
+        let table = BaseTable::<C>::new();
+        let one = C::Scalar::one();
+        let mut scalars = vec![C::Scalar::zero(), one, -one];
+        let mut small = one;
+
+        for _ in 0..20 {
+            small += one;
+            scalars.push(small);
+        }
+
+        for _ in 0..8 {
+            scalars.push(rng.gen());
+        }
+
+        for scalar in scalars.iter() {
+            let expected = ladder(&base, scalar);
+
+            assert!(bool::from(table.mult(scalar).ct_eq(&expected)));
+            assert!(bool::from(C::base_mult(scalar).ct_eq(&expected)));
+        }
+
+        assert!(C::base_mult(&C::order()).is_identity());
+    }
+
+    #[test]
+    fn test_base_mult() {
+        check_base_mult::<E222>();
+        check_base_mult::<Curve1174>();
+        check_base_mult::<Ed25519>();
+        check_base_mult::<E382>();
+        check_base_mult::<Curve41417>();
+        check_base_mult::<Ed448>();
+        check_base_mult::<E521>();
+    }
+
+    #[test]
+    fn test_from_point() {
+        let mut rng = thread_rng();
+        let point = E222::base().tripled();
+        let table = BaseTable::from_point(&point);
+
+        for _ in 0..8 {
+            let scalar = rng.gen();
+
+            assert!(bool::from(table.mult(&scalar)
+                                    .ct_eq(&ladder(&point, &scalar))));
+        }
+    }
+
+    fn time_base_mult<C>(name: &str)
+        where C: EdwardsCurve + Group<EdwardsExtended<C>> {
+        let mut rng = thread_rng();
+        let base = C::base();
+        let scalars: Vec<C::Scalar> = (0..200).map(|_| rng.gen()).collect();
+        let table = BaseTable::<C>::new();
+        let start = Instant::now();
+
+        for scalar in scalars.iter() {
+            black_box(ladder(&base, scalar));
+        }
+
+        let ladder_time = start.elapsed() / scalars.len() as u32;
+        let start = Instant::now();
+
+        for scalar in scalars.iter() {
+            black_box(table.mult(scalar));
+        }
+
+        let table_time = start.elapsed() / scalars.len() as u32;
+
+        println!("{}: ladder {:?}, table {:?}, {:.1}x", name, ladder_time,
+                 table_time,
+                 ladder_time.as_secs_f64() / table_time.as_secs_f64());
+    }
+
+    // Compare the ladder with the table.  Run this with
+    // cargo test --release -- --ignored --nocapture bench_base_mult.
+    #[test]
+    #[ignore]
+    fn bench_base_mult() {
+        time_base_mult::<E222>("E-222");
+        time_base_mult::<Curve1174>("Curve1174");
+        time_base_mult::<Ed25519>("Ed25519");
+        time_base_mult::<E382>("E-382");
+        time_base_mult::<Curve41417>("Curve41417");
+        time_base_mult::<Ed448>("Ed448");
+        time_base_mult::<E521>("E-521");
+    }
+}
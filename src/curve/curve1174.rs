@@ -1,11 +1,364 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::CtOption;
+use choice::is_zero_u32;
+use curve::base_table::BaseTable;
+use curve::decaf::DecafCurve;
 use curve::edwards::*;
+use curve::elligator_squared::ElligatorSquaredCurve;
+use curve::elligator_squared::Params;
+use curve::group::Group;
+use curve::hash_to_curve::HashToCurve;
+use curve::point::Point;
 use field::mod_e251_9::*;
+use field::prime_field::PrimeField;
+use pack::Pack;
+use rand::Rng;
+use scalar::curve1174::Scalar_Curve1174;
+use scalar::scalar_field::ScalarField;
+use std::sync::OnceLock;
 
 #[derive(Copy, Clone)]
 pub struct Curve1174();
 
 impl EdwardsCurve for Curve1174 {
-    type Scalar = Mod_e251_9;
+    type Field = Mod_e251_9;
+    type Scalar = Scalar_Curve1174;
 
-    fn d_val() -> Self::Scalar { CURVE1174_D }
+    fn d_val() -> Self::Field { CURVE1174_D }
+}
+
+impl Group<EdwardsExtended<Curve1174>> for Curve1174 {
+    fn base() -> EdwardsExtended<Curve1174> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(CURVE1174_BASE_X, CURVE1174_BASE_Y);
+        out
+    }
+
+    fn order() -> Scalar_Curve1174 { Scalar_Curve1174::modulus() }
+
+    fn cofactor() -> i32 { 4 }
+
+    fn torsion() -> EdwardsExtended<Curve1174> {
+        // The point (1, 0) has order 4.
+        let mut out = EdwardsExtended::zero();
+
+        out.init(Mod_e251_9::one(), Mod_e251_9::zero());
+        out
+    }
+
+    fn base_mult(scalar: &Scalar_Curve1174) -> EdwardsExtended<Curve1174> {
+        static TABLE: OnceLock<BaseTable<Curve1174>> = OnceLock::new();
+
+        TABLE.get_or_init(BaseTable::new).mult(scalar)
+    }
+}
+
+impl DecafCurve for Curve1174 {
+    fn sqrt_minus_d() -> Self::Field { CURVE1174_SQRT_MINUS_D }
+
+    fn invsqrt_minus_d() -> Self::Field { CURVE1174_INVSQRT_MINUS_D }
+}
+
+/// The Elligator 1 map of Bernstein, Hamburg, Krasnova and Lange,
+/// which takes any field element to a point on Curve1174.  The
+/// elements t and -t give the same point, and 1 and -1 both give the
+/// identity.
+pub fn elligator1(t: &Mod_e251_9) -> EdwardsExtended<Curve1174> {
+    let one = Mod_e251_9::one();
+    let zero = Mod_e251_9::zero();
+    let c = CURVE1174_ELLIGATOR_C;
+    let r = CURVE1174_ELLIGATOR_R;
+    let c_inv_sq = c.squared().inverted();
+    let u = (one - *t) * (one + *t).inverted();
+    let uu = u.squared();
+    let v = ((uu + r.squared().small_sub(2)) * uu + one) * u;
+    let chi_v = v.legendre();
+    let xx = chi_v * u;
+    let yy = (chi_v * v).sqrt() * chi_v * (uu + c_inv_sq).legendre();
+    let xx1 = xx + one;
+    let xx1_sq = xx1.squared();
+    // x = (c - 1) s X (1 + X) / Y, and y = (rX - (1 + X)^2) /
+    // (rX + (1 + X)^2), in projective form.
+    let xn = (c - one) * CURVE1174_ELLIGATOR_S * xx * xx1;
+    let yn = r * xx - xx1_sq;
+    let yd = r * xx + xx1_sq;
+    let point = EdwardsExtended::from_extended(xn * yd, yn * yy, yy * yd,
+                                               xn * yn);
+
+    // Only t = 1 gives u = 0, and with inverted taking 0 to 0, so
+    // does t = -1.  The formulas give (0, -1) there, not the identity.
+    EdwardsExtended::conditional_select(&point, &EdwardsExtended::zero(),
+                                        u.ct_eq(&zero))
+}
+
+/// The inverse of the Elligator 1 map.  This gives the representative
+/// t of a point, with 0 <= t <= (p - 1)/2, if it has one.  About half
+/// of all points have a representative.
+pub fn elligator1_inverse(point: &EdwardsExtended<Curve1174>)
+                          -> CtOption<Mod_e251_9> {
+    let one = Mod_e251_9::one();
+    let zero = Mod_e251_9::zero();
+    let c = CURVE1174_ELLIGATOR_C;
+    let r = CURVE1174_ELLIGATOR_R;
+    let s = CURVE1174_ELLIGATOR_S;
+    let c_inv_sq = c.squared().inverted();
+    let (px, py, pz, _) = point.extended();
+    let z_inv = pz.inverted();
+    let x = px * z_inv;
+    let y = py * z_inv;
+    let eta_r = (y - one) * (y + one).small_mul(2).inverted() * r;
+    let eta_r1 = eta_r + one;
+    let q = eta_r1.squared() - one;
+    let root = q.sqrt();
+    let has_root = root.squared().ct_eq(&q);
+    // When eta r = -2, only one of the two points with that y has a
+    // representative.
+    let special_x = s.small_mul(2) * (c - one) * c.legendre() *
+                    r.inverted();
+    let special = eta_r.small_add(2).ct_eq(&zero);
+    let special_ok = !special | x.ct_eq(&special_x);
+    let finite = !(y + one).ct_eq(&zero);
+    let xx = root - eta_r1;
+    let z = ((c - one) * s * xx * (xx + one) * x *
+             (xx.squared() + c_inv_sq)).legendre();
+    let u = z * xx;
+    let mut t = (one - u) * (one + u).inverted();
+    // Take -t instead of t if t > (p - 1)/2, which is exactly when 2t
+    // wraps around the modulus and becomes odd.
+    let negative = Choice::from(t.small_mul(2).bit(0));
+
+    t.conditional_negate(negative);
+
+    CtOption::new(t, has_root & special_ok & finite)
+}
+
+/// Encode a point as 32 bytes that are indistinguishable from random,
+/// if it has an Elligator 1 representative.  The representative is
+/// less than 2^250, so the top six bits are filled from rng.
+///
+/// This only holds for points that are uniform over the whole curve.
+/// Secret times the base point is always in the prime-order subgroup,
+/// which an observer who multiplies by the group order could detect,
+/// so keys to be encoded should come from `elligator1_generate`.
+pub fn elligator1_encode<R: Rng>(point: &EdwardsExtended<Curve1174>,
+                                 rng: &mut R) -> CtOption<[u8; 32]> {
+    let repr = elligator1_inverse(point);
+    let is_some = repr.is_some();
+    let mut t = repr.unwrap_or(Mod_e251_9::zero());
+    let mut out = [0u8; 32];
+
+    t.pack(&mut out);
+    out[31] |= rng.gen::<u8>() & 0xfc;
+
+    CtOption::new(out, is_some)
+}
+
+/// Decode 32 bytes produced by `elligator1_encode` into a point.
+/// Every string decodes to some point, and the top six bits are
+/// ignored.
+pub fn elligator1_decode(bytes: &[u8; 32]) -> EdwardsExtended<Curve1174> {
+    let mut masked = *bytes;
+
+    masked[31] &= 0x03;
+
+    elligator1(&Mod_e251_9::unpacked(&masked))
+}
+
+/// Generate a key pair whose public key has an Elligator 1
+/// representative, retrying with fresh secrets until one does.  This
+/// returns the secret scalar, the public point and its encoding.
+///
+/// The public point is secret times the base point plus a random point
+/// of order dividing 4, as the decoding of a random string would have.
+/// Peers must clear the cofactor when doing Diffie-Hellman with this
+/// key, or the result will depend on the low-order point.
+pub fn elligator1_generate<R: Rng>(rng: &mut R)
+                                   -> (Scalar_Curve1174,
+                                       EdwardsExtended<Curve1174>,
+                                       [u8; 32]) {
+    let torsion = Curve1174::torsion();
+
+    loop {
+        let secret: Scalar_Curve1174 = rng.gen();
+        let k = rng.gen::<u32>() & 3;
+        let mut multiple = EdwardsExtended::zero();
+        let mut low = EdwardsExtended::zero();
+
+        for i in 1..4 {
+            multiple += &torsion;
+            low.conditional_assign(&multiple, is_zero_u32(i ^ k));
+        }
+
+        let public = Curve1174::base_mult(&secret) + low;
+        let repr = elligator1_encode(&public, rng);
+
+        if bool::from(repr.is_some()) {
+            return (secret, public, repr.unwrap());
+        }
+    }
+}
+
+impl HashToCurve for Curve1174 {}
+
+impl ElligatorSquaredCurve for Curve1174 {
+    fn elligator_squared_params() -> Params<Mod_e251_9> {
+        static PARAMS: OnceLock<Params<Mod_e251_9>> = OnceLock::new();
+
+        *PARAMS.get_or_init(Params::new::<Curve1174>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::ConstantTimeEq;
+    use curve::group::Group;
+    use curve::curve1174::*;
+    use curve::point::*;
+    use field::prime_field::PrimeField;
+    use normalize::NormalizeEq;
+    use rand::Rng;
+    use rand::thread_rng;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    fn field(s: &str) -> Mod_e251_9 {
+        let mut bytes = from_hex(s);
+
+        bytes.reverse();
+        Mod_e251_9::unpacked(&bytes)
+    }
+
+    fn affine_eq(point: &EdwardsExtended<Curve1174>, x: &Mod_e251_9,
+                 y: &Mod_e251_9) -> bool {
+        let (px, py, pz, _) = point.extended();
+
+        (*x * pz).normalize_eq(&mut px.clone()) &&
+        (*y * pz).normalize_eq(&mut py.clone())
+    }
+
+    #[test]
+    fn test_base_order() {
+        let zero = EdwardsExtended::zero();
+        let identity = EdwardsCompressed::<Curve1174>::compressed(zero);
+        let base = Curve1174::base();
+        let mut l_base = base;
+
+        l_base.scalar_mult(&Curve1174::order());
+
+        assert!(identity.bytes() !=
+                EdwardsCompressed::<Curve1174>::compressed(base).bytes());
+        assert_eq!(identity.bytes(),
+                   EdwardsCompressed::<Curve1174>::compressed(l_base).bytes());
+    }
+
+    #[test]
+    fn test_elligator1() {
+        let mut t = field("001f1e1d1c1b1a191817161514131211\
+                       100f0e0d0c0b0a090807060504030201");
+        let x = field("05bf38b411a4579bcbbd52907df7d694\
+                       56bdb92d2729d1ba7d9d347626f7944a");
+        let y = field("06459265ea708f9d5d3ca27a78a3c70e\
+                       01b2b299932d3236d5ae2846e2a0de59");
+        let point = elligator1(&t);
+        let neg_point = elligator1(&-t);
+        let mut repr = elligator1_inverse(&point).unwrap();
+
+        assert!(point.is_on_curve());
+        assert!(affine_eq(&point, &x, &y));
+        assert!(affine_eq(&neg_point, &x, &y));
+        assert!(t.normalize_eq(&mut repr));
+    }
+
+    #[test]
+    fn test_elligator1_identity() {
+        let mut one = Mod_e251_9::one();
+
+        assert!(elligator1(&one).is_identity());
+        assert!(elligator1(&-one).is_identity());
+
+        let mut repr = elligator1_inverse(&EdwardsExtended::zero()).unwrap();
+
+        assert!(one.normalize_eq(&mut repr));
+    }
+
+    #[test]
+    fn test_elligator1_inverse() {
+        let base = Curve1174::base();
+        let mut repr = elligator1_inverse(&base).unwrap();
+        let mut point = elligator1(&repr);
+        let mut expected = field("035579228aa624573b303f56b9630e82\
+                                4577410cfeb9775cbe3315852e4e8ab5");
+
+        assert!(expected.normalize_eq(&mut repr));
+        assert!(bool::from(point.ct_eq(&base)));
+
+        // 2B has no representative, but 3B does.
+        point = base.doubled();
+        assert!(bool::from(elligator1_inverse(&point).is_none()));
+        point += base;
+        repr = elligator1_inverse(&point).unwrap();
+        assert!(bool::from(elligator1(&repr).ct_eq(&point)));
+    }
+
+    #[test]
+    fn test_elligator1_round_trip() {
+        let mut rng = thread_rng();
+        let mut found = 0;
+
+        for _ in 0..32 {
+            let t: Mod_e251_9 = rng.gen();
+            let point = elligator1(&t);
+            let bytes = elligator1_encode(&point, &mut rng).unwrap();
+
+            assert!(bool::from(elligator1_decode(&bytes).ct_eq(&point)));
+
+            let other = point.tripled();
+            let repr = elligator1_inverse(&other);
+
+            if bool::from(repr.is_some()) {
+                found += 1;
+                assert!(bool::from(elligator1(&repr.unwrap())
+                                       .ct_eq(&other)));
+            }
+        }
+
+        // Roughly half of all points have a representative.
+        assert!(found > 0 && found < 32);
+    }
+
+    #[test]
+    fn test_elligator1_generate() {
+        let mut rng = thread_rng();
+        let mut outside = 0;
+
+        for _ in 0..16 {
+            let (secret, public, bytes) = elligator1_generate(&mut rng);
+            let mut low = public + (-Curve1174::base_mult(&secret));
+            let mut point = elligator1_decode(&bytes);
+
+            assert!(bool::from(point.ct_eq(&public)));
+
+            // The public key is off the base point by a point of low
+            // order, which is nonzero three times in four.
+            low.double();
+            low.double();
+            assert!(low.is_identity());
+
+            point.scalar_mult(&Curve1174::order());
+
+            if !point.is_identity() {
+                outside += 1;
+            }
+        }
+
+        assert!(outside > 0);
+    }
 }
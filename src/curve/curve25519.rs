@@ -0,0 +1,169 @@
+use curve::ed25519::Ed25519;
+use curve::edwards::EdwardsExtended;
+use curve::elligator2::Elligator2Curve;
+use curve::hash_to_curve;
+use curve::montgomery::*;
+use error::Error;
+use field::mod_e255_19::*;
+use field::prime_field::PrimeField;
+use pack::Pack;
+use scalar::curve25519::Scalar_Curve25519;
+
+#[derive(Copy, Clone)]
+pub struct Curve25519();
+
+impl MontgomeryCurve for Curve25519 {
+    type Field = Mod_e255_19;
+    type Scalar = Scalar_Curve25519;
+
+    fn a_val() -> Self::Field { CURVE25519_A }
+
+    fn a24_val() -> Self::Field { CURVE25519_A24 }
+}
+
+impl Elligator2Curve for Curve25519 {
+    fn base_x() -> Self::Field { CURVE25519_BASE_X }
+
+    fn dirty_base_x() -> Self::Field { CURVE25519_DIRTY_BASE_X }
+}
+
+/// Decode a scalar as described in RFC 7748: clear the three
+/// low-order bits, clear bit 255, and set bit 254.
+fn clamp(scalar: &[u8; 32]) -> [u8; 32] {
+    let mut out = *scalar;
+
+    out[0] &= 248;
+    out[31] &= 127;
+    out[31] |= 64;
+
+    out
+}
+
+/// The X25519 function from RFC 7748.  This multiplies the
+/// u-coordinate u by the scalar, after clamping the scalar, and
+/// returns the resulting u-coordinate.
+///
+/// The highest bit of u is ignored, and non-canonical values of u
+/// are accepted, as the RFC requires.
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let k = clamp(scalar);
+    let mut p = MontgomeryX::<Curve25519>::new(Mod_e255_19::unpacked(u));
+
+    p.ladder(&k, 255);
+    p.affine_x().packed()
+}
+
+/// Compute the X25519 public key corresponding to a private key, by
+/// multiplying the base point u = 9.
+pub fn x25519_base(scalar: &[u8; 32]) -> [u8; 32] {
+    let k = clamp(scalar);
+    let mut p = MontgomeryX::<Curve25519>::new(CURVE25519_BASE_X);
+
+    p.ladder(&k, 255);
+    p.affine_x().packed()
+}
+
+/// Get the u-coordinate of the Curve25519 point corresponding to an
+/// Ed25519 point, u = (1 + y)/(1 - y).  The identity maps to 0.
+fn edwards_to_u(point: &EdwardsExtended<Ed25519>) -> Mod_e255_19 {
+    let (_, y, z, _) = point.extended();
+
+    (z + y) * (z - y).inverted()
+}
+
+/// The curve25519_XMD:SHA-512_ELL2_RO_ suite from RFC 9380, giving
+/// the u-coordinate of a point in the prime-order subgroup.  The map
+/// from Ed25519 is an isomorphism, so this hashes there and maps the
+/// result across.
+pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Result<Mod_e255_19, Error> {
+    let point = hash_to_curve::hash_to_curve::<Ed25519>(msg, dst)?;
+
+    Ok(edwards_to_u(&point))
+}
+
+/// The curve25519_XMD:SHA-512_ELL2_NU_ suite from RFC 9380.
+pub fn encode_to_curve(msg: &[u8], dst: &[u8])
+                       -> Result<Mod_e255_19, Error> {
+    let point = hash_to_curve::encode_to_curve::<Ed25519>(msg, dst)?;
+
+    Ok(edwards_to_u(&point))
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::curve25519::*;
+
+    fn from_hex(s: &str) -> [u8; 32] {
+        let mut out = [0u8; 32];
+
+        for i in 0..32 {
+            out[i] = u8::from_str_radix(&s[2 * i .. 2 * i + 2], 16).unwrap();
+        }
+
+        out
+    }
+
+    #[test]
+    fn test_rfc7748_vectors() {
+        let k1 = from_hex("a546e36bf0527c9d3b16154b82465edd\
+                           62144c0ac1fc5a18506a2244ba449ac4");
+        let u1 = from_hex("e6db6867583030db3594c1a424b15f7c\
+                           726624ec26b3353b10a903a6d0ab1c4c");
+        let out1 = from_hex("c3da55379de9c6908e94ea4df28d084f\
+                             32eccf03491c71f754b4075577a28552");
+        let k2 = from_hex("4b66e9d4d1b4673c5ad22691957d6af5\
+                           c11b6421e0ea01d42ca4169e7918ba0d");
+        let u2 = from_hex("e5210f12786811d3f4b7959d0538ae2c\
+                           31dbe7106fc03c3efc4cd549c715a493");
+        let out2 = from_hex("95cbde9476e8907d7aade45cb4b873f8\
+                             8b595a68799fa152e6f8f7647aac7957");
+
+        assert_eq!(out1, x25519(&k1, &u1));
+        assert_eq!(out2, x25519(&k2, &u2));
+    }
+
+    #[test]
+    fn test_rfc7748_dh() {
+        let a_priv = from_hex("77076d0a7318a57d3c16c17251b26645\
+                               df4c2f87ebc0992ab177fba51db92c2a");
+        let a_pub = from_hex("8520f0098930a754748b7ddcb43ef75a\
+                              0dbf3a0d26381af4eba4a98eaa9b4e6a");
+        let b_priv = from_hex("5dab087e624a8a4b79e17f8b83800ee6\
+                               6f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let b_pub = from_hex("de9edb7d7b7dc1b4d35b61c2ece43537\
+                              3f8343c85b78674dadfc7e146f882b4f");
+        let shared = from_hex("4a5d9d5ba4ce2de1728e3bf480350f25\
+                               e07e21c947d19e3376f09b3c1e161742");
+
+        assert_eq!(a_pub, x25519_base(&a_priv));
+        assert_eq!(b_pub, x25519_base(&b_priv));
+        assert_eq!(shared, x25519(&a_priv, &b_pub));
+        assert_eq!(shared, x25519(&b_priv, &a_pub));
+    }
+
+    #[test]
+    fn test_rfc7748_iterated() {
+        let one = from_hex("422c8e7a6227d7bca1350b3e2bb7279f\
+                            7897b87bb6854b783c60e80311ae3079");
+        let thousand = from_hex("684cf59ba83309552800ef566f2f4d3c\
+                                 1c3887c49360e3875f2eb94d99532c51");
+        let mut k = [0u8; 32];
+        let mut u = [0u8; 32];
+
+        k[0] = 9;
+        u[0] = 9;
+
+        for i in 0..1000 {
+            let out = x25519(&k, &u);
+
+            u = k;
+            k = out;
+
+            if i == 0 {
+                assert_eq!(one, k);
+            }
+        }
+
+        assert_eq!(thousand, k);
+    }
+}
@@ -0,0 +1,22 @@
+use curve::elligator2::Elligator2Curve;
+use curve::montgomery::*;
+use field::mod_e383_187::*;
+use scalar::curve383187::Scalar_Curve383187;
+
+#[derive(Copy, Clone)]
+pub struct Curve383187();
+
+impl MontgomeryCurve for Curve383187 {
+    type Field = Mod_e383_187;
+    type Scalar = Scalar_Curve383187;
+
+    fn a_val() -> Self::Field { CURVE383187_A }
+
+    fn a24_val() -> Self::Field { CURVE383187_A24 }
+}
+
+impl Elligator2Curve for Curve383187 {
+    fn base_x() -> Self::Field { CURVE383187_BASE_X }
+
+    fn dirty_base_x() -> Self::Field { CURVE383187_DIRTY_BASE_X }
+}
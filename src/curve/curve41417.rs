@@ -1,11 +1,80 @@
+use curve::base_table::BaseTable;
 use curve::edwards::*;
+use curve::elligator_squared::ElligatorSquaredCurve;
+use curve::elligator_squared::Params;
+use curve::group::Group;
+use curve::hash_to_curve::HashToCurve;
+use curve::point::Point;
 use field::mod_e414_17::*;
+use scalar::curve41417::Scalar_Curve41417;
+use scalar::scalar_field::ScalarField;
+use std::sync::OnceLock;
 
 #[derive(Copy, Clone)]
 pub struct Curve41417();
 
 impl EdwardsCurve for Curve41417 {
-    type Scalar = Mod_e414_17;
+    type Field = Mod_e414_17;
+    type Scalar = Scalar_Curve41417;
 
-    fn d_val() -> Self::Scalar { CURVE41417_D }
+    fn d_val() -> Self::Field { CURVE41417_D }
+}
+
+impl Group<EdwardsExtended<Curve41417>> for Curve41417 {
+    fn base() -> EdwardsExtended<Curve41417> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(CURVE41417_BASE_X, CURVE41417_BASE_Y);
+        out
+    }
+
+    fn order() -> Scalar_Curve41417 { Scalar_Curve41417::modulus() }
+
+    fn cofactor() -> i32 { 8 }
+
+    fn torsion() -> EdwardsExtended<Curve41417> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(CURVE41417_TORSION_X, CURVE41417_TORSION_Y);
+        out
+    }
+
+    fn base_mult(scalar: &Scalar_Curve41417) -> EdwardsExtended<Curve41417> {
+        static TABLE: OnceLock<BaseTable<Curve41417>> = OnceLock::new();
+
+        TABLE.get_or_init(BaseTable::new).mult(scalar)
+    }
+}
+
+impl HashToCurve for Curve41417 {}
+
+impl ElligatorSquaredCurve for Curve41417 {
+    fn elligator_squared_params() -> Params<Mod_e414_17> {
+        static PARAMS: OnceLock<Params<Mod_e414_17>> = OnceLock::new();
+
+        *PARAMS.get_or_init(Params::new::<Curve41417>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::edwards::*;
+    use curve::group::Group;
+    use curve::curve41417::Curve41417;
+    use curve::point::*;
+
+    #[test]
+    fn test_base_order() {
+        let zero = EdwardsExtended::zero();
+        let identity = EdwardsCompressed::<Curve41417>::compressed(zero);
+        let base = Curve41417::base();
+        let mut l_base = base;
+
+        l_base.scalar_mult(&Curve41417::order());
+
+        assert!(identity.bytes() !=
+                EdwardsCompressed::<Curve41417>::compressed(base).bytes());
+        assert_eq!(identity.bytes(),
+                   EdwardsCompressed::<Curve41417>::compressed(l_base).bytes());
+    }
 }
@@ -0,0 +1,191 @@
+use curve::ed448::Ed448;
+use curve::edwards::EdwardsExtended;
+use curve::hash_to_curve::HashToCurve;
+use curve::hash_to_curve::hash_to_field;
+use curve::montgomery::*;
+use error::Error;
+use field::mod_e448_goldilocks::*;
+use field::prime_field::PrimeField;
+use pack::Pack;
+use scalar::ed448::Scalar_Ed448;
+
+/// The Montgomery curve Curve448, which is birationally equivalent to
+/// Ed448-Goldilocks and so shares its scalar field.
+#[derive(Copy, Clone)]
+pub struct Curve448();
+
+impl MontgomeryCurve for Curve448 {
+    type Field = Mod_e448_Goldilocks;
+    type Scalar = Scalar_Ed448;
+
+    fn a_val() -> Self::Field { CURVE448_A }
+
+    fn a24_val() -> Self::Field { CURVE448_A24 }
+}
+
+/// Decode a scalar as described in RFC 7748: clear the two low-order
+/// bits, and set bit 447.
+fn clamp(scalar: &[u8; 56]) -> [u8; 56] {
+    let mut out = *scalar;
+
+    out[0] &= 252;
+    out[55] |= 128;
+
+    out
+}
+
+/// The X448 function from RFC 7748.  This multiplies the
+/// u-coordinate u by the scalar, after clamping the scalar, and
+/// returns the resulting u-coordinate.
+///
+/// Non-canonical values of u are accepted, as the RFC requires.
+pub fn x448(scalar: &[u8; 56], u: &[u8; 56]) -> [u8; 56] {
+    let k = clamp(scalar);
+    let mut p = MontgomeryX::<Curve448>::new(Mod_e448_Goldilocks::unpacked(u));
+
+    p.ladder(&k, 448);
+    p.affine_x().packed()
+}
+
+/// Compute the X448 public key corresponding to a private key, by
+/// multiplying the base point u = 5.
+pub fn x448_base(scalar: &[u8; 56]) -> [u8; 56] {
+    let k = clamp(scalar);
+    let mut p = MontgomeryX::<Curve448>::new(CURVE448_BASE_X);
+
+    p.ladder(&k, 448);
+    p.affine_x().packed()
+}
+
+/// Get the u-coordinate of the Curve448 point corresponding to an
+/// Ed448 point by the map from RFC 7748, u = y^2/x^2.  Composed with
+/// the 4-isogeny used by `Ed448::map_to_curve`, this multiplies by 4,
+/// which clears the cofactor.
+fn edwards_to_u(point: &EdwardsExtended<Ed448>) -> Mod_e448_Goldilocks {
+    let (x, y, _, _) = point.extended();
+
+    (y * x.inverted()).squared()
+}
+
+/// The curve448_XOF:SHAKE256_ELL2_RO_ suite from RFC 9380, giving the
+/// u-coordinate of a point in the prime-order subgroup.
+pub fn hash_to_curve(msg: &[u8], dst: &[u8])
+                     -> Result<Mod_e448_Goldilocks, Error> {
+    let u = hash_to_field::<Ed448>(msg, dst, 2)?;
+
+    Ok(edwards_to_u(&(Ed448::map_to_curve(&u[0]) +
+                      Ed448::map_to_curve(&u[1]))))
+}
+
+/// The curve448_XOF:SHAKE256_ELL2_NU_ suite from RFC 9380.
+pub fn encode_to_curve(msg: &[u8], dst: &[u8])
+                       -> Result<Mod_e448_Goldilocks, Error> {
+    let u = hash_to_field::<Ed448>(msg, dst, 1)?;
+
+    Ok(edwards_to_u(&Ed448::map_to_curve(&u[0])))
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::curve448::*;
+
+    fn from_hex(s: &str) -> [u8; 56] {
+        let mut out = [0u8; 56];
+
+        for i in 0..56 {
+            out[i] = u8::from_str_radix(&s[2 * i .. 2 * i + 2], 16).unwrap();
+        }
+
+        out
+    }
+
+    #[test]
+    fn test_rfc7748_vectors() {
+        let k1 = from_hex("3d262fddf9ec8e88495266fea19a34d2\
+                           8882acef045104d0d1aae121700a779c\
+                           984c24f8cdd78fbff44943eba368f54b\
+                           29259a4f1c600ad3");
+        let u1 = from_hex("06fce640fa3487bfda5f6cf2d5263f8a\
+                           ad88334cbd07437f020f08f9814dc031\
+                           ddbdc38c19c6da2583fa5429db94ada1\
+                           8aa7a7fb4ef8a086");
+        let out1 = from_hex("ce3e4ff95a60dc6697da1db1d85e6afb\
+                             df79b50a2412d7546d5f239fe14fbaad\
+                             eb445fc66a01b0779d98223961111e21\
+                             766282f73dd96b6f");
+        let k2 = from_hex("203d494428b8399352665ddca42f9de8\
+                           fef600908e0d461cb021f8c538345dd7\
+                           7c3e4806e25f46d3315c44e0a5b43712\
+                           82dd2c8d5be3095f");
+        let u2 = from_hex("0fbcc2f993cd56d3305b0b7d9e55d4c1\
+                           a8fb5dbb52f8e9a1e9b6201b165d0158\
+                           94e56c4d3570bee52fe205e28a78b91c\
+                           dfbde71ce8d157db");
+        let out2 = from_hex("884a02576239ff7a2f2f63b2db6a9ff3\
+                             7047ac13568e1e30fe63c4a7ad1b3ee3\
+                             a5700df34321d62077e63633c575c1c9\
+                             54514e99da7c179d");
+
+        assert_eq!(&out1[..], &x448(&k1, &u1)[..]);
+        assert_eq!(&out2[..], &x448(&k2, &u2)[..]);
+    }
+
+    #[test]
+    fn test_rfc7748_dh() {
+        let a_priv = from_hex("9a8f4925d1519f5775cf46b04b5800d4\
+                               ee9ee8bae8bc5565d498c28dd9c9baf5\
+                               74a9419744897391006382a6f127ab1d\
+                               9ac2d8c0a598726b");
+        let a_pub = from_hex("9b08f7cc31b7e3e67d22d5aea121074a\
+                              273bd2b83de09c63faa73d2c22c5d9bb\
+                              c836647241d953d40c5b12da88120d53\
+                              177f80e532c41fa0");
+        let b_priv = from_hex("1c306a7ac2a0e2e0990b294470cba339\
+                               e6453772b075811d8fad0d1d6927c120\
+                               bb5ee8972b0d3e21374c9c921b09d1b0\
+                               366f10b65173992d");
+        let b_pub = from_hex("3eb7a829b0cd20f5bcfc0b599b6feccf\
+                              6da4627107bdb0d4f345b43027d8b972\
+                              fc3e34fb4232a13ca706dcb57aec3dae\
+                              07bdc1c67bf33609");
+        let shared = from_hex("07fff4181ac6cc95ec1c16a94a0f74d1\
+                               2da232ce40a77552281d282bb60c0b56\
+                               fd2464c335543936521c24403085d59a\
+                               449a5037514a879d");
+
+        assert_eq!(&a_pub[..], &x448_base(&a_priv)[..]);
+        assert_eq!(&b_pub[..], &x448_base(&b_priv)[..]);
+        assert_eq!(&shared[..], &x448(&a_priv, &b_pub)[..]);
+        assert_eq!(&shared[..], &x448(&b_priv, &a_pub)[..]);
+    }
+
+    #[test]
+    fn test_rfc7748_iterated() {
+        let one = from_hex("3f482c8a9f19b01e6c46ee9711d9dc14\
+                            fd4bf67af30765c2ae2b846a4d23a8cd\
+                            0db897086239492caf350b51f833868b\
+                            9bc2b3bca9cf4113");
+        let thousand = from_hex("aa3b4749d55b9daf1e5b00288826c467\
+                                 274ce3ebbdd5c17b975e09d4af6c67cf\
+                                 10d087202db88286e2b79fceea3ec353\
+                                 ef54faa26e219f38");
+        let mut k = [0u8; 56];
+        let mut u = [0u8; 56];
+
+        k[0] = 5;
+        u[0] = 5;
+
+        for i in 0..1000 {
+            let out = x448(&k, &u);
+
+            u = k;
+            k = out;
+
+            if i == 0 {
+                assert_eq!(&one[..], &k[..]);
+            }
+        }
+
+        assert_eq!(&thousand[..], &k[..]);
+    }
+}
@@ -0,0 +1,574 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use curve::edwards::*;
+use curve::group::Group;
+use curve::point::Point;
+use curve::prime_order_group::PrimeOrderGroup;
+use error::Error;
+use field::prime_field::PrimeField;
+use pack::Pack;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+/// Untwisted Edwards curves with cofactor 4, over which Decaf builds
+/// a prime-order group.
+pub trait DecafCurve : EdwardsCurve + Group<EdwardsExtended<Self>> {
+    /// The non-negative square root of -d.
+    fn sqrt_minus_d() -> Self::Field;
+
+    /// The inverse of the square root of -d.
+    fn invsqrt_minus_d() -> Self::Field;
+}
+
+/// An element of the Decaf group over an Edwards curve with cofactor
+/// 4.  This is the prime-order quotient 2E/E[2]: the point is always
+/// in 2E, and points that differ by 2-torsion are the same element.
+///
+/// The encoding, decoding and one-way map are those of decaf448 in
+/// RFC 9496, which work unchanged for any of these curves.
+#[derive(Copy, Clone)]
+pub struct Decaf<C : DecafCurve>(EdwardsExtended<C>);
+
+/// Negate a field element if it is negative, meaning odd.
+fn abs<F : PrimeField>(val: F) -> F {
+    let mut out = val;
+    let neg = Choice::from(out.bit(0));
+
+    out.conditional_negate(neg);
+    out
+}
+
+/// Compute the non-negative square root of u/v, along with whether
+/// it exists.  When v is zero, this gives zero, which is a root only
+/// when u is also zero.
+fn sqrt_ratio<F : PrimeField + Copy>(u: F, v: F) -> (Choice, F) {
+    let root = abs((u * v.inverted()).sqrt());
+    let was_square = (v * root.squared()).ct_eq(&u);
+
+    (was_square, root)
+}
+
+/// The one-way map from RFC 9496 section 5.3.4, which is Elligator 2
+/// on the Jacobi quartic t^2 = s^4 + 2(1 - 2d)s^2 + 1, followed by
+/// the isogeny to the curve.  The RFC gives it for decaf448, but it
+/// works for any of these curves, as they all have a = 1 and use the
+/// non-square -1, which needs p = 3 mod 4.
+fn map<C : DecafCurve>(t: C::Field) -> EdwardsExtended<C> {
+    let one = C::Field::one();
+    let one_minus_two_d = one - C::d_val().small_mul(2);
+    let r = -t.squared();
+    let u0 = C::d_val() * (r - one);
+    let u1 = (u0 + one) * (u0 - r);
+    let num = (r + one) * one_minus_two_d;
+    // Get 1/sqrt(num u1) if that exists, and 1/sqrt(-num u1) if not.
+    let prod = num * u1;
+    let was_square = prod.sqrt_checked().is_some();
+    let signed = C::Field::conditional_select(&-prod, &prod, was_square);
+    let (_, invsqrt) = sqrt_ratio(one, signed);
+    let v = C::Field::conditional_select(&(-t * invsqrt), &invsqrt,
+                                         was_square);
+    let mut s = v * num;
+    // v s is 1/u1 if num u1 was a square, and r/u1 if not.
+    let vs = C::Field::conditional_select(&(v * s), &-(v * s), was_square);
+    let w3 = vs * (r - one) * one_minus_two_d.squared() - one;
+    // s is non-negative if num u1 was a square, and negative if not.
+    let wrong_sign = !(Choice::from(s.bit(0)) ^ was_square);
+
+    s.conditional_negate(wrong_sign);
+
+    // x = 2s/(1 + s^2) and y = (1 - s^2)/w3.  s = 0 gives (0, -1),
+    // which is 2-torsion, and so the identity of the group.
+    let ss = s.squared();
+    let w0 = s.small_mul(2);
+    let w1 = one + ss;
+    let w2 = one - ss;
+
+    EdwardsExtended::from_extended(w0 * w3, w2 * w1, w1 * w3, w0 * w2)
+}
+
+impl<C : DecafCurve> Decaf<C> {
+    /// Get a representative point on the curve.  This is only
+    /// determined up to 2-torsion.
+    pub fn point(&self) -> EdwardsExtended<C> {
+        self.0
+    }
+}
+
+impl<'b, C : DecafCurve> AddAssign<&'b Decaf<C>> for Decaf<C> {
+    fn add_assign(&mut self, rhs: &'b Decaf<C>) {
+        self.0 += &rhs.0;
+    }
+}
+
+impl<C : DecafCurve> AddAssign<Decaf<C>> for Decaf<C> {
+    fn add_assign(&mut self, rhs: Decaf<C>) {
+        *self += &rhs;
+    }
+}
+
+impl<'b, C : DecafCurve> Add<&'b Decaf<C>> for &Decaf<C> {
+    type Output = Decaf<C>;
+
+    fn add(self, rhs: &'b Decaf<C>) -> Decaf<C> {
+        let mut out = *self;
+
+        out += rhs;
+        out
+    }
+}
+
+impl<C : DecafCurve> Add<Decaf<C>> for Decaf<C> {
+    type Output = Decaf<C>;
+
+    fn add(self, rhs: Decaf<C>) -> Decaf<C> {
+        &self + &rhs
+    }
+}
+
+impl<C : DecafCurve> Neg for Decaf<C> {
+    type Output = Decaf<C>;
+
+    fn neg(self) -> Decaf<C> {
+        Decaf(-self.0)
+    }
+}
+
+impl<'b, C : DecafCurve> SubAssign<&'b Decaf<C>> for Decaf<C> {
+    fn sub_assign(&mut self, rhs: &'b Decaf<C>) {
+        self.0 += &(-rhs.0);
+    }
+}
+
+impl<C : DecafCurve> SubAssign<Decaf<C>> for Decaf<C> {
+    fn sub_assign(&mut self, rhs: Decaf<C>) {
+        *self -= &rhs;
+    }
+}
+
+impl<'b, C : DecafCurve> Sub<&'b Decaf<C>> for &Decaf<C> {
+    type Output = Decaf<C>;
+
+    fn sub(self, rhs: &'b Decaf<C>) -> Decaf<C> {
+        let mut out = *self;
+
+        out -= rhs;
+        out
+    }
+}
+
+impl<C : DecafCurve> Sub<Decaf<C>> for Decaf<C> {
+    type Output = Decaf<C>;
+
+    fn sub(self, rhs: Decaf<C>) -> Decaf<C> {
+        &self - &rhs
+    }
+}
+
+impl<C : DecafCurve> ConstantTimeEq for Decaf<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // Adding (0, -1) negates both coordinates, so x1/y1 = x2/y2
+        // exactly when the points differ by 2-torsion.
+        let (x1, y1, _, _) = self.0.extended();
+        let (x2, y2, _, _) = other.0.extended();
+
+        (x1 * y2).ct_eq(&(y1 * x2))
+    }
+}
+
+impl<C : DecafCurve> PrimeOrderGroup for Decaf<C> {
+    type Scalar = C::Scalar;
+
+    fn identity() -> Self {
+        Decaf(EdwardsExtended::zero())
+    }
+
+    fn generator() -> Self {
+        Decaf(C::base().doubled())
+    }
+
+    fn order() -> C::Scalar {
+        C::order()
+    }
+
+    fn is_identity(&self) -> bool {
+        let (x, _, _, _) = self.0.extended();
+
+        bool::from(x.ct_eq(&C::Field::zero()))
+    }
+
+    fn scalar_mult(&mut self, rhs: &C::Scalar) {
+        self.0.scalar_mult(rhs);
+    }
+
+    fn nbytes() -> usize {
+        C::Field::nbytes() as usize
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let (x0, _, z0, t0) = self.0.extended();
+        let one_minus_d = C::Field::one() - C::d_val();
+        let u1 = (x0 + t0) * (x0 - t0);
+        let (_, inv) = sqrt_ratio(C::Field::one(),
+                                  u1 * one_minus_d * x0.squared());
+        let ratio = abs(inv * u1 * C::sqrt_minus_d());
+        let u2 = C::invsqrt_minus_d() * ratio * z0 - t0;
+        let mut s = abs(one_minus_d * inv * x0 * u2);
+        let mut out = vec![0; Self::nbytes()];
+
+        s.pack(&mut out);
+        out
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != Self::nbytes() {
+            return Err(Error::InvalidLength);
+        }
+
+        let mut s = C::Field::unpacked_checked(bytes)?;
+
+        // Only the non-negative root is a valid encoding.
+        if s.bit(0) {
+            return Err(Error::NonCanonical);
+        }
+
+        let one = C::Field::one();
+        let ss = s.squared();
+        let u1 = one + ss;
+        let u2 = u1.squared() - C::d_val().small_mul(4) * ss;
+        let (was_square, inv) = sqrt_ratio(one, u2 * u1.squared());
+
+        if !bool::from(was_square) {
+            return Err(Error::NotOnCurve);
+        }
+
+        let u3 = abs(s.small_mul(2) * inv * u1 * C::sqrt_minus_d());
+        let x = u3 * inv * u2 * C::invsqrt_minus_d();
+        let y = (one - ss) * inv * u1;
+
+        Ok(Decaf(EdwardsExtended::from_extended(x, y, one, x * y)))
+    }
+
+    fn uniform_nbytes() -> usize {
+        2 * C::Field::nbytes() as usize
+    }
+
+    /// Map bytes to the group as decaf448 does in RFC 9496: reduce
+    /// each half into the field, map both with the one-way map, and
+    /// add the results.
+    fn from_uniform_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let len = Self::uniform_nbytes();
+
+        if bytes.len() != len {
+            return Err(Error::InvalidLength);
+        }
+
+        let half = len / 2;
+        let t1 = C::Field::from_bytes_wide(&bytes[0..half]);
+        let t2 = C::Field::from_bytes_wide(&bytes[half..len]);
+
+        Ok(Decaf(map::<C>(t1) + map::<C>(t2)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::ConstantTimeEq;
+    use curve::curve1174::Curve1174;
+    use curve::decaf::*;
+    use curve::e_222::E222;
+    use curve::e_382::E382;
+    use curve::e_521::E521;
+    use curve::ed448::Ed448;
+    use curve::point::Point;
+    use curve::prime_order_group::PrimeOrderGroup;
+    use error::Error;
+    use field::prime_field::PrimeField;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    fn multiples<C : DecafCurve>(n: usize) -> Vec<Decaf<C>> {
+        let mut out = vec![Decaf::<C>::identity()];
+
+        for i in 1..n {
+            let next = out[i - 1] + Decaf::generator();
+
+            out.push(next);
+        }
+
+        out
+    }
+
+    const DECAF448_MULTIPLES: [&str; 5] = [
+        "0000000000000000000000000000000000000000000000000000000000000000\
+         000000000000000000000000000000000000000000000000",
+        "6666666666666666666666666666666666666666666666666666666633333333\
+         333333333333333333333333333333333333333333333333",
+        "c898eb4f87f97c564c6fd61fc7e49689314a1f818ec85eeb3bd5514ac816d387\
+         78f69ef347a89fca817e66defdedce178c7cc709b2116e75",
+        "a0c09bf2ba7208fda0f4bfe3d0f5b29a543012306d43831b5adc6fe7f8596fa3\
+         08763db15468323b11cf6e4aeb8c18fe44678f44545a69bc",
+        "b46f1836aa287c0a5a5653f0ec5ef9e903f436e21c1570c29ad9e5f596da97ee\
+         af17150ae30bcb3174d04bc2d712c8c7789d7cb4fda138f4"
+    ];
+
+    #[test]
+    fn test_decaf448_multiples() {
+        let points = multiples::<Ed448>(DECAF448_MULTIPLES.len());
+
+        for (point, hex) in points.iter().zip(DECAF448_MULTIPLES.iter()) {
+            let bytes = from_hex(hex);
+            let decoded = Decaf::<Ed448>::decode(&bytes).unwrap();
+
+            assert_eq!(bytes, point.encode());
+            assert_eq!(bytes, decoded.encode());
+            assert!(bool::from(decoded.ct_eq(point)));
+        }
+    }
+
+    fn check_round_trip<C : DecafCurve>() {
+        let points = multiples::<C>(8);
+
+        assert!(points[0].is_identity());
+
+        for i in 0..points.len() {
+            let bytes = points[i].encode();
+            let decoded = Decaf::<C>::decode(&bytes).unwrap();
+
+            assert_eq!(Decaf::<C>::nbytes(), bytes.len());
+            assert!(decoded.point().is_on_curve());
+            assert!(bool::from(decoded.ct_eq(&points[i])));
+            assert_eq!(bytes, decoded.encode());
+
+            for j in 0..i {
+                assert!(!bool::from(points[j].ct_eq(&points[i])));
+                assert!(points[j].encode() != bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        check_round_trip::<E222>();
+        check_round_trip::<Curve1174>();
+        check_round_trip::<E382>();
+        check_round_trip::<E521>();
+        check_round_trip::<Ed448>();
+    }
+
+    fn check_torsion<C : DecafCurve>() {
+        let zero = C::Field::zero();
+        let one = C::Field::one();
+        let torsion = EdwardsExtended::from_extended(zero, -one, one, zero);
+
+        for point in multiples::<C>(4) {
+            let moved = Decaf::<C>(point.point() + torsion);
+
+            assert!(bool::from(moved.ct_eq(&point)));
+            assert_eq!(point.encode(), moved.encode());
+        }
+    }
+
+    #[test]
+    fn test_torsion() {
+        check_torsion::<E222>();
+        check_torsion::<Curve1174>();
+        check_torsion::<E382>();
+        check_torsion::<E521>();
+        check_torsion::<Ed448>();
+    }
+
+    fn check_order<C : DecafCurve>() {
+        let mut point = Decaf::<C>::generator();
+
+        assert!(!point.is_identity());
+        point.scalar_mult(&Decaf::<C>::order());
+        assert!(point.is_identity());
+    }
+
+    #[test]
+    fn test_order() {
+        check_order::<E222>();
+        check_order::<Curve1174>();
+        check_order::<E382>();
+        check_order::<E521>();
+        check_order::<Ed448>();
+    }
+
+    fn check_sub<C : DecafCurve>() {
+        let points = multiples::<C>(6);
+
+        for i in 0..points.len() {
+            let neg = -points[i];
+
+            assert!((neg + points[i]).is_identity());
+            assert!(bool::from((Decaf::identity() - points[i]).ct_eq(&neg)));
+
+            for j in 0..i + 1 {
+                let mut diff = points[i];
+
+                diff -= points[j];
+                assert!(bool::from((points[i] - points[j])
+                                       .ct_eq(&points[i - j])));
+                assert!(bool::from(diff.ct_eq(&points[i - j])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        check_sub::<E222>();
+        check_sub::<Curve1174>();
+        check_sub::<E382>();
+        check_sub::<E521>();
+        check_sub::<Ed448>();
+    }
+
+    fn check_decode_invalid<C : DecafCurve>() {
+        let len = Decaf::<C>::nbytes();
+        let mut bytes = Decaf::<C>::generator().encode();
+
+        assert_eq!(Err(Error::InvalidLength),
+                   Decaf::<C>::decode(&bytes[1..]).map(|_| ()));
+
+        // The negative root of a valid encoding.
+        let s = C::Field::unpacked(&bytes);
+
+        (-s).pack(&mut bytes);
+        assert_eq!(Err(Error::NonCanonical),
+                   Decaf::<C>::decode(&bytes).map(|_| ()));
+
+        // An encoding that is not reduced.
+        assert_eq!(Err(Error::NonCanonical),
+                   Decaf::<C>::decode(&vec![0xff; len]).map(|_| ()));
+
+        // Some small even value of s must fail to decode.
+        let invalid = (1..32).filter(|i| {
+            let mut small = vec![0; len];
+
+            small[0] = 2 * i;
+            Decaf::<C>::decode(&small).err() == Some(Error::NotOnCurve)
+        });
+
+        assert!(invalid.count() > 0);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        check_decode_invalid::<E222>();
+        check_decode_invalid::<Curve1174>();
+        check_decode_invalid::<E382>();
+        check_decode_invalid::<E521>();
+        check_decode_invalid::<Ed448>();
+    }
+
+    #[test]
+    fn test_decaf448_decode_invalid() {
+        let mut bytes = vec![0; 56];
+
+        bytes[0] = 4;
+        assert_eq!(Err(Error::NotOnCurve),
+                   Decaf::<Ed448>::decode(&bytes).map(|_| ()));
+    }
+
+    fn uniform_bytes<C : DecafCurve>() -> Vec<u8> {
+        (0..Decaf::<C>::uniform_nbytes()).map(|i| (i * 7 + 3) as u8)
+                                         .collect()
+    }
+
+    fn check_from_uniform_bytes<C : DecafCurve>() {
+        let bytes = uniform_bytes::<C>();
+        let mut point = Decaf::<C>::from_uniform_bytes(&bytes).unwrap();
+        let decoded = Decaf::<C>::decode(&point.encode()).unwrap();
+
+        assert!(point.point().is_on_curve());
+        assert!(bool::from(decoded.ct_eq(&point)));
+        assert_eq!(Err(Error::InvalidLength),
+                   Decaf::<C>::from_uniform_bytes(&bytes[1..])
+                       .map(|_| ()));
+
+        point.scalar_mult(&Decaf::<C>::order());
+        assert!(point.is_identity());
+    }
+
+    #[test]
+    fn test_from_uniform_bytes() {
+        check_from_uniform_bytes::<E222>();
+        check_from_uniform_bytes::<Curve1174>();
+        check_from_uniform_bytes::<E382>();
+        check_from_uniform_bytes::<E521>();
+        check_from_uniform_bytes::<Ed448>();
+    }
+
+    // The vectors for decaf448 element derivation from RFC 9496.
+    const DECAF448_UNIFORM: [(&str, &str); 5] = [
+        ("cbb8c991fd2f0b7e1913462d6463e4fd2ce4ccdd28274dc2ca1f4165d5ee6cdc\
+          cea57be3416e166fd06718a31af45a2f8e987e301be59ae6673e963001dbbda8\
+          0df47014a21a26d6c7eb4ebe0312aa6fffb8d1b26bc62ca40ed51f8057a635a0\
+          2c2b8c83f48fa6a2d70f58a1185902c0",
+         "0c709c9607dbb01c94513358745b7c23953d03b33e39c7234e268d1d6e24f340\
+          14ccbc2216b965dd231d5327e591dc3c0e8844ccfd568848"),
+        ("b6d8da654b13c3101d6634a231569e6b85961c3f4b460a08ac4a5857069576b6\
+          4428676584baa45b97701be6d0b0ba18ac28d443403b45699ea0fbd1164f5893\
+          d39ad8f29e48e399aec5902508ea95e33bc1e9e4620489d684eb5c26bc1ad1e0\
+          9aba61fabc2cdfee0b6b6862ffc8e55a",
+         "76ab794e28ff1224c727fa1016bf7f1d329260b7218a39aea2fdb17d8bd91190\
+          17b093d641cedf74328c327184dc6f2a64bd90eddccfcdab"),
+        ("36a69976c3e5d74e4904776993cbac27d10f25f5626dd45c51d15dcf7b3e6a54\
+          46a6649ec912a56895d6baa9dc395ce9e34b868d9fb2c1fc72eb6495702ea4f4\
+          46c9b7a188a4e0826b1506b0747a6709f37988ff1aeb5e3788d5076ccbb01a4b\
+          c6623c92ff147a1e21b29cc3fdd0e0f4",
+         "c8d7ac384143500e50890a1c25d643343accce584caf2544f9249b2bf4a69210\
+          82be0e7f3669bb5ec24535e6c45621e1f6dec676edd8b664"),
+        ("d5938acbba432ecd5617c555a6a777734494f176259bff9dab844c81aadcf8f7\
+          abd1a9001d89c7008c1957272c1786a4293bb0ee7cb37cf3988e2513b14e1b75\
+          249a5343643d3c5e5545a0c1a2a4d3c685927c38bc5e5879d68745464e2589e0\
+          00b31301f1dfb7471a4f1300d6fd0f99",
+         "62beffc6b8ee11ccd79dbaac8f0252c750eb052b192f41eeecb12f2979713b56\
+          3caf7d22588eca5e80995241ef963e7ad7cb7962f343a973"),
+        ("4dec58199a35f531a5f0a9f71a53376d7b4bdd6bbd2904234a8ea65bbacbce2a\
+          542291378157a8f4be7b6a092672a34d85e473b26ccfbd4cdc6739783dc3f4f6\
+          ee3537b7aed81df898c7ea0ae89a15b5559596c2a5eeacf8b2b362f3db2940e3\
+          798b63203cae77c4683ebaed71533e51",
+         "f4ccb31d263731ab88bed634304956d2603174c66da38742053fa37dd902346c\
+          3862155d68db63be87439e3d68758ad7268e239d39c4fd3b")
+    ];
+
+    #[test]
+    fn test_decaf448_from_uniform_bytes() {
+        for &(input, output) in DECAF448_UNIFORM.iter() {
+            let point = Decaf::<Ed448>::from_uniform_bytes(&from_hex(input));
+
+            assert_eq!(from_hex(output), point.unwrap().encode());
+        }
+    }
+
+    fn check_map_exceptional<C : DecafCurve>() {
+        // t = 1 and t = -1 give r = -1 and s = 0, and so the identity.
+        for t in [ C::Field::one(), -C::Field::one() ].iter() {
+            let point = map::<C>(*t);
+
+            assert!(point.is_on_curve());
+            assert!(Decaf::<C>(point).is_identity());
+        }
+
+        assert!(map::<C>(C::Field::zero()).is_on_curve());
+    }
+
+    #[test]
+    fn test_map_exceptional() {
+        check_map_exceptional::<E222>();
+        check_map_exceptional::<Curve1174>();
+        check_map_exceptional::<E382>();
+        check_map_exceptional::<E521>();
+        check_map_exceptional::<Ed448>();
+    }
+}
@@ -1,11 +1,89 @@
+use curve::base_table::BaseTable;
+use curve::decaf::DecafCurve;
 use curve::edwards::*;
+use curve::elligator_squared::ElligatorSquaredCurve;
+use curve::elligator_squared::Params;
+use curve::group::Group;
+use curve::hash_to_curve::HashToCurve;
+use curve::point::Point;
 use field::mod_e222_117::*;
+use field::prime_field::PrimeField;
+use scalar::e_222::Scalar_E222;
+use scalar::scalar_field::ScalarField;
+use std::sync::OnceLock;
 
 #[derive(Copy, Clone)]
 pub struct E222();
 
 impl EdwardsCurve for E222 {
-    type Scalar = Mod_e222_117;
+    type Field = Mod_e222_117;
+    type Scalar = Scalar_E222;
 
-    fn d_val() -> Self::Scalar { E222_D }
+    fn d_val() -> Self::Field { E222_D }
+}
+
+impl Group<EdwardsExtended<E222>> for E222 {
+    fn base() -> EdwardsExtended<E222> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(E222_BASE_X, E222_BASE_Y);
+        out
+    }
+
+    fn order() -> Scalar_E222 { Scalar_E222::modulus() }
+
+    fn cofactor() -> i32 { 4 }
+
+    fn torsion() -> EdwardsExtended<E222> {
+        // The point (1, 0) has order 4.
+        let mut out = EdwardsExtended::zero();
+
+        out.init(Mod_e222_117::one(), Mod_e222_117::zero());
+        out
+    }
+
+    fn base_mult(scalar: &Scalar_E222) -> EdwardsExtended<E222> {
+        static TABLE: OnceLock<BaseTable<E222>> = OnceLock::new();
+
+        TABLE.get_or_init(BaseTable::new).mult(scalar)
+    }
+}
+
+impl DecafCurve for E222 {
+    fn sqrt_minus_d() -> Self::Field { E222_SQRT_MINUS_D }
+
+    fn invsqrt_minus_d() -> Self::Field { E222_INVSQRT_MINUS_D }
+}
+
+impl HashToCurve for E222 {}
+
+impl ElligatorSquaredCurve for E222 {
+    fn elligator_squared_params() -> Params<Mod_e222_117> {
+        static PARAMS: OnceLock<Params<Mod_e222_117>> = OnceLock::new();
+
+        *PARAMS.get_or_init(Params::new::<E222>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::edwards::*;
+    use curve::group::Group;
+    use curve::e_222::E222;
+    use curve::point::*;
+
+    #[test]
+    fn test_base_order() {
+        let zero = EdwardsExtended::zero();
+        let identity = EdwardsCompressed::<E222>::compressed(zero);
+        let base = E222::base();
+        let mut l_base = base;
+
+        l_base.scalar_mult(&E222::order());
+
+        assert!(identity.bytes() !=
+                EdwardsCompressed::<E222>::compressed(base).bytes());
+        assert_eq!(identity.bytes(),
+                   EdwardsCompressed::<E222>::compressed(l_base).bytes());
+    }
 }
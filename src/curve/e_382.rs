@@ -1,11 +1,89 @@
+use curve::base_table::BaseTable;
+use curve::decaf::DecafCurve;
 use curve::edwards::*;
+use curve::elligator_squared::ElligatorSquaredCurve;
+use curve::elligator_squared::Params;
+use curve::group::Group;
+use curve::hash_to_curve::HashToCurve;
+use curve::point::Point;
 use field::mod_e382_105::*;
+use field::prime_field::PrimeField;
+use scalar::e_382::Scalar_E382;
+use scalar::scalar_field::ScalarField;
+use std::sync::OnceLock;
 
 #[derive(Copy, Clone)]
 pub struct E382();
 
 impl EdwardsCurve for E382 {
-    type Scalar = Mod_e382_105;
+    type Field = Mod_e382_105;
+    type Scalar = Scalar_E382;
 
-    fn d_val() -> Self::Scalar { E382_D }
+    fn d_val() -> Self::Field { E382_D }
+}
+
+impl Group<EdwardsExtended<E382>> for E382 {
+    fn base() -> EdwardsExtended<E382> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(E382_BASE_X, E382_BASE_Y);
+        out
+    }
+
+    fn order() -> Scalar_E382 { Scalar_E382::modulus() }
+
+    fn cofactor() -> i32 { 4 }
+
+    fn torsion() -> EdwardsExtended<E382> {
+        // The point (1, 0) has order 4.
+        let mut out = EdwardsExtended::zero();
+
+        out.init(Mod_e382_105::one(), Mod_e382_105::zero());
+        out
+    }
+
+    fn base_mult(scalar: &Scalar_E382) -> EdwardsExtended<E382> {
+        static TABLE: OnceLock<BaseTable<E382>> = OnceLock::new();
+
+        TABLE.get_or_init(BaseTable::new).mult(scalar)
+    }
+}
+
+impl DecafCurve for E382 {
+    fn sqrt_minus_d() -> Self::Field { E382_SQRT_MINUS_D }
+
+    fn invsqrt_minus_d() -> Self::Field { E382_INVSQRT_MINUS_D }
+}
+
+impl HashToCurve for E382 {}
+
+impl ElligatorSquaredCurve for E382 {
+    fn elligator_squared_params() -> Params<Mod_e382_105> {
+        static PARAMS: OnceLock<Params<Mod_e382_105>> = OnceLock::new();
+
+        *PARAMS.get_or_init(Params::new::<E382>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::edwards::*;
+    use curve::group::Group;
+    use curve::e_382::E382;
+    use curve::point::*;
+
+    #[test]
+    fn test_base_order() {
+        let zero = EdwardsExtended::zero();
+        let identity = EdwardsCompressed::<E382>::compressed(zero);
+        let base = E382::base();
+        let mut l_base = base;
+
+        l_base.scalar_mult(&E382::order());
+
+        assert!(identity.bytes() !=
+                EdwardsCompressed::<E382>::compressed(base).bytes());
+        assert_eq!(identity.bytes(),
+                   EdwardsCompressed::<E382>::compressed(l_base).bytes());
+    }
 }
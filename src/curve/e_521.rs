@@ -1,11 +1,93 @@
+use curve::base_table::BaseTable;
+use curve::decaf::DecafCurve;
 use curve::edwards::*;
+use curve::elligator_squared::ElligatorSquaredCurve;
+use curve::elligator_squared::Params;
+use curve::group::Group;
+use curve::hash_to_curve::HashToCurve;
+use curve::point::Point;
 use field::mod_e521_1::*;
+use field::prime_field::PrimeField;
+use scalar::e_521::Scalar_E521;
+use scalar::scalar_field::ScalarField;
+use std::sync::OnceLock;
 
 #[derive(Copy, Clone)]
 pub struct E521();
 
 impl EdwardsCurve for E521 {
-    type Scalar = Mod_e521_1;
+    type Field = Mod_e521_1;
+    type Scalar = Scalar_E521;
 
-    fn d_val() -> Self::Scalar { E521_D }
+    fn d_val() -> Self::Field { E521_D }
+}
+
+impl Group<EdwardsExtended<E521>> for E521 {
+    fn base() -> EdwardsExtended<E521> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(E521_BASE_X, E521_BASE_Y);
+        out
+    }
+
+    fn order() -> Scalar_E521 { Scalar_E521::modulus() }
+
+    fn cofactor() -> i32 { 4 }
+
+    fn torsion() -> EdwardsExtended<E521> {
+        // The point (1, 0) has order 4.
+        let mut out = EdwardsExtended::zero();
+
+        out.init(Mod_e521_1::one(), Mod_e521_1::zero());
+        out
+    }
+
+    fn base_mult(scalar: &Scalar_E521) -> EdwardsExtended<E521> {
+        static TABLE: OnceLock<BaseTable<E521>> = OnceLock::new();
+
+        TABLE.get_or_init(BaseTable::new).mult(scalar)
+    }
+}
+
+impl DecafCurve for E521 {
+    fn sqrt_minus_d() -> Self::Field { E521_SQRT_MINUS_D }
+
+    fn invsqrt_minus_d() -> Self::Field { E521_INVSQRT_MINUS_D }
+}
+
+impl HashToCurve for E521 {
+    /// This uses k = 256, as RFC 9380 does for P-521, since SHA-512
+    /// cannot give more.
+    fn security_bits() -> usize { 256 }
+}
+
+impl ElligatorSquaredCurve for E521 {
+    fn elligator_squared_params() -> Params<Mod_e521_1> {
+        static PARAMS: OnceLock<Params<Mod_e521_1>> = OnceLock::new();
+
+        *PARAMS.get_or_init(Params::new::<E521>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::edwards::*;
+    use curve::group::Group;
+    use curve::e_521::E521;
+    use curve::point::*;
+
+    #[test]
+    fn test_base_order() {
+        let zero = EdwardsExtended::zero();
+        let identity = EdwardsCompressed::<E521>::compressed(zero);
+        let base = E521::base();
+        let mut l_base = base;
+
+        l_base.scalar_mult(&E521::order());
+
+        assert!(identity.bytes() !=
+                EdwardsCompressed::<E521>::compressed(base).bytes());
+        assert_eq!(identity.bytes(),
+                   EdwardsCompressed::<E521>::compressed(l_base).bytes());
+    }
 }
@@ -0,0 +1,137 @@
+use curve::base_table::BaseTable;
+use curve::edwards::*;
+use curve::elligator_squared::ElligatorSquaredCurve;
+use curve::elligator_squared::Params;
+use curve::group::Group;
+use curve::hash_to_curve::*;
+use curve::point::Point;
+use field::mod_e255_19::*;
+use scalar::curve25519::Scalar_Curve25519;
+use scalar::scalar_field::ScalarField;
+use std::sync::OnceLock;
+
+/// The twisted Edwards curve edwards25519, which is birationally
+/// equivalent to Curve25519 and so shares its scalar field.
+#[derive(Copy, Clone)]
+pub struct Ed25519();
+
+impl EdwardsCurve for Ed25519 {
+    type Field = Mod_e255_19;
+    type Scalar = Scalar_Curve25519;
+
+    fn a_val() -> i32 { -1 }
+
+    fn d_val() -> Self::Field { ED25519_D }
+
+    fn d2_val() -> Self::Field { ED25519_D2 }
+}
+
+impl Group<EdwardsExtended<Ed25519>> for Ed25519 {
+    fn base() -> EdwardsExtended<Ed25519> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(ED25519_BASE_X, ED25519_BASE_Y);
+        out
+    }
+
+    fn order() -> Scalar_Curve25519 { Scalar_Curve25519::modulus() }
+
+    fn cofactor() -> i32 { 8 }
+
+    fn torsion() -> EdwardsExtended<Ed25519> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(ED25519_TORSION_X, ED25519_TORSION_Y);
+        out
+    }
+
+    fn base_mult(scalar: &Scalar_Curve25519) -> EdwardsExtended<Ed25519> {
+        static TABLE: OnceLock<BaseTable<Ed25519>> = OnceLock::new();
+
+        TABLE.get_or_init(BaseTable::new).mult(scalar)
+    }
+}
+
+/// The edwards25519_XMD:SHA-512_ELL2 suites from RFC 9380, which map
+/// to Curve25519 and then use the map from RFC 7748.
+impl HashToCurve for Ed25519 {
+    fn map_to_curve(u: &Mod_e255_19) -> EdwardsExtended<Ed25519> {
+        let (s, t) = map_to_montgomery(u, &CURVE25519_A, &ONE, 2);
+
+        montgomery_to_edwards(&s, &t, &ED25519_SQRT_M486664)
+    }
+}
+
+impl ElligatorSquaredCurve for Ed25519 {
+    fn elligator_squared_params() -> Params<Mod_e255_19> {
+        static PARAMS: OnceLock<Params<Mod_e255_19>> = OnceLock::new();
+
+        *PARAMS.get_or_init(Params::new::<Ed25519>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::ConstantTimeEq;
+    use curve::edwards::*;
+    use curve::group::Group;
+    use curve::ed25519::Ed25519;
+    use curve::point::*;
+    use pack::Pack;
+    use scalar::curve25519::Scalar_Curve25519;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    #[test]
+    fn test_base_order() {
+        let zero = EdwardsExtended::zero();
+        let identity = EdwardsCompressed::<Ed25519>::compressed(zero);
+        let base = Ed25519::base();
+        let mut l_base = base;
+
+        l_base.scalar_mult(&Ed25519::order());
+
+        assert!(base.is_on_curve());
+        assert!(base.doubled().is_on_curve());
+        assert!(base.tripled().is_on_curve());
+        assert!(l_base.is_identity());
+        assert!(identity.bytes() !=
+                EdwardsCompressed::<Ed25519>::compressed(base).bytes());
+        assert_eq!(identity.bytes(),
+                   EdwardsCompressed::<Ed25519>::compressed(l_base).bytes());
+    }
+
+    #[test]
+    fn test_base_rfc8032() {
+        // The encoding of the base point from RFC 8032, section 5.1.
+        let expected = from_hex("58666666666666666666666666666666\
+                                 66666666666666666666666666666666");
+        let base = EdwardsCompressed::<Ed25519>::compressed(Ed25519::base());
+
+        assert_eq!(32, EdwardsCompressed::<Ed25519>::nbytes());
+        assert_eq!(&expected[..], base.bytes());
+        assert!(bool::from(EdwardsCompressed::<Ed25519>::from_bytes(&expected)
+                               .decompressed().unwrap()
+                               .ct_eq(&Ed25519::base())));
+    }
+
+    #[test]
+    fn test_public_key_rfc8032() {
+        // The secret scalar from test 1 of RFC 8032, section 7.1,
+        // reduced mod the group order, and its public key.
+        let a = Scalar_Curve25519::unpacked(
+            &from_hex("7c2cac12e69be96ae9065065462385e8\
+                       fcff2768d980c0a3a520f006904de90f"));
+        let expected = from_hex("d75a980182b10ab7d54bfed3c964073a\
+                                 0ee172f3daa62325af021a68f707511a");
+        let mut p = Ed25519::base();
+
+        p.scalar_mult(&a);
+        assert_eq!(&expected[..],
+                   EdwardsCompressed::<Ed25519>::compressed(p).bytes());
+    }
+}
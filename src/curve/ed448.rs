@@ -0,0 +1,153 @@
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use curve::base_table::BaseTable;
+use curve::decaf::DecafCurve;
+use curve::edwards::*;
+use curve::elligator_squared::ElligatorSquaredCurve;
+use curve::elligator_squared::Params;
+use curve::group::Group;
+use curve::hash_to_curve::*;
+use curve::point::Point;
+use error::Error;
+use field::mod_e448_goldilocks::*;
+use field::prime_field::PrimeField;
+use hash::expand_message::expand_message_xof;
+use scalar::ed448::Scalar_Ed448;
+use scalar::scalar_field::ScalarField;
+use std::sync::OnceLock;
+
+#[derive(Copy, Clone)]
+pub struct Ed448();
+
+impl EdwardsCurve for Ed448 {
+    type Field = Mod_e448_Goldilocks;
+    type Scalar = Scalar_Ed448;
+
+    fn d_val() -> Self::Field { ED448_D }
+}
+
+impl Group<EdwardsExtended<Ed448>> for Ed448 {
+    fn base() -> EdwardsExtended<Ed448> {
+        let mut out = EdwardsExtended::zero();
+
+        out.init(ED448_BASE_X, ED448_BASE_Y);
+        out
+    }
+
+    fn order() -> Scalar_Ed448 { Scalar_Ed448::modulus() }
+
+    fn cofactor() -> i32 { 4 }
+
+    fn torsion() -> EdwardsExtended<Ed448> {
+        // The point (1, 0) has order 4.
+        let mut out = EdwardsExtended::zero();
+
+        out.init(Mod_e448_Goldilocks::one(), Mod_e448_Goldilocks::zero());
+        out
+    }
+
+    fn base_mult(scalar: &Scalar_Ed448) -> EdwardsExtended<Ed448> {
+        static TABLE: OnceLock<BaseTable<Ed448>> = OnceLock::new();
+
+        TABLE.get_or_init(BaseTable::new).mult(scalar)
+    }
+}
+
+impl DecafCurve for Ed448 {
+    fn sqrt_minus_d() -> Self::Field { ED448_SQRT_MINUS_D }
+
+    fn invsqrt_minus_d() -> Self::Field { ED448_INVSQRT_MINUS_D }
+}
+
+/// The edwards448_XOF:SHAKE256_ELL2 suites from RFC 9380, which map
+/// to Curve448 and then use the 4-isogeny from RFC 7748.
+impl HashToCurve for Ed448 {
+    fn expand_message(msg: &[u8], dst: &[u8], len: usize)
+                      -> Result<Vec<u8>, Error> {
+        expand_message_xof(msg, dst, len, Self::security_bits())
+    }
+
+    fn map_to_curve(u: &Mod_e448_Goldilocks) -> EdwardsExtended<Ed448> {
+        let (s, t) = map_to_montgomery(u, &CURVE448_A, &ONE, -1);
+        let ss = s.squared();
+        let tt = t.squared();
+        let ss_minus_one = ss - ONE;
+        let xn = t.small_mul(4) * ss_minus_one;
+        let xd = ss_minus_one.squared() + tt.small_mul(4);
+        let yn = -s * (ss_minus_one.squared() - tt.small_mul(4));
+        let yd = s * ss_minus_one.squared() - tt.small_mul(2) * (ss + ONE);
+        // Points where either denominator vanishes go to the identity.
+        let z = xd * yd;
+        let point = EdwardsExtended::from_extended(xn * yd, yn * xd, z,
+                                                   xn * yn);
+
+        EdwardsExtended::conditional_select(&point, &EdwardsExtended::zero(),
+                                            z.ct_eq(&ZERO))
+    }
+}
+
+impl ElligatorSquaredCurve for Ed448 {
+    fn elligator_squared_params() -> Params<Mod_e448_Goldilocks> {
+        static PARAMS: OnceLock<Params<Mod_e448_Goldilocks>> = OnceLock::new();
+
+        *PARAMS.get_or_init(Params::new::<Ed448>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::ConstantTimeEq;
+    use curve::edwards::*;
+    use curve::group::Group;
+    use curve::ed448::Ed448;
+    use curve::point::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    #[test]
+    fn test_base_order() {
+        let zero = EdwardsExtended::zero();
+        let identity = EdwardsCompressed::<Ed448>::compressed(zero);
+        let base = Ed448::base();
+        let mut l_base = base;
+
+        l_base.scalar_mult(&Ed448::order());
+
+        assert!(base.is_on_curve());
+        assert!(identity.bytes() !=
+                EdwardsCompressed::<Ed448>::compressed(base).bytes());
+        assert_eq!(identity.bytes(),
+                   EdwardsCompressed::<Ed448>::compressed(l_base).bytes());
+    }
+
+    #[test]
+    fn test_base_rfc8032() {
+        // The encoding of the base point from RFC 8032, section 5.2.
+        let expected = from_hex("14fa30f25b790898adc8d74e2c13bdfd\
+                                 c4397ce61cffd33ad7c2a0051e9c7887\
+                                 4098a36c7373ea4b62c7c95637207688\
+                                 24bcb66e71463f6900");
+        let base = EdwardsCompressed::<Ed448>::compressed(Ed448::base());
+
+        assert_eq!(57, EdwardsCompressed::<Ed448>::nbytes());
+        assert_eq!(&expected[..], base.bytes());
+        assert!(bool::from(EdwardsCompressed::<Ed448>::from_bytes(&expected)
+                               .decompressed().unwrap()
+                               .ct_eq(&Ed448::base())));
+    }
+
+    #[test]
+    fn test_decompress_extra_byte() {
+        let mut bytes = EdwardsCompressed::<Ed448>::compressed(Ed448::base())
+                            .bytes().to_vec();
+
+        bytes[56] |= 0x01;
+        assert_eq!(::error::Error::NonCanonical,
+                   EdwardsCompressed::<Ed448>::from_bytes(&bytes)
+                       .decompressed().err().unwrap());
+    }
+}
@@ -1,44 +1,149 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
 use curve::point::*;
+use error::Error;
 use field::prime_field::PrimeField;
-use normalize::Normalize;
+use normalize::NormalizeEq;
+use pack::Pack;
+use scalar::scalar_field::ScalarField;
+use std::marker::PhantomData;
 use std::ops::Add;
 use std::ops::AddAssign;
+use std::ops::Neg;
 
 pub trait EdwardsCurve : Copy {
-    type Scalar : PrimeField + Copy;
+    type Field : PrimeField + Copy;
+    type Scalar : ScalarField;
 
-    fn d_val() -> Self::Scalar;
+    /// The a value in the curve equation ax^2 + y^2 = 1 + dx^2 y^2.
+    /// Only 1 and -1 are supported, so that multiplying by a is at
+    /// most a negation.  This is 1 unless the curve is twisted.
+    fn a_val() -> i32 { 1 }
+
+    fn d_val() -> Self::Field;
+
+    /// Twice the d value, which the addition formula for a = -1
+    /// uses.  Curves with a = -1 should override this with a
+    /// constant.
+    fn d2_val() -> Self::Field { Self::d_val().small_mul(2) }
+}
+
+/// Multiply by the a value of the curve.  This branches only on the
+/// curve, never on secret data.
+fn times_a<C : EdwardsCurve>(val: C::Field) -> C::Field {
+    if C::a_val() < 0 {
+        -val
+    } else {
+        val
+    }
 }
 
 /// Edwards curve point in homogeneous extended coordinates.
 #[derive(Copy, Clone)]
 pub struct EdwardsExtended<C : EdwardsCurve> {
-    x : C::Scalar,
-    y : C::Scalar,
-    z : C::Scalar,
-    t : C::Scalar
+    x : C::Field,
+    y : C::Field,
+    z : C::Field,
+    t : C::Field
+}
+
+impl<C : EdwardsCurve> EdwardsExtended<C> {
+    /// Create a point from its extended coordinates (X : Y : Z : T),
+    /// where x = X/Z, y = Y/Z and XY = ZT.  This does not check that
+    /// the point is on the curve.
+    pub fn from_extended(x: C::Field, y: C::Field, z: C::Field,
+                         t: C::Field) -> Self {
+        EdwardsExtended { x: x, y: y, z: z, t: t }
+    }
+
+    /// Get the extended coordinates (X, Y, Z, T).
+    pub fn extended(&self) -> (C::Field, C::Field, C::Field, C::Field) {
+        (self.x, self.y, self.z, self.t)
+    }
+}
+
+/// Add two points on a curve with any a value (add-2008-hwcd).
+fn add_generic<C : EdwardsCurve>(p: &mut EdwardsExtended<C>,
+                                 q: &EdwardsExtended<C>) {
+    let a = p.x * q.x;
+    let b = p.y * q.y;
+    let c = p.t * C::d_val() * q.t;
+    let d = p.z * q.z;
+    let e = ((p.x + p.y) * (q.x + q.y)) - a - b;
+    let f = d - c;
+    let g = d + c;
+    let h = b - times_a::<C>(a);
+
+    p.x = e * f;
+    p.y = g * h;
+    p.z = f * g;
+    p.t = e * h;
+}
+
+/// Add two points on a curve with a = -1 (add-2008-hwcd-3).  This
+/// takes 8 multiplications and one by 2d, where the generic formula
+/// takes 9 and one by d.
+fn add_a_minus_one<C : EdwardsCurve>(p: &mut EdwardsExtended<C>,
+                                     q: &EdwardsExtended<C>) {
+    let a = (p.y - p.x) * (q.y - q.x);
+    let b = (p.y + p.x) * (q.y + q.x);
+    let c = p.t * C::d2_val() * q.t;
+    let d = (p.z * q.z).small_mul(2);
+    let e = b - a;
+    let f = d - c;
+    let g = d + c;
+    let h = b + a;
+
+    p.x = e * f;
+    p.y = g * h;
+    p.z = f * g;
+    p.t = e * h;
+}
+
+/// Double a point on a curve with any a value (dbl-2008-hwcd).
+fn double_generic<C : EdwardsCurve>(p: &mut EdwardsExtended<C>) {
+    let a = p.x.squared();
+    let b = p.y.squared();
+    let c = p.z.squared().small_mul(2);
+    let d = times_a::<C>(a);
+    let e = (p.x + p.y).squared() - a - b;
+    let g = d + b;
+    let f = g - c;
+    let h = d - b;
+
+    p.x = e * f;
+    p.y = g * h;
+    p.z = f * g;
+    p.t = e * h;
+}
+
+/// Double a point on a curve with a = -1.  This is dbl-2008-hwcd with
+/// every intermediate negated, which saves computing -a.
+fn double_a_minus_one<C : EdwardsCurve>(p: &mut EdwardsExtended<C>) {
+    let a = p.x.squared();
+    let b = p.y.squared();
+    let c = p.z.squared().small_mul(2);
+    let h = a + b;
+    let e = h - (p.x + p.y).squared();
+    let g = a - b;
+    let f = c + g;
+
+    p.x = e * f;
+    p.y = g * h;
+    p.z = f * g;
+    p.t = e * h;
 }
 
 impl<'b, C : EdwardsCurve> AddAssign<&'b EdwardsExtended<C>>
     for EdwardsExtended<C> {
     fn add_assign(&mut self, rhs: &'b EdwardsExtended<C>) {
-        let a = self.x * rhs.x;
-        let b = self.y * rhs.y;
-        let c = self.t * C::d_val() * rhs.t;
-        let d = self.z * rhs.z;
-        let e = ((self.x + self.y) * (rhs.x + rhs.y)) - a - b;
-        let f = d - c;
-        let g = d + c;
-        let h = b - a;
-        let x3 = e * f;
-        let y3 = g * h;
-        let z3 = f * g;
-        let t3 = e * h;
-
-        self.x = x3;
-        self.y = y3;
-        self.z = z3;
-        self.t = t3;
+        if C::a_val() < 0 {
+            add_a_minus_one(self, rhs)
+        } else {
+            add_generic(self, rhs)
+        }
     }
 }
 
@@ -68,49 +173,109 @@ impl<C : EdwardsCurve> Add<EdwardsExtended<C>> for EdwardsExtended<C> {
     }
 }
 
-impl<C: EdwardsCurve> EdwardsExtended<C> {
-    fn normalized_bitand(&mut self, mask: &C::Scalar) {
-        self.x.normalized_bitand(mask);
-        self.y.normalized_bitand(mask);
-        self.z.normalized_bitand(mask);
-        self.t.normalized_bitand(mask);
+impl<C: EdwardsCurve> ConditionallySelectable for EdwardsExtended<C> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        EdwardsExtended {
+            x: C::Field::conditional_select(&a.x, &b.x, choice),
+            y: C::Field::conditional_select(&a.y, &b.y, choice),
+            z: C::Field::conditional_select(&a.z, &b.z, choice),
+            t: C::Field::conditional_select(&a.t, &b.t, choice)
+        }
+    }
+}
+
+impl<C: EdwardsCurve> ConditionallyNegatable for EdwardsExtended<C> {
+    fn conditional_negate(&mut self, choice: Choice) {
+        // The negation of (x, y) is (-x, y).
+        self.x.conditional_negate(choice);
+        self.t.conditional_negate(choice);
     }
+}
+
+impl<C: EdwardsCurve> Neg for EdwardsExtended<C> {
+    type Output = EdwardsExtended<C>;
 
-    fn normalized_bitor(&mut self, rhs: &Self) {
-        self.x.normalized_bitor(&rhs.x);
-        self.y.normalized_bitor(&rhs.y);
-        self.z.normalized_bitor(&rhs.z);
-        self.t.normalized_bitor(&rhs.t);
+    fn neg(self) -> EdwardsExtended<C> {
+        EdwardsExtended { x: -self.x, y: self.y, z: self.z, t: -self.t }
+    }
+}
+
+impl<C: EdwardsCurve> ConstantTimeEq for EdwardsExtended<C> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // Compare the affine coordinates x/z and y/z without
+        // inverting.
+        let x1 = self.x * other.z;
+        let x2 = other.x * self.z;
+        let y1 = self.y * other.z;
+        let y2 = other.y * self.z;
+
+        x1.ct_eq(&x2) & y1.ct_eq(&y2)
     }
 }
 
 impl<C: EdwardsCurve> Point for EdwardsExtended<C> {
+    type Field = C::Field;
     type Scalar = C::Scalar;
 
     fn zero() -> Self {
         EdwardsExtended {
-            x: C::Scalar::zero(),
-            y: C::Scalar::one(),
-            z: C::Scalar::one(),
-            t: C::Scalar::one()
+            x: C::Field::zero(),
+            y: C::Field::one(),
+            z: C::Field::one(),
+            t: C::Field::zero()
         }
     }
 
-    fn init(&mut self, x: C::Scalar, y: C::Scalar) {
+    fn init(&mut self, x: C::Field, y: C::Field) {
         self.x = x;
         self.y = y;
-        self.z = C::Scalar::one();
-        self.t = C::Scalar::one();
+        self.z = C::Field::one();
+        self.t = x * y;
+    }
+
+    fn init_checked(&mut self, x: C::Field, y: C::Field)
+                    -> Result<(), Error> {
+        let mut point = *self;
+
+        point.init(x, y);
+
+        if point.is_on_curve() {
+            *self = point;
+
+            Ok(())
+        } else {
+            Err(Error::NotOnCurve)
+        }
+    }
+
+    fn is_on_curve(&self) -> bool {
+        // In extended coordinates, the curve equation becomes
+        // aX^2 + Y^2 = Z^2 + dT^2, and we also need XY = ZT.
+        let zz = self.z.squared();
+        let mut lhs = times_a::<C>(self.x.squared()) + self.y.squared();
+        let mut rhs = zz + (C::d_val() * self.t.squared());
+        let mut xy = self.x * self.y;
+        let mut zt = self.z * self.t;
+        let mut z = self.z;
+        let nonzero = !z.normalize_self_eq(&C::Field::zero());
+
+        lhs.normalize_eq(&mut rhs) & xy.normalize_eq(&mut zt) & nonzero
     }
 
-    fn scalar_mult(&mut self, rhs: &mut Self::Scalar) {
-        rhs.normalize();
-        self.scalar_mult_normalized(rhs);
+    fn is_identity(&self) -> bool {
+        let mut x = self.x;
+        let mut y = self.y;
+        let mut z = self.z;
+        let nonzero = !z.normalize_self_eq(&C::Field::zero());
+
+        x.normalize_self_eq(&C::Field::zero()) & y.normalize_eq(&mut z) &
+        nonzero
     }
 
-    fn scalar_mult_normalized(&mut self, rhs: &Self::Scalar) {
-        // This is a branchless variant of the classic Montgomery
-        // ladder using bitwise operations in lieu of branches.
+    fn scalar_mult(&mut self, rhs: &Self::Scalar) {
+        // This is the classic Montgomery ladder, using constant-time
+        // selection in lieu of branches.  It maintains the invariant
+        // r1 = r0 + self.
 
         let mut r0 = Self::zero();
         let mut r1 = self.clone();
@@ -118,38 +283,15 @@ impl<C: EdwardsCurve> Point for EdwardsExtended<C> {
 
         for i in 0..nbits {
             let idx = nbits - 1 - i;
-            let bit = rhs.bit_normalized(idx);
-            // Note that we fill everything with 0s or 1s.  This
-            // allows us to skip normalization.
-            let tmask = C::Scalar::filled(bit);
-            let fmask = C::Scalar::filled(!bit);
-            let mut d0 = r0;
-            let mut d1 = r1;
-
-            // r0 = r1 = r0 + r1
-            r0 += r1;
-            r1 = r0;
-
-            // d0 = bit ? r0 : r1
-            d0.normalized_bitand(&tmask);
-            d1.normalized_bitand(&fmask);
-            d0.normalized_bitor(&d1);
-
-            // d0 *= 2
-            d0.double();
-
-            // d1 = d0
-            d1 = d0;
-
-            // r0 = bit ? d0 : r0
-            r0.normalized_bitand(&fmask);
-            d0.normalized_bitand(&tmask);
-            r0.normalized_bitor(&d0);
-
-            // r1 = bit ? r1 : d1
-            r1.normalized_bitand(&tmask);
-            d1.normalized_bitand(&fmask);
-            r1.normalized_bitor(&d1);
+            let bit = Choice::from(rhs.bit(idx));
+            let sum = r0 + r1;
+            // d = bit ? 2 r1 : 2 r0
+            let d = Self::conditional_select(&r0, &r1, bit).doubled();
+
+            // r0 = bit ? r0 + r1 : 2 r0
+            r0 = Self::conditional_select(&d, &sum, bit);
+            // r1 = bit ? 2 r1 : r0 + r1
+            r1 = Self::conditional_select(&sum, &d, bit);
         }
 
         self.x = r0.x;
@@ -159,23 +301,11 @@ impl<C: EdwardsCurve> Point for EdwardsExtended<C> {
     }
 
     fn double(&mut self) {
-        let a = self.x.squared();
-        let b = self.y.squared();
-        let c = self.z.squared().small_mul(2);
-        let d = a;
-        let e = (self.x + self.y).squared() - a - b;
-        let g = d + b;
-        let f = g - c;
-        let h = d - b;
-        let x3 = e * f;
-        let y3 = g * h;
-        let z3 = f * g;
-        let t3 = e * h;
-
-        self.x = x3;
-        self.y = y3;
-        self.z = z3;
-        self.t = t3;
+        if C::a_val() < 0 {
+            double_a_minus_one(self)
+        } else {
+            double_generic(self)
+        }
     }
 
     fn doubled(&self) -> Self {
@@ -186,14 +316,14 @@ impl<C: EdwardsCurve> Point for EdwardsExtended<C> {
 
     fn triple(&mut self) {
         let yy = self.y.squared();
-        let xx = self.x.squared();
+        let xx = times_a::<C>(self.x.squared());
         let ap = yy + xx;
         let b = (self.z.squared().small_mul(2) - ap).small_mul(2);
         let xb = xx * b;
         let yb = yy * b;
         let aa = ap * (yy - xx);
         let f = aa - yb;
-        let g = aa - xb;
+        let g = aa + xb;
         let xe = self.x * (yb + aa);
         let yh = self.y * (xb - aa);
         let zf = self.z * f;
@@ -215,3 +345,421 @@ impl<C: EdwardsCurve> Point for EdwardsExtended<C> {
         out
     }
 }
+
+/// Compressed Edwards point.  This is the little-endian encoding of
+/// the y-coordinate, with the low-order bit of the x-coordinate stored
+/// in the highest bit of the last byte.  When the field has no bit to
+/// spare in its encoding, as with Ed448, an extra byte is appended to
+/// hold the sign.
+#[derive(Clone)]
+pub struct EdwardsCompressed<C : EdwardsCurve> {
+    bytes : Vec<u8>,
+    phantom : PhantomData<C>
+}
+
+impl<C : EdwardsCurve> EdwardsCompressed<C> {
+    /// Get the number of bytes in a compressed point.
+    pub fn nbytes() -> usize {
+        (C::Field::nbits() + 8) / 8
+    }
+
+    /// Create a compressed point from its byte representation.  This
+    /// does not check whether the bytes describe a valid point;
+    /// decompression does that.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        EdwardsCompressed { bytes: bytes.to_vec(), phantom: PhantomData }
+    }
+
+    /// Get the byte representation.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<C : EdwardsCurve> Compressed<EdwardsExtended<C>> for EdwardsCompressed<C> {
+    fn compress(&mut self, point: EdwardsExtended<C>) {
+        let len = Self::nbytes();
+        let flen = C::Field::nbytes() as usize;
+        let zinv = point.z.inverted();
+        let mut x = point.x * zinv;
+        let mut y = point.y * zinv;
+        let sign = x.bit(0) as u8;
+
+        self.bytes.clear();
+        self.bytes.resize(len, 0);
+        y.pack(&mut self.bytes[0..flen]);
+        self.bytes[len - 1] |= sign << 7;
+    }
+
+    fn compressed(point: EdwardsExtended<C>) -> Self {
+        let mut out = EdwardsCompressed { bytes: Vec::new(),
+                                          phantom: PhantomData };
+
+        out.compress(point);
+        out
+    }
+
+    fn decompress(&self, point: &mut EdwardsExtended<C>)
+                  -> Result<(), Error> {
+        let len = Self::nbytes();
+        let flen = C::Field::nbytes() as usize;
+
+        if self.bytes.len() != len {
+            return Err(Error::InvalidLength);
+        }
+
+        let mut bytes = self.bytes.clone();
+        let sign = bytes[len - 1] >> 7 == 1;
+
+        bytes[len - 1] &= 0x7f;
+
+        // Any bytes past the field element must be zero.
+        if bytes[flen..].iter().any(|b| *b != 0) {
+            return Err(Error::NonCanonical);
+        }
+
+        // Reject encodings of y that are not reduced.
+        let y = C::Field::unpacked_checked(&bytes[0..flen])?;
+        // Solve ax^2 + y^2 = 1 + dx^2 y^2 for x.
+        let yy = y.squared();
+        let u = C::Field::one() - yy;
+        let v = times_a::<C>(C::Field::one()) - (C::d_val() * yy);
+        // Since d/a is not a square, v is never zero.
+        let root = (u * v.inverted()).sqrt_checked();
+
+        if !bool::from(root.is_some()) {
+            return Err(Error::NotOnCurve);
+        }
+
+        let mut x = root.unwrap();
+
+        let zero = x.normalize_self_eq(&C::Field::zero());
+
+        // There is no negative zero.
+        if zero && sign {
+            return Err(Error::NotOnCurve);
+        }
+
+        if x.bit(0) != sign {
+            x = -x;
+        }
+
+        point.init(x, y);
+
+        Ok(())
+    }
+
+    fn decompressed(&self) -> Result<EdwardsExtended<C>, Error> {
+        let mut out = EdwardsExtended::zero();
+
+        self.decompress(&mut out)?;
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::*;
+    use curve::curve1174::Curve1174;
+    use curve::curve41417::Curve41417;
+    use curve::e_222::E222;
+    use curve::e_382::E382;
+    use curve::e_521::E521;
+    use curve::ed25519::Ed25519;
+    use curve::ed448::Ed448;
+    use curve::edwards::*;
+    use curve::group::Group;
+    use error::Error;
+    use field::mod_e222_117::*;
+    use field::mod_e251_9::*;
+    use field::mod_e255_19::*;
+    use field::mod_e382_105::*;
+    use field::mod_e414_17::*;
+    use field::mod_e521_1::*;
+    use field::mod_e448_goldilocks::*;
+    use normalize::NormalizeEq;
+    use pack::Pack;
+    use scalar::e_222::Scalar_E222;
+    use scalar::scalar_field::ScalarField;
+
+    fn base() -> EdwardsExtended<E222> {
+        EdwardsExtended { x: E222_BASE_X, y: E222_BASE_Y,
+                          z: Mod_e222_117::one(),
+                          t: E222_BASE_X * E222_BASE_Y }
+    }
+
+    fn affine_eq(a: &EdwardsExtended<E222>, b: &EdwardsExtended<E222>) -> bool {
+        let mut ax = a.x * b.z;
+        let mut ay = a.y * b.z;
+        let mut bx = b.x * a.z;
+        let mut by = b.y * a.z;
+
+        ax.normalize_eq(&mut bx) && ay.normalize_eq(&mut by)
+    }
+
+    fn mult(p: &EdwardsExtended<E222>, n: u8) -> EdwardsExtended<E222> {
+        let mut out = p.clone();
+
+        out.scalar_mult(&Scalar_E222::unpacked(&[n]));
+        out
+    }
+
+    #[test]
+    fn test_scalar_mult_small() {
+        let b = base();
+        let b2 = b.doubled();
+        let b3 = b2 + b;
+        let b6 = b3.doubled();
+
+        assert!(affine_eq(&EdwardsExtended::zero(), &mult(&b, 0)));
+        assert!(affine_eq(&b, &mult(&b, 1)));
+        assert!(affine_eq(&b2, &mult(&b, 2)));
+        assert!(affine_eq(&b3, &mult(&b, 3)));
+        assert!(affine_eq(&b3, &b.tripled()));
+        assert!(affine_eq(&b6, &mult(&b, 6)));
+        assert!(affine_eq(&b6, &mult(&b2, 3)));
+        assert!(affine_eq(&b6, &mult(&b3, 2)));
+    }
+
+    #[test]
+    fn test_scalar_mult_linear() {
+        let a = Scalar_E222::unpacked(&[ 0x5a, 0x2f, 0x91, 0x03, 0xee, 0x71,
+                                         0x08, 0xc4, 0x3b, 0x66, 0xd2 ]);
+        let b = Scalar_E222::unpacked(&[ 0x17, 0xf0, 0x4c, 0xa8, 0x29, 0x9d,
+                                         0x62, 0x0e, 0xb5, 0x7c, 0x13, 0x44,
+                                         0xe9, 0x81 ]);
+        let mut pa = base();
+        let mut pb = base();
+        let mut pab = base();
+        let mut pprod = base();
+        let mut pl = base();
+        let mut pbpa = pb;
+
+        pa.scalar_mult(&a);
+        pb.scalar_mult(&b);
+        pab.scalar_mult(&(a + b));
+        pprod.scalar_mult(&(a * b));
+        pl.scalar_mult(&(-a));
+        pbpa.scalar_mult(&b);
+        pbpa.scalar_mult(&a);
+
+        assert!(affine_eq(&pab, &(pa + pb)));
+        assert!(affine_eq(&pprod, &pbpa));
+        assert!(affine_eq(&EdwardsExtended::zero(), &(pl + pa)));
+        assert!(!affine_eq(&EdwardsExtended::zero(), &pa));
+
+        let mut one = base();
+
+        one.scalar_mult(&Scalar_E222::one());
+        assert!(affine_eq(&base(), &one));
+    }
+
+    fn check_compress<C: EdwardsCurve>(x: C::Field, y: C::Field) {
+        let b = EdwardsExtended::<C> { x, y, z: C::Field::one(), t: x * y };
+        let points = [ b, b.doubled(), b.tripled(), b.doubled().doubled(),
+                       EdwardsExtended::zero() ];
+
+        for i in 0..5 {
+            let c = EdwardsCompressed::compressed(points[i]);
+            let d = c.decompressed().unwrap();
+            let mut ax = points[i].x * d.z;
+            let mut ay = points[i].y * d.z;
+            let mut bx = d.x * points[i].z;
+            let mut by = d.y * points[i].z;
+
+            assert_eq!(EdwardsCompressed::<C>::nbytes(), c.bytes().len());
+            assert!(ax.normalize_eq(&mut bx));
+            assert!(ay.normalize_eq(&mut by));
+        }
+    }
+
+    #[test]
+    fn test_compress_decompress() {
+        check_compress::<E222>(E222_BASE_X, E222_BASE_Y);
+        check_compress::<Curve1174>(CURVE1174_BASE_X, CURVE1174_BASE_Y);
+        check_compress::<E382>(E382_BASE_X, E382_BASE_Y);
+        check_compress::<Curve41417>(CURVE41417_BASE_X, CURVE41417_BASE_Y);
+        check_compress::<E521>(E521_BASE_X, E521_BASE_Y);
+        check_compress::<Ed448>(ED448_BASE_X, ED448_BASE_Y);
+        check_compress::<Ed25519>(ED25519_BASE_X, ED25519_BASE_Y);
+    }
+
+    #[test]
+    fn test_compress_base() {
+        let mut expected = [0u8; 28];
+
+        expected[0] = 0x1c;
+        expected[27] = 0x80;
+
+        assert_eq!(&expected[..],
+                   EdwardsCompressed::compressed(base()).bytes());
+    }
+
+    #[test]
+    fn test_decompress_invalid() {
+        let mut bytes = [0u8; 28];
+
+        // y = 2 gives a non-square x^2.
+        bytes[0] = 2;
+        assert_eq!(Error::NotOnCurve,
+                   EdwardsCompressed::<E222>::from_bytes(&bytes)
+                       .decompressed().err().unwrap());
+
+        // y = 1 gives x = 0, which has no negative.
+        bytes[0] = 1;
+        assert!(EdwardsCompressed::<E222>::from_bytes(&bytes)
+                    .decompressed().is_ok());
+        bytes[27] = 0x80;
+        assert_eq!(Error::NotOnCurve,
+                   EdwardsCompressed::<E222>::from_bytes(&bytes)
+                       .decompressed().err().unwrap());
+
+        assert_eq!(Error::InvalidLength,
+                   EdwardsCompressed::<E222>::from_bytes(&bytes[0..27])
+                       .decompressed().err().unwrap());
+
+        // p + 1 is a non-canonical encoding of y = 1.
+        let mut bytes = ::field::mod_e222_117::M_ONE.packed();
+
+        bytes[0] += 2;
+        assert_eq!(Error::NonCanonical,
+                   EdwardsCompressed::<E222>::from_bytes(&bytes)
+                       .decompressed().err().unwrap());
+
+        // Bits above the modulus, other than the sign.
+        let mut bytes = [0u8; 28];
+
+        bytes[0] = 1;
+        bytes[27] = 0x40;
+        assert_eq!(Error::NonCanonical,
+                   EdwardsCompressed::<E222>::from_bytes(&bytes)
+                       .decompressed().err().unwrap());
+    }
+
+    #[test]
+    fn test_on_curve() {
+        let b = base();
+        let mut l_base = b;
+        let mut off = b;
+        let mut bad_t = b;
+        let mut checked = EdwardsExtended::<E222>::zero();
+
+        l_base.scalar_mult(&Scalar_E222::modulus());
+        off.init(E222_BASE_X, E222_BASE_Y + Mod_e222_117::one());
+        bad_t.t = Mod_e222_117::one();
+
+        assert!(b.is_on_curve());
+        assert!(b.doubled().is_on_curve());
+        assert!(b.tripled().is_on_curve());
+        assert!((b + b.doubled()).is_on_curve());
+        assert!(l_base.is_on_curve());
+        assert!(EdwardsExtended::<E222>::zero().is_on_curve());
+        assert!(!off.is_on_curve());
+        assert!(!bad_t.is_on_curve());
+
+        assert!(checked.init_checked(E222_BASE_X, E222_BASE_Y).is_ok());
+        assert!(affine_eq(&b, &checked));
+        assert_eq!(Error::NotOnCurve,
+                   checked.init_checked(E222_BASE_X,
+                                        E222_BASE_Y + Mod_e222_117::one())
+                          .err().unwrap());
+        assert!(affine_eq(&b, &checked));
+    }
+
+    #[test]
+    fn test_is_identity() {
+        let b = base();
+        let mut l_base = b;
+        let mut neg = b;
+
+        l_base.scalar_mult(&Scalar_E222::modulus());
+        neg.scalar_mult(&(-Scalar_E222::one()));
+
+        assert!(EdwardsExtended::<E222>::zero().is_identity());
+        assert!(l_base.is_identity());
+        assert!((b + neg).is_identity());
+        assert!(!b.is_identity());
+        assert!(!b.doubled().is_identity());
+    }
+
+    #[test]
+    fn test_conditional() {
+        let b = base();
+        let b2 = b.doubled();
+        let mut neg = b;
+        let mut p = b;
+        let mut q = b2;
+
+        assert!(bool::from(b.ct_eq(&mult(&b, 1))));
+        assert!(bool::from(b2.ct_eq(&(b + b))));
+        assert!(!bool::from(b.ct_eq(&b2)));
+
+        EdwardsExtended::conditional_swap(&mut p, &mut q, Choice::from(0));
+        assert!(bool::from(p.ct_eq(&b)) && bool::from(q.ct_eq(&b2)));
+        EdwardsExtended::conditional_swap(&mut p, &mut q, Choice::from(1));
+        assert!(bool::from(p.ct_eq(&b2)) && bool::from(q.ct_eq(&b)));
+
+        neg.conditional_negate(Choice::from(0));
+        assert!(bool::from(neg.ct_eq(&b)));
+        neg.conditional_negate(Choice::from(1));
+        assert!(neg.is_on_curve());
+        assert!((neg + b).is_identity());
+        assert!(bool::from((-b).ct_eq(&neg)));
+        assert!(bool::from((-neg).ct_eq(&b)));
+    }
+
+    #[test]
+    fn test_a_minus_one() {
+        let b = Ed25519::base();
+        let t = Ed25519::torsion();
+        let points = [ b, b.doubled(), b.tripled(), -b, t, t + b,
+                       EdwardsExtended::zero() ];
+        let mut d2 = Ed25519::d_val().small_mul(2);
+        let mut k = Ed25519::d2_val();
+
+        assert!(d2.normalize_eq(&mut k));
+
+        for p in points.iter() {
+            let mut fast = *p;
+            let mut slow = *p;
+
+            double_a_minus_one(&mut fast);
+            double_generic(&mut slow);
+            assert!(fast.is_on_curve());
+            assert!(bool::from(fast.ct_eq(&slow)));
+
+            for q in points.iter() {
+                let mut fast = *p;
+                let mut slow = *p;
+
+                add_a_minus_one(&mut fast, q);
+                add_generic(&mut slow, q);
+                assert!(fast.is_on_curve());
+                assert!(bool::from(fast.ct_eq(&slow)));
+            }
+        }
+    }
+
+    fn check_torsion<C : EdwardsCurve + Group<EdwardsExtended<C>>>() {
+        let half = C::Scalar::from_bytes_wide(&[ C::cofactor() as u8 / 2 ]);
+        let mut torsion = C::torsion();
+
+        // The torsion point has order exactly the cofactor.
+        assert!(torsion.is_on_curve());
+        torsion.scalar_mult(&half);
+        assert!(!torsion.is_identity());
+        assert!(torsion.doubled().is_identity());
+    }
+
+    #[test]
+    fn test_torsion() {
+        check_torsion::<E222>();
+        check_torsion::<Curve1174>();
+        check_torsion::<Ed25519>();
+        check_torsion::<E382>();
+        check_torsion::<Curve41417>();
+        check_torsion::<Ed448>();
+        check_torsion::<E521>();
+    }
+}
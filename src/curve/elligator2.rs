@@ -0,0 +1,353 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::CtOption;
+use curve::montgomery::*;
+use error::Error;
+use field::prime_field::PrimeField;
+use pack::Pack;
+use rand::Rng;
+use scalar::scalar_field::ScalarField;
+
+/// Montgomery curves with an Elligator 2 map.
+pub trait Elligator2Curve : MontgomeryCurve {
+    /// The u-coordinate of the base point.
+    fn base_x() -> Self::Field;
+
+    /// The u-coordinate of the base point plus a point of order 8.
+    /// This generates the whole group, whose order is 8 times that of
+    /// the base point on all of these curves.
+    fn dirty_base_x() -> Self::Field;
+
+    /// The non-square Z used by the map.  The map also needs -1 to be
+    /// a square, so the default of 2 works whenever p = 5 mod 8.
+    fn non_square() -> i32 { 2 }
+}
+
+/// The Elligator 2 map of Bernstein, Hamburg, Krasnova and Lange,
+/// which takes any field element r to the u-coordinate of a point on
+/// the curve.  The elements r and -r give the same point.
+pub fn elligator2<C : Elligator2Curve>(r: &C::Field) -> C::Field {
+    let one = C::Field::one();
+    let a = C::a_val();
+    // Since Z is a non-square and -1 is a square, 1 + Zr^2 is never
+    // zero.
+    let x1 = -a * (one + r.squared().small_mul(C::non_square())).inverted();
+    let x2 = -x1 - a;
+    let g1 = ((x1 + a) * x1 + one) * x1;
+
+    // Exactly one of x1 and x2 is on the curve.
+    C::Field::conditional_select(&x2, &x1, g1.sqrt_checked().is_some())
+}
+
+/// The inverse of the Elligator 2 map.  This gives a representative r
+/// of the u-coordinate, with 0 <= r <= (p - 1)/2, if it has one.
+/// About half of all points have a representative.
+pub fn elligator2_inverse<C : Elligator2Curve>(u: &C::Field)
+                                               -> CtOption<C::Field> {
+    // Solve u = -A/(1 + Zr^2) for r.
+    let ratio = -(*u + C::a_val()) * u.small_mul(C::non_square()).inverted();
+    let mut r = ratio.sqrt();
+    let square = r.squared().ct_eq(&ratio);
+    // Take -r instead of r if r > (p - 1)/2, which is exactly when 2r
+    // wraps around the modulus and becomes odd.
+    let negative = Choice::from(r.small_mul(2).bit(0));
+
+    r.conditional_negate(negative);
+
+    // This fails for points on the twist, where the map gives the
+    // other candidate instead.
+    let valid = square & elligator2::<C>(&r).ct_eq(u);
+
+    CtOption::new(r, valid)
+}
+
+/// Encode a u-coordinate as bytes that are indistinguishable from
+/// random, if it has an Elligator 2 representative.  The bits above
+/// the representative are filled from rng.
+pub fn elligator2_encode<C : Elligator2Curve, R : Rng>(u: &C::Field,
+                                                       rng: &mut R)
+                                                       -> CtOption<Vec<u8>> {
+    let repr = elligator2_inverse::<C>(u);
+    let is_some = repr.is_some();
+    let mut r = repr.unwrap_or(C::Field::zero());
+    let len = C::Field::nbytes() as usize;
+    let top = C::Field::nbits() - 1;
+    let mut out = vec![0u8; len];
+
+    r.pack(&mut out);
+
+    out[top / 8] |= rng.gen::<u8>() & (0xff << (top % 8));
+
+    for byte in out[top / 8 + 1 ..].iter_mut() {
+        *byte = rng.gen();
+    }
+
+    CtOption::new(out, is_some)
+}
+
+/// Decode bytes produced by `elligator2_encode` into a u-coordinate.
+/// Every string of the right length decodes to some point, and the
+/// bits above the representative are ignored.
+pub fn elligator2_decode<C : Elligator2Curve>(bytes: &[u8])
+                                              -> Result<C::Field, Error> {
+    let len = C::Field::nbytes() as usize;
+    let top = C::Field::nbits() - 1;
+
+    if bytes.len() != len {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut masked = bytes.to_vec();
+
+    masked[top / 8] &= (1 << (top % 8)) - 1;
+
+    for byte in masked[top / 8 + 1 ..].iter_mut() {
+        *byte = 0;
+    }
+
+    Ok(elligator2::<C>(&C::Field::unpacked(&masked)))
+}
+
+/// Get the integer below 8 times the group order which is congruent
+/// to secret mod the group order and to low mod 8, as little-endian
+/// bytes.
+fn dirty_scalar<C : Elligator2Curve>(secret: &C::Scalar, low: u8)
+                                     -> Vec<u8> {
+    let len = C::Scalar::nbytes() as usize;
+    let mut out = vec![0u8; len + 1];
+    let mut order = vec![0u8; len];
+
+    secret.pack_normalized(&mut out[0..len]);
+    C::Scalar::modulus().pack(&mut order);
+
+    // The order is odd, so it is its own inverse mod 8, and adding m
+    // times the order changes secret mod 8 to low.
+    let m = ((low.wrapping_sub(out[0]) & 7).wrapping_mul(order[0]) & 7) as u16;
+    let mut carry = 0u16;
+
+    for (byte, o) in out.iter_mut().zip(order.iter()) {
+        let sum = (*byte as u16) + m * (*o as u16) + carry;
+
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+
+    out[len] = carry as u8;
+    out
+}
+
+/// Generate a key pair whose public key has an Elligator 2
+/// representative, retrying with fresh secrets until one does.  This
+/// returns the secret scalar, the public u-coordinate and its encoded
+/// representative.
+///
+/// A public key made as secret times the base point is always in the
+/// prime-order subgroup, which an observer who multiplies by the group
+/// order could use to tell it from random.  So this adds a random
+/// point of order dividing 8, as the decoding of a random string would
+/// have.  Peers must clear the cofactor when doing Diffie-Hellman with
+/// this key, by multiplying by 8 or using a secret that is a multiple
+/// of 8, or the result will depend on the low-order point.
+pub fn generate<C : Elligator2Curve, R : Rng>(rng: &mut R)
+                                              -> (C::Scalar, C::Field,
+                                                  Vec<u8>) {
+    loop {
+        let secret: C::Scalar = rng.gen();
+        let scalar = dirty_scalar::<C>(&secret, rng.gen::<u8>() & 7);
+        let mut point = MontgomeryX::<C>::new(C::dirty_base_x());
+
+        point.ladder(&scalar, C::Scalar::nbits() + 3);
+
+        let public = point.affine_x();
+        let repr = elligator2_encode::<C, R>(&public, rng);
+
+        if bool::from(repr.is_some()) {
+            return (secret, public, repr.unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::curve25519::Curve25519;
+    use curve::curve383187::Curve383187;
+    use curve::elligator2::*;
+    use curve::m_221::M221;
+    use curve::m_383::M383;
+    use curve::m_511::M511;
+    use normalize::NormalizeEq;
+    use rand::thread_rng;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    fn check_vector<C : Elligator2Curve>(r: &str, u: &str, base: &str) {
+        let mut r = C::Field::unpacked(&from_hex(r));
+        let mut u = C::Field::unpacked(&from_hex(u));
+        let repr = elligator2_inverse::<C>(&u);
+
+        assert!(u.normalize_eq(&mut elligator2::<C>(&r)));
+        assert!(u.normalize_eq(&mut elligator2::<C>(&-r)));
+        assert!(u.normalize_eq(&mut elligator2::<C>(&repr.unwrap())));
+
+        let base_repr = elligator2_inverse::<C>(&C::base_x());
+
+        if base.is_empty() {
+            assert!(bool::from(base_repr.is_none()));
+        } else {
+            r = C::Field::unpacked(&from_hex(base));
+            assert!(r.normalize_eq(&mut base_repr.unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_vectors() {
+        check_vector::<Curve25519>(
+            "05121f2c394653606d7a8794a1aebbc8\
+             d5e2effc091623303d4a5764717e8b18",
+            "150fc5f9e2bc3e25b0d86f036bc9a5b4\
+             d19aa5b48e6007fb45ebf24d22d6052e",
+            "b9762dadc1db2944f08aeb419d76f6b1\
+             9e66fd47ec1076dfe7a7a1c4e0f0a92b");
+        check_vector::<M221>(
+            "05121f2c394653606d7a8794a1aebbc8d5e2effc091623303d4a5704",
+            "745c294e7e22cf49b0025f29cf529dec01315f6c1e6452732bbd0006",
+            "");
+        check_vector::<M383>(
+            "05121f2c394653606d7a8794a1aebbc8d5e2effc091623303d4a5764\
+             717e8b98a5b2bfccd9e6f3000d1a2734414e5b28",
+            "2ef50cf81b5de8b2733808e75ac82fa6b33e19e01dfac315d27074dc\
+             e292b84a4e1e5a08e7928e3559007b2e3198975c",
+            "d1c6175684398bc16f375294ff6cb73b7c072b578e7a76964ca75478\
+             d18a3c88b70688ee0b3a092db98d778b59eabe30");
+        check_vector::<Curve383187>(
+            "05121f2c394653606d7a8794a1aebbc8d5e2effc091623303d4a5764\
+             717e8b98a5b2bfccd9e6f3000d1a2734414e5b28",
+            "cd2aea0c80c2f3cbd9372ab754227370d82d5ea47b9e595b63228449\
+             4389de33ce49f5d8a9a4b4a74c3f443575e09c32",
+            "653283b35b2c314a34fbc9dce68bb3d50698ef2f1935df72f8bab1b9\
+             351e2a54978dd12f2feec13812ac0fca34fbfd14");
+        check_vector::<M511>(
+            "05121f2c394653606d7a8794a1aebbc8d5e2effc091623303d4a5764\
+             717e8b98a5b2bfccd9e6f3000d1a2734414e5b6875828f9ca9b6c3d0\
+             ddeaf704111e2b38",
+            "1fc0a9886b5cec3915ff38c04cb40167fcc4e2cc848976d366dfdbc6\
+             fd38ecf51b61643caa569fcb1c6504a860529e30fcb5eabd0e926745\
+             bb8a41d26ea7182b",
+            "249d0ab3effab8e6489c0eceaa330d3e4eb5df3b24956e5edf21bbf2\
+             71bef97ee4e063a0bd7c91e0d017a66c4d66ae47ea26cc1d908219ca\
+             3af6c713980f5e38");
+    }
+
+    fn check_round_trip<C : Elligator2Curve>() {
+        let mut rng = thread_rng();
+        let len = C::Field::nbytes() as usize;
+
+        for _ in 0..16 {
+            let r: C::Field = rng.gen();
+            let mut u = elligator2::<C>(&r);
+            let bytes = elligator2_encode::<C, _>(&u, &mut rng).unwrap();
+            let repr = elligator2_inverse::<C>(&u).unwrap();
+
+            // The representative is at most (p - 1)/2.
+            assert!(!repr.small_mul(2).bit(0));
+            assert_eq!(len, bytes.len());
+            assert!(u.normalize_eq(&mut elligator2_decode::<C>(&bytes)
+                                            .unwrap()));
+        }
+
+        assert_eq!(Some(Error::InvalidLength),
+                   elligator2_decode::<C>(&vec![0; len + 1]).err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        check_round_trip::<Curve25519>();
+        check_round_trip::<M221>();
+        check_round_trip::<M383>();
+        check_round_trip::<Curve383187>();
+        check_round_trip::<M511>();
+    }
+
+    #[test]
+    fn test_decode_ignores_padding() {
+        let mut bytes = from_hex("05121f2c394653606d7a8794a1aebbc8\
+                                  d5e2effc091623303d4a5764717e8b18");
+        let mut u = elligator2_decode::<Curve25519>(&bytes).unwrap();
+
+        bytes[31] |= 0xc0;
+        assert!(u.normalize_eq(&mut elligator2_decode::<Curve25519>(&bytes)
+                                        .unwrap()));
+    }
+
+    fn check_generate<C : Elligator2Curve>() {
+        let mut rng = thread_rng();
+        let (secret, mut public, repr) = generate::<C, _>(&mut rng);
+        let mut point = MontgomeryX::<C>::new(C::base_x());
+        let mut cleared = MontgomeryX::<C>::new(public);
+
+        assert!(public.normalize_eq(&mut elligator2_decode::<C>(&repr)
+                                             .unwrap()));
+
+        // Clearing the cofactor removes the low-order point.
+        point.scalar_mult(&secret);
+        point.ladder(&[ 8 ], 4);
+        cleared.ladder(&[ 8 ], 4);
+        assert!(cleared.affine_x().normalize_eq(&mut point.affine_x()));
+    }
+
+    #[test]
+    fn test_generate() {
+        check_generate::<Curve25519>();
+        check_generate::<M221>();
+        check_generate::<M383>();
+        check_generate::<Curve383187>();
+        check_generate::<M511>();
+    }
+
+    fn check_not_in_subgroup<C : Elligator2Curve>() {
+        let mut rng = thread_rng();
+        let mut order = vec![0u8; C::Scalar::nbytes() as usize];
+        let mut base = MontgomeryX::<C>::new(C::dirty_base_x());
+        let mut outside = 0;
+
+        C::Scalar::modulus().pack(&mut order);
+
+        // The order times the dirty base point has order 8, so twice
+        // that is a point of order 4, which has a non-zero u.
+        base.ladder(&order, C::Scalar::nbits());
+        base.ladder(&[ 2 ], 2);
+        assert!(!base.affine_x().normalize_eq(&mut C::Field::zero()));
+
+        // A decoded key multiplied by the order is the identity or the
+        // point of order 2, both with u = 0, only if the low-order part
+        // is 0 or 4, which happens a quarter of the time.
+        for _ in 0..16 {
+            let (_, _, repr) = generate::<C, _>(&mut rng);
+            let u = elligator2_decode::<C>(&repr).unwrap();
+            let mut point = MontgomeryX::<C>::new(u);
+
+            point.ladder(&order, C::Scalar::nbits());
+
+            if !point.affine_x().normalize_eq(&mut C::Field::zero()) {
+                outside += 1;
+            }
+        }
+
+        assert!(outside > 0);
+    }
+
+    #[test]
+    fn test_generate_not_in_subgroup() {
+        check_not_in_subgroup::<Curve25519>();
+        check_not_in_subgroup::<M221>();
+        check_not_in_subgroup::<M383>();
+        check_not_in_subgroup::<Curve383187>();
+        check_not_in_subgroup::<M511>();
+    }
+}
@@ -0,0 +1,429 @@
+use choice::Choice;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_u32;
+use curve::edwards::*;
+use curve::group::Group;
+use curve::hash_to_curve::map_to_montgomery;
+use curve::hash_to_curve::montgomery_to_edwards;
+use curve::point::Point;
+use error::Error;
+use field::prime_field::PrimeField;
+use pack::Pack;
+use rand::Rng;
+
+/// The parameters of Elligator 2 on the Montgomery form of an Edwards
+/// curve, Kt^2 = s^3 + Js^2 + s, with J = 2(a + d)/(a - d) and
+/// K = 4/(a - d).  These depend only on the curve.
+#[derive(Copy, Clone)]
+pub struct Params<F> {
+    j: F,
+    k: F,
+    j_over_k: F,
+    z: i32
+}
+
+/// Edwards curves that can use Elligator Squared.
+pub trait ElligatorSquaredCurve : EdwardsCurve {
+    /// Get the parameters of the map.  Finding them takes inversions
+    /// and a search for a non-square, so by default this redoes that
+    /// on every call, but curves can override it to cache them.
+    fn elligator_squared_params() -> Params<Self::Field> {
+        Params::new::<Self>()
+    }
+}
+
+impl<F : PrimeField + Copy> Params<F> {
+    /// Compute the parameters for a curve.
+    pub fn new<C : EdwardsCurve<Field = F>>() -> Self {
+        let one = F::one();
+        let a = one.small_mul(C::a_val());
+        let a_minus_d_inv = (a - C::d_val()).inverted();
+        let j = (a + C::d_val()).small_mul(2) * a_minus_d_inv;
+        let k = one.small_mul(4) * a_minus_d_inv;
+        // Take the first of -1, 2, -2, 3, ... that is not a square.
+        let mut z = -1;
+
+        while bool::from(one.small_mul(z).sqrt_checked().is_some()) {
+            z = if z < 0 { 1 - z } else { -z };
+        }
+
+        Params { j, k, j_over_k: j * k.inverted(), z }
+    }
+
+    fn map<C : EdwardsCurve<Field = F>>(&self, u: &F) -> EdwardsExtended<C> {
+        let (s, t) = map_to_montgomery(u, &self.j, &self.k, self.z);
+
+        montgomery_to_edwards(&s, &t, &F::one())
+    }
+
+    /// Get the four candidates for preimages of a point under `map`,
+    /// each with whether it really is one.  Repeated preimages are
+    /// only counted once.
+    fn preimages<C : EdwardsCurve<Field = F>>(&self,
+                                              point: &EdwardsExtended<C>)
+                                              -> [(F, Choice); 4] {
+        // Go to the Montgomery curve, scaled by 1/K to match the map,
+        // with s = (1 + y)/(1 - y).
+        let (_, y, z, _) = point.extended();
+        let x = (z + y) * ((z - y) * self.k).inverted();
+        let n = x + self.j_over_k;
+        // Either x = -(J/K)/(1 + Zu^2), or that is -x - J/K.  Solve
+        // each for u^2.
+        let first = (-n * x.small_mul(self.z).inverted()).sqrt();
+        let second = (-x * n.small_mul(self.z).inverted()).sqrt();
+        let mut out = [(first, Choice::from(1u8)),
+                       (-first, Choice::from(1u8)),
+                       (second, Choice::from(1u8)),
+                       (-second, Choice::from(1u8))];
+
+        for i in 0..4 {
+            let mut valid = self.map::<C>(&out[i].0).ct_eq(point);
+
+            for j in 0..i {
+                valid = valid & !(out[j].1 & out[j].0.ct_eq(&out[i].0));
+            }
+
+            out[i].1 = valid;
+        }
+
+        out
+    }
+}
+
+/// The Elligator Squared map, from Tibouchi's "Elligator Squared:
+/// Uniform Points on Elliptic Curves of Prime Order as Uniform Random
+/// Strings".  This takes a pair of field elements (u, v) to
+/// f(u) + f(v), where f is Elligator 2 on the Montgomery form of the
+/// curve.  Every pair gives a point, and for uniformly random pairs,
+/// the point is uniformly distributed.
+pub fn elligator_squared<C>(u: &C::Field, v: &C::Field) -> EdwardsExtended<C>
+    where C: ElligatorSquaredCurve {
+    let params = C::elligator_squared_params();
+
+    params.map::<C>(u) + params.map::<C>(v)
+}
+
+/// The inverse of the Elligator Squared map, which picks a random
+/// preimage (u, v) of a point.  For a random point, the pair is
+/// uniformly distributed, and unlike Elligator 1 and 2, this works
+/// for every point.
+///
+/// This picks u at random and tries again until P - f(u) has a
+/// preimage under f.  Each attempt is constant-time, but the number
+/// of attempts varies, and is four on average.
+pub fn elligator_squared_inverse<C, R>(point: &EdwardsExtended<C>,
+                                       rng: &mut R) -> (C::Field, C::Field)
+    where C: ElligatorSquaredCurve,
+          R: Rng {
+    let params = C::elligator_squared_params();
+
+    loop {
+        let u: C::Field = rng.gen();
+        let rest = -params.map::<C>(&u) + *point;
+
+        // Pick one of the four candidates at random, so that each
+        // preimage of the rest of the point is equally likely.
+        let idx = rng.gen::<u8>() & 3;
+        let mut v = C::Field::zero();
+        let mut found = Choice::from(0u8);
+
+        for (i, &(cand, valid)) in params.preimages(&rest).iter()
+                                         .enumerate() {
+            let pick = is_zero_u32(i as u32 ^ idx as u32);
+
+            v = C::Field::conditional_select(&v, &cand, pick);
+            found = found | (pick & valid);
+        }
+
+        if bool::from(found) {
+            return (u, v);
+        }
+    }
+}
+
+/// Get the number of bytes in an encoding.
+pub fn elligator_squared_nbytes<C : EdwardsCurve>() -> usize {
+    2 * C::Field::nbytes() as usize
+}
+
+/// Encode a point as bytes that are indistinguishable from random,
+/// for a random point.  The bits above each field element are filled
+/// from rng.
+pub fn elligator_squared_encode<C, R>(point: &EdwardsExtended<C>,
+                                      rng: &mut R) -> Vec<u8>
+    where C: ElligatorSquaredCurve,
+          R: Rng {
+    let (mut u, mut v) = elligator_squared_inverse(point, rng);
+    let len = C::Field::nbytes() as usize;
+    let bits = C::Field::nbits();
+    let mut out = vec![0u8; 2 * len];
+
+    u.pack(&mut out[0..len]);
+    v.pack(&mut out[len..2 * len]);
+
+    for half in out.chunks_mut(len) {
+        if bits % 8 != 0 {
+            half[bits / 8] |= rng.gen::<u8>() & (0xff << (bits % 8));
+        }
+    }
+
+    out
+}
+
+/// Decode bytes produced by `elligator_squared_encode` into a point.
+/// Every string of the right length decodes to some point, and the
+/// bits above each field element are ignored.
+pub fn elligator_squared_decode<C>(bytes: &[u8])
+                                   -> Result<EdwardsExtended<C>, Error>
+    where C: ElligatorSquaredCurve {
+    let len = C::Field::nbytes() as usize;
+    let bits = C::Field::nbits();
+
+    if bytes.len() != 2 * len {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut masked = bytes.to_vec();
+
+    for half in masked.chunks_mut(len) {
+        if bits % 8 != 0 {
+            half[bits / 8] &= (1 << (bits % 8)) - 1;
+        }
+    }
+
+    let u = C::Field::unpacked(&masked[0..len]);
+    let v = C::Field::unpacked(&masked[len..2 * len]);
+
+    Ok(elligator_squared::<C>(&u, &v))
+}
+
+/// Generate a key pair along with an encoding of the public key.
+/// Unlike with Elligator 2, every public key can be encoded, so this
+/// never needs to retry.  This returns the secret scalar, the public
+/// point and its encoding.
+///
+/// The encoding is only uniform for points that are uniform over the
+/// whole curve, but secret times the base point is always in the
+/// prime-order subgroup, which an observer who multiplies by the group
+/// order could detect.  So the public point also has a random point
+/// of low order added.  Peers must clear the cofactor when doing
+/// Diffie-Hellman with this key, or the result will depend on it.
+pub fn generate<C, R>(rng: &mut R) -> (C::Scalar, EdwardsExtended<C>, Vec<u8>)
+    where C: ElligatorSquaredCurve + Group<EdwardsExtended<C>>,
+          R: Rng {
+    let secret: C::Scalar = rng.gen();
+    let cofactor = C::cofactor() as u32;
+    // The cofactor is a power of 2, so this is uniform.
+    let k = rng.gen::<u32>() % cofactor;
+    let torsion = C::torsion();
+    let mut multiple = EdwardsExtended::zero();
+    let mut low = EdwardsExtended::zero();
+
+    for i in 1..cofactor {
+        multiple += &torsion;
+        low.conditional_assign(&multiple, is_zero_u32(i ^ k));
+    }
+
+    let public = C::base_mult(&secret) + low;
+    let bytes = elligator_squared_encode(&public, rng);
+
+    (secret, public, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::curve1174::Curve1174;
+    use curve::curve41417::Curve41417;
+    use curve::e_222::E222;
+    use curve::e_382::E382;
+    use curve::e_521::E521;
+    use curve::ed25519::Ed25519;
+    use curve::ed448::Ed448;
+    use curve::elligator_squared::*;
+    use curve::point::Point;
+    use rand::thread_rng;
+    use scalar::scalar_field::ScalarField;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    fn check_vector<C : ElligatorSquaredCurve>(x: &str, y: &str) {
+        let bytes: Vec<u8> = (0..elligator_squared_nbytes::<C>())
+                                 .map(|i| (i * 7 + 3) as u8).collect();
+        let point = elligator_squared_decode::<C>(&bytes).unwrap();
+        let (px, py, pz, _) = point.extended();
+        let z_inv = pz.inverted();
+        let mut x_bytes = from_hex(x);
+        let mut y_bytes = from_hex(y);
+        let mut out = vec![0; C::Field::nbytes() as usize];
+
+        // The vectors are big-endian.
+        x_bytes.reverse();
+        y_bytes.reverse();
+        assert!(point.is_on_curve());
+        (px * z_inv).pack(&mut out);
+        assert_eq!(x_bytes, out);
+        (py * z_inv).pack(&mut out);
+        assert_eq!(y_bytes, out);
+    }
+
+    #[test]
+    fn test_vectors() {
+        check_vector::<E222>("1b524999681e3b7f88c8dc3a093fa4c7\
+                              01f1ca8b9f7f1295ad7e3d66",
+                             "279611e3cc32583339e4758e327ef1e4\
+                              9a4d705cf90a5e0dffb57b57");
+        check_vector::<Ed25519>("1e2533be59045fb0a3d828124bda6404\
+                                 d2a5a0e7e645013d5ca313594f22f9ec",
+                                "24bfce0b4b13cf258fd3a950dd4ad66f\
+                                 fe19dc7cd8877eadcb00df352e56162e");
+        check_vector::<Ed448>("f6191c48c0f5d0f7cc926805be51401a\
+                               91d5fb935fc203ad1b4588a98b9db4d2\
+                               09b2637bc4c96910f6c83cb22886f71d\
+                               af627a225f727377",
+                              "f3f8289aa2019f5db4b3d7cf9dce0a5f\
+                               28c9c33961f4658552af80cb1877845a\
+                               a9166bc15faf7a2db50f5c736b35c219\
+                               16b70f8b2ad6e887");
+    }
+
+    fn check_preimages<C : ElligatorSquaredCurve>() {
+        let mut rng = thread_rng();
+        let params = C::elligator_squared_params();
+        let fresh = Params::new::<C>();
+
+        // The cached parameters are the ones the curve gives.
+        assert!(bool::from(params.j.ct_eq(&fresh.j)));
+        assert!(bool::from(params.k.ct_eq(&fresh.k)));
+        assert_eq!(fresh.z, params.z);
+
+        for _ in 0..8 {
+            let r: C::Field = rng.gen();
+            let preimages = params.preimages(&params.map::<C>(&r));
+            let found = preimages.iter().filter(|&&(u, valid)| {
+                bool::from(valid & (u.ct_eq(&r) | u.ct_eq(&-r)))
+            });
+
+            assert_eq!(2, found.count());
+        }
+    }
+
+    #[test]
+    fn test_preimages() {
+        check_preimages::<E222>();
+        check_preimages::<Curve1174>();
+        check_preimages::<Ed25519>();
+        check_preimages::<E382>();
+        check_preimages::<Curve41417>();
+        check_preimages::<Ed448>();
+        check_preimages::<E521>();
+    }
+
+    fn check_round_trip<C : ElligatorSquaredCurve>() {
+        let mut rng = thread_rng();
+        let len = elligator_squared_nbytes::<C>();
+        let r: C::Field = rng.gen();
+        // Include a point with a torsion component, and the identity.
+        let points = [C::elligator_squared_params().map::<C>(&r),
+                      EdwardsExtended::zero()];
+
+        for point in points.iter() {
+            let bytes = elligator_squared_encode(point, &mut rng);
+            let decoded = elligator_squared_decode::<C>(&bytes).unwrap();
+
+            assert_eq!(len, bytes.len());
+            assert!(bool::from(decoded.ct_eq(point)));
+        }
+
+        assert_eq!(Some(Error::InvalidLength),
+                   elligator_squared_decode::<C>(&vec![0; len - 1]).err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        check_round_trip::<E222>();
+        check_round_trip::<Curve1174>();
+        check_round_trip::<Ed25519>();
+        check_round_trip::<E382>();
+        check_round_trip::<Curve41417>();
+        check_round_trip::<Ed448>();
+        check_round_trip::<E521>();
+    }
+
+    #[test]
+    fn test_decode_ignores_padding() {
+        let mut rng = thread_rng();
+        let point = Ed25519::base();
+        let mut bytes = elligator_squared_encode(&point, &mut rng);
+
+        bytes[31] |= 0x80;
+        bytes[63] |= 0x80;
+        assert!(bool::from(elligator_squared_decode::<Ed25519>(&bytes)
+                               .unwrap().ct_eq(&point)));
+    }
+
+    fn check_generate<C>()
+        where C: ElligatorSquaredCurve + Group<EdwardsExtended<C>> {
+        let mut rng = thread_rng();
+        let (secret, public, bytes) = generate::<C, _>(&mut rng);
+        let cofactor = C::Scalar::from_bytes_wide(&[ C::cofactor() as u8 ]);
+        let mut point = C::base();
+        let mut cleared = public;
+
+        assert!(bool::from(elligator_squared_decode::<C>(&bytes).unwrap()
+                               .ct_eq(&public)));
+
+        // Clearing the cofactor removes the low-order point.
+        point.scalar_mult(&(secret * cofactor));
+        cleared.scalar_mult(&cofactor);
+        assert!(bool::from(point.ct_eq(&cleared)));
+    }
+
+    #[test]
+    fn test_generate() {
+        check_generate::<E222>();
+        check_generate::<Curve1174>();
+        check_generate::<Ed25519>();
+        check_generate::<E382>();
+        check_generate::<Curve41417>();
+        check_generate::<Ed448>();
+        check_generate::<E521>();
+    }
+
+    fn check_not_in_subgroup<C>()
+        where C: ElligatorSquaredCurve + Group<EdwardsExtended<C>> {
+        let mut rng = thread_rng();
+        let mut outside = 0;
+
+        // A key in the prime-order subgroup would give the identity
+        // every time, but here that happens with probability only one
+        // over the cofactor.
+        for _ in 0..16 {
+            let (_, _, bytes) = generate::<C, _>(&mut rng);
+            let mut point = elligator_squared_decode::<C>(&bytes).unwrap();
+
+            point.scalar_mult(&C::order());
+
+            if !point.is_identity() {
+                outside += 1;
+            }
+        }
+
+        assert!(outside > 0);
+    }
+
+    #[test]
+    fn test_generate_not_in_subgroup() {
+        check_not_in_subgroup::<E222>();
+        check_not_in_subgroup::<Curve1174>();
+        check_not_in_subgroup::<Ed25519>();
+        check_not_in_subgroup::<E382>();
+        check_not_in_subgroup::<Curve41417>();
+        check_not_in_subgroup::<Ed448>();
+        check_not_in_subgroup::<E521>();
+    }
+}
@@ -1,5 +1,4 @@
 use curve::point::Point;
-use field::prime_field::PrimeField;
 
 pub trait Group<P : Point> {
     fn base() -> P;
@@ -7,4 +6,17 @@ pub trait Group<P : Point> {
     fn order() -> P::Scalar;
 
     fn cofactor() -> i32;
+
+    /// A point whose order is the cofactor.  Its multiples are all the
+    /// points of low order.
+    fn torsion() -> P;
+
+    /// Multiply the base point by a scalar.  By default this uses the
+    /// ladder, but curves can override it with a precomputed table.
+    fn base_mult(scalar: &P::Scalar) -> P {
+        let mut out = Self::base();
+
+        out.scalar_mult(scalar);
+        out
+    }
 }
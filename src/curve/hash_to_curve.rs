@@ -0,0 +1,457 @@
+use choice::Choice;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use curve::edwards::*;
+use curve::group::Group;
+use curve::point::Point;
+use error::Error;
+use field::prime_field::PrimeField;
+use hash::expand_message::expand_message_xmd;
+
+/// Edwards curves with hash-to-curve suites in the style of RFC 9380.
+/// The defaults give the generic Elligator 2 suite with
+/// expand_message_xmd and SHA-512, which needs p = 3 mod 4; curves
+/// with a suite defined in the RFC override them to match it.
+pub trait HashToCurve : EdwardsCurve + Group<EdwardsExtended<Self>> {
+    /// The target security level k, in bits.  Each field element is
+    /// reduced from ceil((log2(p) + k)/8) bytes.
+    fn security_bits() -> usize {
+        <Self as EdwardsCurve>::Field::nbits() / 2
+    }
+
+    /// Expand a message to len pseudorandom bytes.
+    fn expand_message(msg: &[u8], dst: &[u8], len: usize)
+                      -> Result<Vec<u8>, Error> {
+        expand_message_xmd(msg, dst, len)
+    }
+
+    /// Map a field element to a point on the curve, which need not be
+    /// in the prime-order subgroup.  The default is Elligator 2 with
+    /// Z = -1 on the birationally equivalent Montgomery curve, as in
+    /// RFC 9380 section 6.8.2.
+    fn map_to_curve(u: &Self::Field) -> EdwardsExtended<Self> {
+        let one = Self::Field::one();
+        let a = one.small_mul(Self::a_val());
+        let a_minus_d_inv = (a - Self::d_val()).inverted();
+        let j = (a + Self::d_val()).small_mul(2) * a_minus_d_inv;
+        let k = one.small_mul(4) * a_minus_d_inv;
+        let (s, t) = map_to_montgomery(u, &j, &k, -1);
+
+        montgomery_to_edwards(&s, &t, &one)
+    }
+}
+
+/// The Elligator 2 map from RFC 9380 section 6.7.1, onto the
+/// Montgomery curve Kt^2 = s^3 + Js^2 + s with the non-square Z.
+/// This returns (s, t), with the sign of t fixed by which of the two
+/// candidates for s was taken.
+pub fn map_to_montgomery<F : PrimeField + Copy>(u: &F, j: &F, k: &F, z: i32)
+                                                -> (F, F) {
+    let k_inv = k.inverted();
+    let j_over_k = *j * k_inv;
+    let k_inv_sq = k_inv.squared();
+    // Inverting zero gives zero, which inv0 requires.
+    let x1 = -j_over_k * (F::one() + u.squared().small_mul(z)).inverted();
+    let x1 = F::conditional_select(&x1, &-j_over_k, x1.ct_eq(&F::zero()));
+    let x2 = -x1 - j_over_k;
+    let gx1 = ((x1 + j_over_k) * x1 + k_inv_sq) * x1;
+    let gx2 = ((x2 + j_over_k) * x2 + k_inv_sq) * x2;
+    let square = gx1.sqrt_checked().is_some();
+    let x = F::conditional_select(&x2, &x1, square);
+    let mut y = F::conditional_select(&gx2, &gx1, square).sqrt();
+    // y is odd when the first candidate is taken, and even otherwise.
+    let wrong_sign = Choice::from(y.bit(0)) ^ square;
+
+    y.conditional_negate(wrong_sign);
+
+    (x * *k, y * *k)
+}
+
+/// The rational map (s, t) -> (c s/t, (s - 1)/(s + 1)) from a
+/// Montgomery curve to an Edwards curve.  The exceptional points
+/// with t = 0 or s = -1 go to the identity.
+pub fn montgomery_to_edwards<C : EdwardsCurve>(s: &C::Field, t: &C::Field,
+                                               c: &C::Field)
+                                               -> EdwardsExtended<C> {
+    let one = C::Field::one();
+    let s_plus_one = *s + one;
+    let s_minus_one = *s - one;
+    let cs = *c * *s;
+    let z = *t * s_plus_one;
+    let point = EdwardsExtended::from_extended(cs * s_plus_one,
+                                               s_minus_one * *t, z,
+                                               cs * s_minus_one);
+
+    EdwardsExtended::conditional_select(&point, &EdwardsExtended::zero(),
+                                        z.ct_eq(&C::Field::zero()))
+}
+
+/// Multiply by the cofactor, which is a power of two for every
+/// Edwards curve here, to get a point in the prime-order subgroup.
+pub fn clear_cofactor<C : HashToCurve>(point: &EdwardsExtended<C>)
+                                       -> EdwardsExtended<C> {
+    let mut out = *point;
+    let mut h = C::cofactor();
+
+    while h > 1 {
+        out.double();
+        h >>= 1;
+    }
+
+    out
+}
+
+/// The hash_to_field function from RFC 9380, which hashes a message
+/// to count field elements.  Each element is a big-endian chunk of
+/// the expanded message, reduced modulo p.
+pub fn hash_to_field<C : HashToCurve>(msg: &[u8], dst: &[u8], count: usize)
+                                      -> Result<Vec<C::Field>, Error> {
+    let len = (C::Field::nbits() + C::security_bits()).div_ceil(8);
+    let bytes = C::expand_message(msg, dst, count * len)?;
+
+    Ok(bytes.chunks(len).map(|chunk| {
+        let mut le = chunk.to_vec();
+
+        le.reverse();
+        C::Field::from_bytes_wide(&le)
+    }).collect())
+}
+
+/// The hash_to_curve function from RFC 9380, which hashes a message
+/// to a point in the prime-order subgroup.  The output is
+/// indistinguishable from a random oracle.
+pub fn hash_to_curve<C : HashToCurve>(msg: &[u8], dst: &[u8])
+                                      -> Result<EdwardsExtended<C>, Error> {
+    let u = hash_to_field::<C>(msg, dst, 2)?;
+    let sum = C::map_to_curve(&u[0]) + C::map_to_curve(&u[1]);
+
+    Ok(clear_cofactor(&sum))
+}
+
+/// The encode_to_curve function from RFC 9380.  This is cheaper than
+/// `hash_to_curve`, but its output is not uniformly distributed, and
+/// covers only about half of the points.
+pub fn encode_to_curve<C : HashToCurve>(msg: &[u8], dst: &[u8])
+                                        -> Result<EdwardsExtended<C>, Error> {
+    let u = hash_to_field::<C>(msg, dst, 1)?;
+
+    Ok(clear_cofactor(&C::map_to_curve(&u[0])))
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::curve1174::Curve1174;
+    use curve::curve25519;
+    use curve::curve41417::Curve41417;
+    use curve::curve448;
+    use curve::e_222::E222;
+    use curve::e_382::E382;
+    use curve::e_521::E521;
+    use curve::ed25519::Ed25519;
+    use curve::ed448::Ed448;
+    use curve::hash_to_curve::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    fn dst(suite: &str) -> Vec<u8> {
+        format!("QUUX-V01-CS02-with-{}", suite).into_bytes()
+    }
+
+    /// Get the big-endian encoding of a field element, as the RFC
+    /// prints them.
+    fn big_endian<F : PrimeField>(mut val: F) -> Vec<u8> {
+        let mut out = vec![0; F::nbytes() as usize];
+
+        val.pack(&mut out);
+        out.reverse();
+        out
+    }
+
+    fn check_point<C : HashToCurve>(point: EdwardsExtended<C>, x: &str,
+                                    y: &str) {
+        let (px, py, pz, _) = point.extended();
+        let z_inv = pz.inverted();
+
+        assert!(point.is_on_curve());
+        assert_eq!(from_hex(x), big_endian(px * z_inv));
+        assert_eq!(from_hex(y), big_endian(py * z_inv));
+    }
+
+    fn check_ro<C : HashToCurve>(suite: &str, msg: &[u8], x: &str, y: &str) {
+        check_point(hash_to_curve::<C>(msg, &dst(suite)).unwrap(), x, y);
+    }
+
+    fn check_nu<C : HashToCurve>(suite: &str, msg: &[u8], x: &str, y: &str) {
+        check_point(encode_to_curve::<C>(msg, &dst(suite)).unwrap(), x, y);
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        let suite = dst("edwards25519_XMD:SHA-512_ELL2_RO_");
+        let u = hash_to_field::<Ed25519>(b"", &suite, 2).unwrap();
+
+        assert_eq!(from_hex("03fef4813c8cb5f98c6eef88fae174e6\
+                             e7d5380de2b007799ac7ee712d203f3a"),
+                   big_endian(u[0]));
+        assert_eq!(from_hex("780bdddd137290c8f589dc687795aafa\
+                             e35f6b674668d92bf92ae793e6a60c75"),
+                   big_endian(u[1]));
+    }
+
+    #[test]
+    fn test_edwards25519_rfc9380() {
+        let ro = "edwards25519_XMD:SHA-512_ELL2_RO_";
+        let nu = "edwards25519_XMD:SHA-512_ELL2_NU_";
+
+        check_ro::<Ed25519>(ro, b"",
+                            "3c3da6925a3c3c268448dcabb47ccde5\
+                             439559d9599646a8260e47b1e4822fc6",
+                            "09a6c8561a0b22bef63124c588ce4c62\
+                             ea83a3c899763af26d795302e115dc21");
+        check_ro::<Ed25519>(ro, b"abc",
+                            "608040b42285cc0d72cbb3985c6b04c9\
+                             35370c7361f4b7fbdb1ae7f8c1a8ecad",
+                            "1a8395b88338f22e435bbd301183e7f2\
+                             0a5f9de643f11882fb237f88268a5531");
+        check_ro::<Ed25519>(ro, b"abcdef0123456789",
+                            "6d7fabf47a2dc03fe7d47f7dddd21082\
+                             c5fb8f86743cd020f3fb147d57161472",
+                            "53060a3d140e7fbcda641ed3cf42c88a\
+                             75411e648a1add71217f70ea8ec561a6");
+        check_nu::<Ed25519>(nu, b"",
+                            "1ff2b70ecf862799e11b7ae744e3489a\
+                             a058ce805dd323a936375a84695e76da",
+                            "222e314d04a4d5725e9f2aff9fb2a6b6\
+                             9ef375a1214eb19021ceab2d687f0f9b");
+        check_nu::<Ed25519>(nu, b"abc",
+                            "5f13cc69c891d86927eb37bd4afc6672\
+                             360007c63f68a33ab423a3aa040fd2a8",
+                            "67732d50f9a26f73111dd1ed5dba2256\
+                             14e538599db58ba30aaea1f5c827fa42");
+    }
+
+    #[test]
+    fn test_curve25519_rfc9380() {
+        let ro = dst("curve25519_XMD:SHA-512_ELL2_RO_");
+        let nu = dst("curve25519_XMD:SHA-512_ELL2_NU_");
+
+        assert_eq!(from_hex("2de3780abb67e861289f5749d16d3e21\
+                             7ffa722192d16bbd9d1bfb9d112b98c0"),
+                   big_endian(curve25519::hash_to_curve(b"", &ro)
+                                  .unwrap()));
+        assert_eq!(from_hex("2b4419f1f2d48f5872de692b0aca72cc\
+                             7b0a60915dd70bde432e826b6abc526d"),
+                   big_endian(curve25519::hash_to_curve(b"abc", &ro)
+                                  .unwrap()));
+        assert_eq!(from_hex("1bb913f0c9daefa0b3375378ffa534bd\
+                             a5526c97391952a7789eb976edfe4d08"),
+                   big_endian(curve25519::encode_to_curve(b"", &nu)
+                                  .unwrap()));
+        assert_eq!(from_hex("7c22950b7d900fa866334262fcaea47a\
+                             441a578df43b894b4625c9b450f9a026"),
+                   big_endian(curve25519::encode_to_curve(b"abc", &nu)
+                                  .unwrap()));
+    }
+
+    #[test]
+    fn test_edwards448_rfc9380() {
+        let ro = "edwards448_XOF:SHAKE256_ELL2_RO_";
+        let nu = "edwards448_XOF:SHAKE256_ELL2_NU_";
+
+        check_ro::<Ed448>(ro, b"",
+                          "73036d4a88949c032f01507005c13388\
+                           4e2f0d81f9a950826245dda9e844fc78\
+                           186c39daaa7147ead3e462cff60e9c63\
+                           40b58134480b4d17",
+                          "94c1d61b43728e5d784ef4fcb1f38e10\
+                           75f3aef5e99866911de5a234f1aafdc2\
+                           6b554344742e6ba0420b71b298671bbe\
+                           b2b7736618634610");
+        check_ro::<Ed448>(ro, b"abc",
+                          "4e0158acacffa545adb818a6ed8e0b87\
+                           0e6abc24dfc1dc45cf9a052e98469275\
+                           d9ff0c168d6a5ac7ec05b742412ee090\
+                           581f12aa398f9f8c",
+                          "894d3fa437b2d2e28cdc3bfaade03543\
+                           0f350ec5239b6b406b5501da6f6d6210\
+                           ff26719cad83b63e97ab26a12df6dec8\
+                           51d6bf38e294af9a");
+        check_nu::<Ed448>(nu, b"",
+                          "eb5a1fc376fd73230af2de0f3374087c\
+                           c7f279f0460114cf0a6c12d6d044c16d\
+                           e34ec2350c34b26bf110377655ab7793\
+                           6869d085406af71e",
+                          "df5dcea6d42e8f494b279a500d09e895\
+                           d26ac703d75ca6d118e8ca58bf6f608a\
+                           2a383f292fce1563ff995dce75aede1f\
+                           dc8e7c0c737ae9ad");
+        check_nu::<Ed448>(nu, b"abc",
+                          "4623a64bceaba3202df76cd8b6e3daf7\
+                           0164f3fcbda6d6e340f7fab5cdf89140\
+                           d955f722524f5fe4d968fef6ba2853ff\
+                           4ea086c2f67d8110",
+                          "abaac321a169761a8802ab5b5d10061f\
+                           ec1a83c670ac6bc95954700317ee5f82\
+                           870120e0e2c5a21b12a0c7ad17ebd343\
+                           363604c4bcecafd1");
+    }
+
+    #[test]
+    fn test_curve448_rfc9380() {
+        let ro = dst("curve448_XOF:SHAKE256_ELL2_RO_");
+        let nu = dst("curve448_XOF:SHAKE256_ELL2_NU_");
+
+        assert_eq!(from_hex("5ea5ff623d27c75e73717514134e73e4\
+                             19f831a875ca9e82915fdfc7069d0a9f\
+                             8b532cfb32b1d8dd04ddeedbe3fa1d0d\
+                             681c01e825d6a9ea"),
+                   big_endian(curve448::hash_to_curve(b"", &ro).unwrap()));
+        assert_eq!(from_hex("9b2f7ce34878d7cebf34c582db149583\
+                             08ea09366d1ec71f646411d3de0ae564\
+                             d082b06f40cd30dfc08d9fb7cb21df39\
+                             0cf207806ad9d0e4"),
+                   big_endian(curve448::hash_to_curve(b"abc", &ro)
+                                  .unwrap()));
+        assert_eq!(from_hex("b65e8dbb279fd656f926f68d463b13ca\
+                             7a982b32f5da9c7cc58afcf6199e4729\
+                             863fb75ca9ae3c95c6887d95a5102637\
+                             a1c5c40ff0aafadc"),
+                   big_endian(curve448::encode_to_curve(b"", &nu).unwrap()));
+        assert_eq!(from_hex("51aceca4fa95854bbaba58d8a5e17a86\
+                             c07acadef32e1188cafda26232131800\
+                             002cc2f27c7aec454e5e0c615bddffb7\
+                             df6a5f7f0f14793f"),
+                   big_endian(curve448::encode_to_curve(b"abc", &nu)
+                                  .unwrap()));
+    }
+
+    // RFC 9380 defines no suites for these curves, so the suite IDs
+    // are our own and the expected values are self-generated: they
+    // come from this code and agree with a Python model of the RFC's
+    // Elligator 2 suites written alongside it.  They guard against
+    // regressions, but are not independent test vectors.
+    #[test]
+    fn test_generic_regression() {
+        check_ro::<E222>("E222_XMD:SHA-512_ELL2_RO_", b"abc",
+                         "232d53f47f72bb5fe7cee74d65cb0cca\
+                          d463396fab14e3ee3dddf696",
+                         "3586810c66dfeaf172c73827bf36811e\
+                          7958a5baea05936491d2bacc");
+        check_nu::<E222>("E222_XMD:SHA-512_ELL2_NU_", b"abc",
+                         "07ee328003e4874722b07e8f89cae024\
+                          e2e058f78769a69d99f22ec4",
+                         "0abfd06d4b9f2c31c9a1054b11dd94d5\
+                          7c6b801d979d8fb632809fd6");
+        check_ro::<Curve1174>("Curve1174_XMD:SHA-512_ELL2_RO_", b"abc",
+                              "0541c9cc18611a70d677dc8d10454eb9\
+                               e536d04f0228f924fab798bf466368e7",
+                              "07ba4866eab630c337d4ff2b296cbba0\
+                               874bb245f2b7a39a822d2df34a0761d0");
+        check_nu::<Curve1174>("Curve1174_XMD:SHA-512_ELL2_NU_", b"abc",
+                              "05d84a8647663a9062bdd5e132967e38\
+                               53e1eacb30cb61cbfc9879c997a96302",
+                              "046c108e76afd1daaaab5d48c61d5588\
+                               d417946338c0f0004956ec5f8e788b87");
+        check_ro::<E382>("E382_XMD:SHA-512_ELL2_RO_", b"abc",
+                         "0232c157236044fe10bfe7152fb1500b\
+                          37f6a35b2176473de9f41cb9e9190144\
+                          d5fb6a4dfb3cb1402be971c260eb80fb",
+                         "202126cdf96fc269a46b95c89402234d\
+                          c1fc2f3a6f38248671f5058889785d80\
+                          bfe597bdfa6ebc69805bb268d8bbc187");
+        check_nu::<E382>("E382_XMD:SHA-512_ELL2_NU_", b"abc",
+                         "1dbbac667a7f0d6415e212b2fe99bf52\
+                          befe4fd0e055f88affa0df09c8e298ac\
+                          3c691b7000a35d62c6f1fc61ebcd690b",
+                         "092b95d0bc0416772c0648ed3c0b84a3\
+                          1f340dd3385e43c1d0c8563b370e9fa7\
+                          b72f5271f46b5a55d770f0cd6c9d7f41");
+        check_ro::<Curve41417>("Curve41417_XMD:SHA-512_ELL2_RO_", b"abc",
+                               "1d3a182f0c03268fb3df7a21d9b45ff4\
+                                9229e786e53674da61e8db31b45b0c56\
+                                5b27867b86a42cd0d7d86f940b91bd02\
+                                b3c87a5e",
+                               "0c5ce77b9809e228015d873253511199\
+                                ab75f0c9f4e07d92eec4aba9ed0f52c5\
+                                c1e87b15e5d31fbe08e77ca69a0a6dfe\
+                                6246dca3");
+        check_nu::<Curve41417>("Curve41417_XMD:SHA-512_ELL2_NU_", b"abc",
+                               "094a468c9d06bfa6124a824cb366815b\
+                                226b53889f65252a44b8c92aecaeb6b2\
+                                6bfcdd80e6195ba3032ade96ac8f3a99\
+                                d87d7c51",
+                               "0d666d4b7ca884d3c61e51e1cfaff698\
+                                32be57adaabea6bbb953bba612c0fad1\
+                                e8e61df852b78216c8dfdaea5be66752\
+                                61331713");
+        check_ro::<E521>("E521_XMD:SHA-512_ELL2_RO_", b"abc",
+                         "012be838ed5c6abe35a24ea4ec3d694c\
+                          a456ce6199fd61c233685102766ecbfe\
+                          ae966355ae8a97d1e935bbb886eeca0c\
+                          bed328f74125384968d74e4e79440046\
+                          35d6",
+                         "00c1cd37e2a84e5ab45fe9c205ba06ae\
+                          3c5504debca36fdd97933166fddbacd9\
+                          4ff1a8b9654c23819b6340ba406b7226\
+                          2c668cd46d3f36d48e3b672927207fc4\
+                          43bc");
+        check_nu::<E521>("E521_XMD:SHA-512_ELL2_NU_", b"abc",
+                         "01ce7d81807379b00c6b681e4873680e\
+                          8bee9ac14fca6e28578feddc879be549\
+                          23e498dc090fd7ca277cfa657f448081\
+                          a23a6bbcf07fdd114571a0f6a9d58ff9\
+                          79b5",
+                         "01e8fee57c0d6e6c1d2f42c71b2b3982\
+                          437604a0a25b505fb167144e12b38dc9\
+                          426c38c66dce31df03ff07c377dde175\
+                          a377098072f88b5b42f4d19779e4c6a7\
+                          4580");
+    }
+
+    fn check_subgroup<C : HashToCurve>() {
+        let suite = dst("test");
+
+        for msg in [&b""[..], b"abc", b"abcdef0123456789"].iter() {
+            let mut point = hash_to_curve::<C>(msg, &suite).unwrap();
+
+            assert!(point.is_on_curve());
+            assert!(!point.is_identity());
+            point.scalar_mult(&C::order());
+            assert!(point.is_identity());
+        }
+    }
+
+    #[test]
+    fn test_subgroup() {
+        check_subgroup::<E222>();
+        check_subgroup::<Curve1174>();
+        check_subgroup::<Ed25519>();
+        check_subgroup::<E382>();
+        check_subgroup::<Curve41417>();
+        check_subgroup::<Ed448>();
+        check_subgroup::<E521>();
+    }
+
+    fn check_exceptional<C : HashToCurve>() {
+        // u = 0 gives x1 = -J/K, and so does u = 1 when Z = -1, since
+        // then 1 + Zu^2 = 0.
+        assert!(C::map_to_curve(&C::Field::zero()).is_on_curve());
+        assert!(C::map_to_curve(&C::Field::one()).is_on_curve());
+        assert!(C::map_to_curve(&C::Field::m_one()).is_on_curve());
+    }
+
+    #[test]
+    fn test_exceptional() {
+        check_exceptional::<E222>();
+        check_exceptional::<Curve1174>();
+        check_exceptional::<Ed25519>();
+        check_exceptional::<E382>();
+        check_exceptional::<Curve41417>();
+        check_exceptional::<Ed448>();
+        check_exceptional::<E521>();
+    }
+}
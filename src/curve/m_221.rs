@@ -1,19 +1,22 @@
-/*
-static BASE_X: Mod_e221_3 =
-    Mod_e221_3::unpacked(&[0x04, 0x00, 0x00, 0x00,
-                           0x00, 0x00, 0x00, 0x00,
-                           0x00, 0x00, 0x00, 0x00,
-                           0x00, 0x00, 0x00, 0x00,
-                           0x00, 0x00, 0x00, 0x00,
-                           0x00, 0x00, 0x00, 0x00,
-                           0x00, 0x00, 0x00, 0x00]);
+use curve::elligator2::Elligator2Curve;
+use curve::montgomery::*;
+use field::mod_e221_3::*;
+use scalar::m_221::Scalar_M221;
 
-static BASE_Y: Mod_e221_3 =
-    Mod_e221_3::unpacked(&[0x6d, 0x05, 0xc5, 0x08,
-                           0x6c, 0xdc, 0x07, 0x07,
-                           0xf1, 0xbf, 0x1d, 0xe6,
-                           0x28, 0xc2, 0x37, 0xca,
-                           0x4e, 0xf1, 0xce, 0xd1,
-                           0x71, 0x95, 0x93, 0xa4,
-                           0xd2, 0xcd, 0x7a, 0x0f]);
-*/
+#[derive(Copy, Clone)]
+pub struct M221();
+
+impl MontgomeryCurve for M221 {
+    type Field = Mod_e221_3;
+    type Scalar = Scalar_M221;
+
+    fn a_val() -> Self::Field { M221_A }
+
+    fn a24_val() -> Self::Field { M221_A24 }
+}
+
+impl Elligator2Curve for M221 {
+    fn base_x() -> Self::Field { M221_BASE_X }
+
+    fn dirty_base_x() -> Self::Field { M221_DIRTY_BASE_X }
+}
@@ -0,0 +1,22 @@
+use curve::elligator2::Elligator2Curve;
+use curve::montgomery::*;
+use field::mod_e383_187::*;
+use scalar::m_383::Scalar_M383;
+
+#[derive(Copy, Clone)]
+pub struct M383();
+
+impl MontgomeryCurve for M383 {
+    type Field = Mod_e383_187;
+    type Scalar = Scalar_M383;
+
+    fn a_val() -> Self::Field { M383_A }
+
+    fn a24_val() -> Self::Field { M383_A24 }
+}
+
+impl Elligator2Curve for M383 {
+    fn base_x() -> Self::Field { M383_BASE_X }
+
+    fn dirty_base_x() -> Self::Field { M383_DIRTY_BASE_X }
+}
@@ -0,0 +1,22 @@
+use curve::elligator2::Elligator2Curve;
+use curve::montgomery::*;
+use field::mod_e511_187::*;
+use scalar::m_511::Scalar_M511;
+
+#[derive(Copy, Clone)]
+pub struct M511();
+
+impl MontgomeryCurve for M511 {
+    type Field = Mod_e511_187;
+    type Scalar = Scalar_M511;
+
+    fn a_val() -> Self::Field { M511_A }
+
+    fn a24_val() -> Self::Field { M511_A24 }
+}
+
+impl Elligator2Curve for M511 {
+    fn base_x() -> Self::Field { M511_BASE_X }
+
+    fn dirty_base_x() -> Self::Field { M511_DIRTY_BASE_X }
+}
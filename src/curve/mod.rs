@@ -1,8 +1,24 @@
 pub mod point;
 pub mod group;
+pub mod base_table;
 pub mod edwards;
+pub mod prime_order_group;
+pub mod montgomery;
+pub mod elligator2;
+pub mod elligator_squared;
 pub mod e_222;
 pub mod curve1174;
 pub mod e_382;
 pub mod curve41417;
 pub mod e_521;
+pub mod m_221;
+pub mod curve25519;
+pub mod m_383;
+pub mod curve383187;
+pub mod m_511;
+pub mod ed448;
+pub mod curve448;
+pub mod ed25519;
+pub mod decaf;
+pub mod ristretto255;
+pub mod hash_to_curve;
@@ -0,0 +1,200 @@
+use choice::Choice;
+use choice::ConditionallySelectable;
+use field::prime_field::PrimeField;
+use pack::Pack;
+use scalar::scalar_field::ScalarField;
+
+pub trait MontgomeryCurve : Copy {
+    type Field : PrimeField + Copy;
+    type Scalar : ScalarField;
+
+    /// The A value in the curve equation y^2 = x^3 + Ax^2 + x.
+    fn a_val() -> Self::Field;
+
+    /// The value (A + 2) / 4, used by the ladder.
+    fn a24_val() -> Self::Field;
+}
+
+/// Montgomery curve point in projective x-only coordinates (X : Z).
+/// This represents both a point and its negation, which is all that
+/// the ladder needs.
+#[derive(Copy, Clone)]
+pub struct MontgomeryX<C : MontgomeryCurve> {
+    x : C::Field,
+    z : C::Field
+}
+
+impl<C : MontgomeryCurve> MontgomeryX<C> {
+    /// Create a point from its affine x-coordinate.
+    pub fn new(x: C::Field) -> Self {
+        MontgomeryX { x, z: C::Field::one() }
+    }
+
+    /// The point at infinity.
+    pub fn zero() -> Self {
+        MontgomeryX { x: C::Field::one(), z: C::Field::zero() }
+    }
+
+    /// Get the affine x-coordinate.  The point at infinity maps to 0.
+    pub fn affine_x(&self) -> C::Field {
+        self.x * self.z.inverted()
+    }
+
+    /// Multiply by a scalar given as a little-endian byte array,
+    /// using only its lowest nbits bits.  This is the x-only
+    /// Montgomery ladder, and runs in time depending only on nbits.
+    pub fn ladder(&mut self, k: &[u8], nbits: usize) {
+        let x1 = self.affine_x();
+        let mut x2 = C::Field::one();
+        let mut z2 = C::Field::zero();
+        let mut x3 = x1;
+        let mut z3 = C::Field::one();
+        let mut swap = Choice::from(0);
+
+        for i in 0..nbits {
+            let idx = nbits - 1 - i;
+            let bit = Choice::from(k[idx / 8] >> (idx % 8));
+
+            swap = swap ^ bit;
+            C::Field::conditional_swap(&mut x2, &mut x3, swap);
+            C::Field::conditional_swap(&mut z2, &mut z3, swap);
+            swap = bit;
+
+            let a = x2 + z2;
+            let aa = a.squared();
+            let b = x2 - z2;
+            let bb = b.squared();
+            let e = aa - bb;
+            let c = x3 + z3;
+            let d = x3 - z3;
+            let da = d * a;
+            let cb = c * b;
+
+            x3 = (da + cb).squared();
+            z3 = x1 * (da - cb).squared();
+            x2 = aa * bb;
+            z2 = e * (bb + (C::a24_val() * e));
+        }
+
+        C::Field::conditional_swap(&mut x2, &mut x3, swap);
+        C::Field::conditional_swap(&mut z2, &mut z3, swap);
+
+        self.x = x2;
+        self.z = z2;
+    }
+
+    /// Scalar multiplication.
+    pub fn scalar_mult(&mut self, rhs: &C::Scalar) {
+        let mut bytes = vec![0u8; C::Scalar::nbytes() as usize];
+
+        rhs.pack_normalized(&mut bytes);
+        self.ladder(&bytes, C::Scalar::nbits());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::curve25519::Curve25519;
+    use curve::curve383187::Curve383187;
+    use curve::curve448::Curve448;
+    use curve::m_221::M221;
+    use curve::m_383::M383;
+    use curve::m_511::M511;
+    use curve::montgomery::*;
+    use field::mod_e221_3::*;
+    use field::mod_e255_19::*;
+    use field::mod_e383_187::*;
+    use field::mod_e448_goldilocks::*;
+    use field::mod_e511_187::*;
+    use field::prime_field::PrimeField;
+    use normalize::NormalizeEq;
+    use pack::Pack;
+    use scalar::scalar_field::ScalarField;
+
+    fn bytes(n: usize) -> Vec<u8> {
+        (0..n).map(|i| (i + 1) as u8).collect()
+    }
+
+    fn check_curve<C: MontgomeryCurve>(base_x: C::Field, expected: &[u8]) {
+        let k = C::Scalar::unpacked(&bytes(C::Scalar::nbytes() as usize));
+        let j = C::Scalar::unpacked(&[ 0x3b, 0xa1, 0x7e, 0x05, 0xd2, 0x9c ]);
+        let mut p = MontgomeryX::<C>::new(base_x);
+        let mut inf = MontgomeryX::<C>::new(base_x);
+        let mut kj = MontgomeryX::<C>::new(base_x);
+        let mut expect = C::Field::unpacked(expected);
+
+        p.scalar_mult(&k);
+        assert!(expect.normalize_eq(&mut p.affine_x()));
+
+        // Multiplying by k then j is the same as by kj.
+        p.scalar_mult(&j);
+        kj.scalar_mult(&(k * j));
+        assert!(p.affine_x().normalize_eq(&mut kj.affine_x()));
+
+        // The base point has the group order.
+        inf.ladder(&bytes_of(&C::Scalar::modulus()), C::Scalar::nbits());
+        assert!(inf.z.normalize_eq(&mut C::Field::zero()));
+        assert!(!p.z.normalize_eq(&mut C::Field::zero()));
+    }
+
+    fn bytes_of<S: ScalarField>(s: &S) -> Vec<u8> {
+        let mut out = vec![0u8; S::nbytes() as usize];
+
+        s.pack_normalized(&mut out);
+        out
+    }
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    #[test]
+    fn test_m221() {
+        check_curve::<M221>(M221_BASE_X,
+                            &from_hex("c5c772a3285cf09730f452dd0a9aa3c2\
+                                       56ed8696b92ca8bd229bc602"));
+    }
+
+    #[test]
+    fn test_curve25519() {
+        check_curve::<Curve25519>(CURVE25519_BASE_X,
+                                  &from_hex("bcd6886bb4119943e0d74eb75fa44d28\
+                                             b11e65d78aa98f94149bb261174f3834"));
+    }
+
+    #[test]
+    fn test_m383() {
+        check_curve::<M383>(M383_BASE_X,
+                            &from_hex("e5e1fc337e426546c7e312e419885e2b\
+                                       5627cdf678b022c7d0d0e8f6282ca061\
+                                       d4abbecae8464df23314165838567973"));
+    }
+
+    #[test]
+    fn test_curve383187() {
+        check_curve::<Curve383187>(CURVE383187_BASE_X,
+                                   &from_hex("45f2388256bb2dc8b470d15a08bace0f\
+                                              c17c72d6777c92b7f021cdf20bac9728\
+                                              29e2677eab0e5c232aeb9334960cba6f"));
+    }
+
+    #[test]
+    fn test_curve448() {
+        check_curve::<Curve448>(CURVE448_BASE_X,
+                                &from_hex("a9df0c4fa0b13eae0744c44b780ab8a3\
+                                           f2d91c3e40587cc36ebe46060046e879\
+                                           f0788218706dd56734403e7412eda0ff\
+                                           367cc9b16c7f03db"));
+    }
+
+    #[test]
+    fn test_m511() {
+        check_curve::<M511>(M511_BASE_X,
+                            &from_hex("02d7e24298ec56fce8f763309a41ffd6\
+                                       093986a2d921b3e344c7b00346ef0d46\
+                                       5b585c58b745c3fe97fb8d6653f2a3c9\
+                                       45dbcea56e608dcb21c742f4d5ed4f76"));
+    }
+}
@@ -1,21 +1,37 @@
+use error::Error;
 use field::prime_field::PrimeField;
+use scalar::scalar_field::ScalarField;
 use std::ops::Add;
 use std::ops::AddAssign;
 
 /// Operations on elliptic curve points.
 pub trait Point : Add<Self, Output = Self> + AddAssign<Self> + Copy {
-    type Scalar : PrimeField;
+    /// The field over which the curve is defined.
+    type Field : PrimeField;
+
+    /// The integers mod the order of the curve's prime subgroup.
+    type Scalar : ScalarField;
 
     fn zero() -> Self;
 
-    /// In-place constructor for points from x-y coordinates.
-    fn init(&mut self, x: Self::Scalar, y: Self::Scalar);
+    /// In-place constructor for points from x-y coordinates.  This
+    /// does not check that the point is on the curve.
+    fn init(&mut self, x: Self::Field, y: Self::Field);
 
-    /// Scalar multiplication.
-    fn scalar_mult(&mut self, rhs: &mut Self::Scalar);
+    /// In-place constructor for points from x-y coordinates, which
+    /// fails if the point is not on the curve.  Use this for any
+    /// coordinates that come from an untrusted source.
+    fn init_checked(&mut self, x: Self::Field, y: Self::Field)
+                    -> Result<(), Error>;
+
+    /// Check whether this is a valid point on the curve.
+    fn is_on_curve(&self) -> bool;
+
+    /// Check whether this is the identity element.
+    fn is_identity(&self) -> bool;
 
-    /// Scalar multiplication by a normalized scalar.
-    fn scalar_mult_normalized(&mut self, rhs: &Self::Scalar);
+    /// Scalar multiplication.
+    fn scalar_mult(&mut self, rhs: &Self::Scalar);
 
     /// Double a point in place.
     fn double(&mut self);
@@ -30,9 +46,18 @@ pub trait Point : Add<Self, Output = Self> + AddAssign<Self> + Copy {
     fn tripled(&self) -> Self;
 }
 
+/// Compressed representations of points.
 pub trait Compressed<T> {
+    /// Compress a point in place.
     fn compress(&mut self, T);
+
+    /// Functional compression.
     fn compressed(T) -> Self;
-    fn decompress(&self, &mut T);
-    fn decomressed(&self) -> T;
+
+    /// Decompress into a point in place.  This fails if the
+    /// representation does not describe a point on the curve.
+    fn decompress(&self, &mut T) -> Result<(), Error>;
+
+    /// Functional decompression.
+    fn decompressed(&self) -> Result<T, Error>;
 }
@@ -0,0 +1,53 @@
+use choice::ConstantTimeEq;
+use error::Error;
+use scalar::scalar_field::ScalarField;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+/// Groups of prime order, built as quotients of curves with a
+/// cofactor.  Every element has exactly one encoding, and equality
+/// ignores the small-order torsion of the underlying curve, so
+/// protocols never see small-subgroup elements.
+pub trait PrimeOrderGroup : Add<Self, Output = Self> + AddAssign<Self> +
+    Sub<Self, Output = Self> + SubAssign<Self> + Neg<Output = Self> +
+    ConstantTimeEq + Copy {
+    /// The integers mod the order of the group.
+    type Scalar : ScalarField;
+
+    /// The identity element.
+    fn identity() -> Self;
+
+    /// The standard generator.
+    fn generator() -> Self;
+
+    /// The order of the group.
+    fn order() -> Self::Scalar;
+
+    /// Check whether this is the identity element.
+    fn is_identity(&self) -> bool;
+
+    /// Scalar multiplication.
+    fn scalar_mult(&mut self, rhs: &Self::Scalar);
+
+    /// Get the number of bytes in an encoded element.
+    fn nbytes() -> usize;
+
+    /// Get the canonical encoding of this element.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Decode an element.  This fails on anything other than the
+    /// canonical encoding of a group element.
+    fn decode(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Get the number of bytes taken by `from_uniform_bytes`.
+    fn uniform_nbytes() -> usize;
+
+    /// Map uniformly random bytes to a group element, such that the
+    /// output is indistinguishable from uniform and its discrete log
+    /// is unknown.  Hash a message to `uniform_nbytes` bytes to get a
+    /// hash-to-group function.
+    fn from_uniform_bytes(bytes: &[u8]) -> Result<Self, Error>;
+}
@@ -0,0 +1,475 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use curve::ed25519::Ed25519;
+use curve::edwards::*;
+use curve::group::Group;
+use curve::point::Point;
+use curve::prime_order_group::PrimeOrderGroup;
+use error::Error;
+use field::mod_e255_19::*;
+use field::prime_field::PrimeField;
+use pack::Pack;
+use scalar::curve25519::Scalar_Curve25519;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+/// An element of the ristretto255 group from RFC 9496.  This is a
+/// prime-order quotient of edwards25519, in which points that differ
+/// by 4-torsion are the same element.
+#[derive(Copy, Clone)]
+pub struct Ristretto255(EdwardsExtended<Ed25519>);
+
+/// Check whether a field element is negative, meaning odd.
+fn is_negative(val: Mod_e255_19) -> Choice {
+    let mut val = val;
+
+    Choice::from(val.bit(0))
+}
+
+/// Negate a field element if it is negative.
+fn abs(val: Mod_e255_19) -> Mod_e255_19 {
+    let mut out = val;
+
+    out.conditional_negate(is_negative(val));
+    out
+}
+
+/// The one-way map MAP from RFC 9496, which takes a field element to
+/// a point on edwards25519.
+fn map(t: Mod_e255_19) -> EdwardsExtended<Ed25519> {
+    let r = SQRT_M1 * t.squared();
+    let u = (r + ONE) * RISTRETTO_ONE_MINUS_D_SQ;
+    let v = (M_ONE - r * ED25519_D) * (r + ED25519_D);
+    let (was_square, s) = Mod_e255_19::sqrt_ratio(&u, &v);
+    let s_prime = -abs(s * t);
+    let s = Mod_e255_19::conditional_select(&s_prime, &s, was_square);
+    let c = Mod_e255_19::conditional_select(&r, &M_ONE, was_square);
+    let n = c * (r - ONE) * RISTRETTO_D_MINUS_ONE_SQ - v;
+    let w0 = s.small_mul(2) * v;
+    let w1 = n * RISTRETTO_SQRT_AD_MINUS_ONE;
+    let w2 = ONE - s.squared();
+    let w3 = ONE + s.squared();
+
+    EdwardsExtended::from_extended(w0 * w3, w2 * w1, w1 * w3, w0 * w2)
+}
+
+impl Ristretto255 {
+    /// Get a representative point on edwards25519.  This is only
+    /// determined up to 4-torsion.
+    pub fn point(&self) -> EdwardsExtended<Ed25519> {
+        self.0
+    }
+}
+
+impl<'b> AddAssign<&'b Ristretto255> for Ristretto255 {
+    fn add_assign(&mut self, rhs: &'b Ristretto255) {
+        self.0 += &rhs.0;
+    }
+}
+
+impl AddAssign<Ristretto255> for Ristretto255 {
+    fn add_assign(&mut self, rhs: Ristretto255) {
+        *self += &rhs;
+    }
+}
+
+impl<'b> Add<&'b Ristretto255> for &Ristretto255 {
+    type Output = Ristretto255;
+
+    fn add(self, rhs: &'b Ristretto255) -> Ristretto255 {
+        let mut out = *self;
+
+        out += rhs;
+        out
+    }
+}
+
+impl Add<Ristretto255> for Ristretto255 {
+    type Output = Ristretto255;
+
+    fn add(self, rhs: Ristretto255) -> Ristretto255 {
+        &self + &rhs
+    }
+}
+
+impl Neg for Ristretto255 {
+    type Output = Ristretto255;
+
+    fn neg(self) -> Ristretto255 {
+        Ristretto255(-self.0)
+    }
+}
+
+impl<'b> SubAssign<&'b Ristretto255> for Ristretto255 {
+    fn sub_assign(&mut self, rhs: &'b Ristretto255) {
+        self.0 += &(-rhs.0);
+    }
+}
+
+impl SubAssign<Ristretto255> for Ristretto255 {
+    fn sub_assign(&mut self, rhs: Ristretto255) {
+        *self -= &rhs;
+    }
+}
+
+impl<'b> Sub<&'b Ristretto255> for &Ristretto255 {
+    type Output = Ristretto255;
+
+    fn sub(self, rhs: &'b Ristretto255) -> Ristretto255 {
+        let mut out = *self;
+
+        out -= rhs;
+        out
+    }
+}
+
+impl Sub<Ristretto255> for Ristretto255 {
+    type Output = Ristretto255;
+
+    fn sub(self, rhs: Ristretto255) -> Ristretto255 {
+        &self - &rhs
+    }
+}
+
+impl ConstantTimeEq for Ristretto255 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let (x1, y1, _, _) = self.0.extended();
+        let (x2, y2, _, _) = other.0.extended();
+
+        (x1 * y2).ct_eq(&(y1 * x2)) | (y1 * y2).ct_eq(&(x1 * x2))
+    }
+}
+
+impl PrimeOrderGroup for Ristretto255 {
+    type Scalar = Scalar_Curve25519;
+
+    fn identity() -> Self {
+        Ristretto255(EdwardsExtended::zero())
+    }
+
+    fn generator() -> Self {
+        Ristretto255(Ed25519::base())
+    }
+
+    fn order() -> Scalar_Curve25519 {
+        Ed25519::order()
+    }
+
+    fn is_identity(&self) -> bool {
+        bool::from(self.ct_eq(&Self::identity()))
+    }
+
+    fn scalar_mult(&mut self, rhs: &Scalar_Curve25519) {
+        self.0.scalar_mult(rhs);
+    }
+
+    fn nbytes() -> usize {
+        32
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let (x0, y0, z0, t0) = self.0.extended();
+        let u1 = (z0 + y0) * (z0 - y0);
+        let u2 = x0 * y0;
+        let (_, invsqrt) = Mod_e255_19::sqrt_ratio(&ONE,
+                                                   &(u1 * u2.squared()));
+        let den1 = invsqrt * u1;
+        let den2 = invsqrt * u2;
+        let z_inv = den1 * den2 * t0;
+        let ix0 = x0 * SQRT_M1;
+        let iy0 = y0 * SQRT_M1;
+        let enchanted_denominator = den1 * RISTRETTO_INVSQRT_A_MINUS_D;
+        let rotate = is_negative(t0 * z_inv);
+        let x = Mod_e255_19::conditional_select(&x0, &iy0, rotate);
+        let mut y = Mod_e255_19::conditional_select(&y0, &ix0, rotate);
+        let den_inv = Mod_e255_19::conditional_select(&den2,
+                                                      &enchanted_denominator,
+                                                      rotate);
+
+        y.conditional_negate(is_negative(x * z_inv));
+
+        let mut s = abs(den_inv * (z0 - y));
+        let mut out = vec![0; Self::nbytes()];
+
+        s.pack(&mut out);
+        out
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != Self::nbytes() {
+            return Err(Error::InvalidLength);
+        }
+
+        let s = Mod_e255_19::unpacked_checked(bytes)?;
+
+        // Only the non-negative root is a valid encoding.
+        if bool::from(is_negative(s)) {
+            return Err(Error::NonCanonical);
+        }
+
+        let ss = s.squared();
+        let u1 = ONE - ss;
+        let u2 = ONE + ss;
+        let u2_sqr = u2.squared();
+        let v = -(ED25519_D * u1.squared()) - u2_sqr;
+        let (was_square, invsqrt) = Mod_e255_19::sqrt_ratio(&ONE,
+                                                            &(v * u2_sqr));
+        let den_x = invsqrt * u2;
+        let den_y = invsqrt * den_x * v;
+        let x = abs(s.small_mul(2) * den_x);
+        let y = u1 * den_y;
+        let t = x * y;
+        let valid = was_square & !is_negative(t) & !y.ct_eq(&ZERO);
+
+        if !bool::from(valid) {
+            return Err(Error::NotOnCurve);
+        }
+
+        Ok(Ristretto255(EdwardsExtended::from_extended(x, y, ONE, t)))
+    }
+
+    fn uniform_nbytes() -> usize {
+        64
+    }
+
+    /// The one-way map from RFC 9496, which maps each half of the
+    /// input to a point, ignoring its top bit, and adds the two.
+    fn from_uniform_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != Self::uniform_nbytes() {
+            return Err(Error::InvalidLength);
+        }
+
+        let mut t1 = [0u8; 32];
+        let mut t2 = [0u8; 32];
+
+        t1.copy_from_slice(&bytes[0..32]);
+        t2.copy_from_slice(&bytes[32..64]);
+        t1[31] &= 0x7f;
+        t2[31] &= 0x7f;
+
+        let r1 = Mod_e255_19::from_bytes_wide(&t1);
+        let r2 = Mod_e255_19::from_bytes_wide(&t2);
+
+        Ok(Ristretto255(map(r1) + map(r2)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::ConstantTimeEq;
+    use curve::point::Point;
+    use curve::prime_order_group::PrimeOrderGroup;
+    use curve::ristretto255::*;
+    use error::Error;
+    use hash::sha512::sha512;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    const MULTIPLES: [&str; 16] = [
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+        "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+        "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+        "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+        "e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e",
+        "f64746d3c92b13050ed8d80236a7f0007c3b3f962f5ba793d19a601ebb1df403",
+        "44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d",
+        "903293d8f2287ebe10e2374dc1a53e0bc887e592699f02d077d5263cdd55601c",
+        "02622ace8f7303a31cafc63f8fc48fdc16e1c8c8d234b2f0d6685282a9076031",
+        "20706fd788b2720a1ed2a5dad4952b01f413bcf0e7564de8cdc816689e2db95f",
+        "bce83f8ba5dd2fa572864c24ba1810f9522bc6004afe95877ac73241cafdab42",
+        "e4549ee16b9aa03099ca208c67adafcafa4c3f3e4e5303de6026e3ca8ff84460",
+        "aa52e000df2e16f55fb1032fc33bc42742dad6bd5a8fc0be0167436c5948501f",
+        "46376b80f409b29dc2b5f6f0c52591990896e5716f41477cd30085ab7f10301e",
+        "e0c418f7c8d9c4cdd7395b93ea124f3ad99021bb681dfc3302a9d99a2e53e64e"
+    ];
+
+    fn multiples() -> Vec<Ristretto255> {
+        let mut out = vec![Ristretto255::identity()];
+
+        for i in 1..MULTIPLES.len() {
+            let next = out[i - 1] + Ristretto255::generator();
+
+            out.push(next);
+        }
+
+        out
+    }
+
+    #[test]
+    fn test_multiples() {
+        for (point, hex) in multiples().iter().zip(MULTIPLES.iter()) {
+            let bytes = from_hex(hex);
+            let decoded = Ristretto255::decode(&bytes).unwrap();
+
+            assert_eq!(bytes, point.encode());
+            assert_eq!(bytes, decoded.encode());
+            assert!(decoded.point().is_on_curve());
+            assert!(bool::from(decoded.ct_eq(point)));
+        }
+    }
+
+    #[test]
+    fn test_equality() {
+        let points = multiples();
+
+        assert!(points[0].is_identity());
+
+        for i in 1..points.len() {
+            assert!(!points[i].is_identity());
+
+            for j in 0..i {
+                assert!(!bool::from(points[i].ct_eq(&points[j])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_torsion() {
+        // (sqrt(-1), 0) has order 4 on edwards25519.
+        let torsion = EdwardsExtended::from_extended(SQRT_M1, ZERO,
+                                                     ONE, ZERO);
+
+        for point in multiples() {
+            let mut moved = point.point();
+
+            for _ in 0..4 {
+                moved += torsion;
+
+                let moved = Ristretto255(moved);
+
+                assert!(bool::from(moved.ct_eq(&point)));
+                assert_eq!(point.encode(), moved.encode());
+            }
+        }
+    }
+
+    #[test]
+    fn test_sub() {
+        let points = multiples();
+
+        for i in 0..points.len() {
+            let neg = -points[i];
+
+            assert!((neg + points[i]).is_identity());
+            assert!(bool::from((Ristretto255::identity() - points[i])
+                                   .ct_eq(&neg)));
+
+            for j in 0..i + 1 {
+                let mut diff = points[i];
+
+                diff -= points[j];
+                assert!(bool::from((points[i] - points[j])
+                                       .ct_eq(&points[i - j])));
+                assert!(bool::from(diff.ct_eq(&points[i - j])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        let mut point = Ristretto255::generator();
+
+        point.scalar_mult(&Ristretto255::order());
+        assert!(point.is_identity());
+    }
+
+    const BAD_ENCODINGS: [&str; 29] = [
+        // Non-canonical field encodings.
+        "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "f3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        // Negative field elements.
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "ed57ffd8c914fb201471d1c3d245ce3c746fcbe63a3679d51b6a516ebebe0e20",
+        "c34c4e1826e5d403b78e246e88aa051c36ccf0aafebffe137d148a2bf9104562",
+        "c940e5a4404157cfb1628b108db051a8d439e1a421394ec4ebccb9ec92a8ac78",
+        "47cfc5497c53dc8e61c91d17fd626ffb1c49e2bca94eed052281b510b1117a24",
+        "f1c6165d33367351b0da8f6e4511010c68174a03b6581212c71c0e1d026c3c72",
+        "87260f7a2f12495118360f02c26a470f450dadf34a413d21042b43b9d93e1309",
+        // Non-square x^2.
+        "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+        "4eac077a713c57b4f4397629a4145982c661f48044dd3f96427d40b147d9742f",
+        "de6a7b00deadc788eb6b6c8d20c0ae96c2f2019078fa604fee5b87d6e989ad7b",
+        "bcab477be20861e01e4a0e295284146a510150d9817763caf1a6f4b422d67042",
+        "2a292df7e32cababbd9de088d1d1abec9fc0440f637ed2fba145094dc14bea08",
+        "f4a9e534fc0d216c44b218fa0c42d99635a0127ee2e53c712f70609649fdff22",
+        "8268436f8c4126196cf64b3c7ddbda90746a378625f9813dd9b8457077256731",
+        "2810e5cbc2cc4d4eece54f61c6f69758e289aa7ab440b3cbeaa21995c2f4232b",
+        // Negative xy value.
+        "3eb858e78f5a7254d8c9731174a94f76755fd3941c0ac93735c07ba14579630e",
+        "a45fdc55c76448c049a1ab33f17023edfb2be3581e9c7aade8a6125215e04220",
+        "d483fe813c6ba647ebbfd3ec41adca1c6130c2beeee9d9bf065c8d151c5f396e",
+        "8a2e1d30050198c65a54483123960ccc38aef6848e1ec8f5f780e8523769ba32",
+        "32888462f8b486c68ad7dd9610be5192bbeaf3b443951ac1a8118419d9fa097b",
+        "227142501b9d4355ccba290404bde41575b037693cef1f438c47f8fbf35d1165",
+        "5c37cc491da847cfeb9281d407efc41e15144c876e0170b499a96a22ed31e01e",
+        "445425117cb8c90edcbc7c1cc0e74f747f2c1efa5630a967c64f287792a48a4b",
+        // s = -1, which causes y = 0.
+        "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f"
+    ];
+
+    #[test]
+    fn test_decode_invalid() {
+        for (i, hex) in BAD_ENCODINGS.iter().enumerate() {
+            let expected = if i < 12 {
+                Error::NonCanonical
+            } else {
+                Error::NotOnCurve
+            };
+
+            assert_eq!(Some(expected),
+                       Ristretto255::decode(&from_hex(hex)).err());
+        }
+
+        assert_eq!(Some(Error::InvalidLength),
+                   Ristretto255::decode(&[0; 31]).err());
+        assert_eq!(Some(Error::InvalidLength),
+                   Ristretto255::decode(&[0; 33]).err());
+    }
+
+    const LABELS: [&str; 7] = [
+        "Ristretto is traditionally a short shot of espresso coffee",
+        "made with the normal amount of ground coffee but extracted with",
+        "about half the amount of water in the same amount of time",
+        "by using a finer grind.",
+        "This produces a concentrated shot of coffee per volume.",
+        "Just pulling a normal shot short will produce a weaker shot",
+        "and is not a Ristretto as some believe."
+    ];
+
+    const HASHED: [&str; 7] = [
+        "3066f82a1a747d45120d1740f14358531a8f04bbffe6a819f86dfe50f44a0a46",
+        "f26e5b6f7d362d2d2a94c5d0e7602cb4773c95a2e5c31a64f133189fa76ed61b",
+        "006ccd2a9e6867e6a2c5cea83d3302cc9de128dd2a9a57dd8ee7b9d7ffe02826",
+        "f8f0c87cf237953c5890aec3998169005dae3eca1fbb04548c635953c817f92a",
+        "ae81e7dedf20a497e10c304a765c1767a42d6e06029758d2d7e8ef7cc4c41179",
+        "e2705652ff9f5e44d3e841bf1c251cf7dddb77d140870d1ab2ed64f1a9ce8628",
+        "80bd07262511cdde4863f8a7434cef696750681cb9510eea557088f76d9e5065"
+    ];
+
+    #[test]
+    fn test_from_uniform_bytes() {
+        for (label, hex) in LABELS.iter().zip(HASHED.iter()) {
+            let hash = sha512(label.as_bytes());
+            let point = Ristretto255::from_uniform_bytes(&hash).unwrap();
+
+            assert!(point.point().is_on_curve());
+            assert_eq!(from_hex(hex), point.encode());
+        }
+
+        assert_eq!(Some(Error::InvalidLength),
+                   Ristretto255::from_uniform_bytes(&[0; 63]).err());
+    }
+}
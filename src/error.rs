@@ -0,0 +1,30 @@
+use std::error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+/// Errors arising from decoding or validating untrusted input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The input had the wrong number of bytes.
+    InvalidLength,
+    /// The input is not the canonical encoding of a value, because it
+    /// is not less than the modulus or has extra high bits set.
+    NonCanonical,
+    /// The input does not describe a point on the curve.
+    NotOnCurve,
+    /// The signature is well-formed, but does not verify.
+    InvalidSignature
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> ::std::fmt::Result {
+        match *self {
+            Error::InvalidLength => write!(f, "invalid length"),
+            Error::NonCanonical => write!(f, "non-canonical encoding"),
+            Error::NotOnCurve => write!(f, "point is not on the curve"),
+            Error::InvalidSignature => write!(f, "invalid signature")
+        }
+    }
+}
+
+impl error::Error for Error {}
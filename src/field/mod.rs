@@ -8,3 +8,4 @@ pub mod mod_e255_19;
 pub mod mod_e251_9;
 pub mod mod_e222_117;
 pub mod mod_e221_3;
+pub mod mod_e448_goldilocks;
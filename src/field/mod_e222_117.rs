@@ -1,3 +1,9 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_i64;
+use error::Error;
 use field::prime_field::PrimeField;
 use field::prime_field::PrimeFieldMask;
 use normalize::Normalize;
@@ -69,11 +75,17 @@ pub const E222_BASE_X: Mod_e222_117 =
 /// The y-coordinate of the base point of the curve E-222, 0x1c.
 pub const E222_BASE_Y: Mod_e222_117 = Mod_e222_117([ 0x1c, 0, 0, 0 ]);
 
-/// The prime subgroup order of the curve M-221,
-/// 2^218 + 438651314700378199859927091142747
-pub const E222_ORDER: Mod_e222_117 =
-    Mod_e222_117([ 0x0002f31423598cbf, 0x03fdc32f257a4cbe,
-                   0x03ffffffffffffff, 0x00003fffffffffff ]);
+/// The non-negative square root of -d for E-222, used by Decaf,
+/// 0x2ab9780e73cbbfe0e2814de2c46e784c39497aa45aafc4b5bd0e9e4e.
+pub const E222_SQRT_MINUS_D: Mod_e222_117 =
+    Mod_e222_117([ 0x02afc4b5bd0e9e4e, 0x039e130e525ea916,
+                   0x03fe0e2814de2c46, 0x0000aae5e039cf2e ]);
+
+/// The inverse of the square root of -d for E-222, used by Decaf,
+/// 0x7bb4a6794f5d724abd4d95ea747eb13326718047c57b2540a91d682.
+pub const E222_INVSQRT_MINUS_D: Mod_e222_117 =
+    Mod_e222_117([ 0x0057b2540a91d682, 0x01fac4cc99c6011f,
+                   0x01724abd4d95ea74, 0x00001eed299e53d7 ]);
 
 impl Debug for Mod_e222_117 {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -121,6 +133,26 @@ impl Mod_e222_117 {
         self.0[3] >> 48
     }
 
+    /// Add 2p to a value whose representation has gone negative.
+    /// Multiplication assumes that every digit is non-negative, so
+    /// this must follow any operation that can borrow out of the
+    /// highest digit.
+    fn fix_sign(&mut self) {
+        let m = self.0[3] >> 63;
+        let s0 = self.0[0] + ((MODULUS.0[0] << 1) & m);
+        let c0 = s0 >> 58;
+        let s1 = self.0[1] + ((MODULUS.0[1] << 1) & m) + c0;
+        let c1 = s1 >> 58;
+        let s2 = self.0[2] + ((MODULUS.0[2] << 1) & m) + c1;
+        let c2 = s2 >> 58;
+        let s3 = self.0[3] + ((MODULUS.0[3] << 1) & m) + c2;
+
+        self.0[0] = s0 & 0x03ffffffffffffff;
+        self.0[1] = s1 & 0x03ffffffffffffff;
+        self.0[2] = s2 & 0x03ffffffffffffff;
+        self.0[3] = s3;
+    }
+
     /// Serialize a value as a little-endian byte array.  This has the
     /// effect of normalizing the representation.
     pub fn packed(&mut self) -> [u8; 28] {
@@ -134,9 +166,9 @@ impl Neg for Mod_e222_117 {
     type Output = Mod_e222_117;
 
     fn neg(self) -> Mod_e222_117 {
-        let mut out = self;
+        let mut out = ZERO;
 
-        out += &MODULUS;
+        out -= &self;
         out
     }
 }
@@ -325,7 +357,7 @@ impl<'b> SubAssign<&'b Mod_e222_117> for Mod_e222_117 {
         let b2 = rhs.0[2];
         let b3 = rhs.0[3] & 0x0000ffffffffffff;
 
-        let cin = self.carry_out() + rhs.carry_out();
+        let cin = self.carry_out() - rhs.carry_out();
         let s0 = a0 - b0 + (cin * C_VAL);
         let c0 = s0 >> 58;
         let s1 = a1 - b1 + c0;
@@ -338,6 +370,8 @@ impl<'b> SubAssign<&'b Mod_e222_117> for Mod_e222_117 {
         self.0[1] = s1 & 0x03ffffffffffffff;
         self.0[2] = s2 & 0x03ffffffffffffff;
         self.0[3] = s3;
+
+        self.fix_sign();
     }
 }
 
@@ -746,13 +780,20 @@ impl Mul<Mod_e222_117> for Mod_e222_117 {
 
 impl Rand for Mod_e222_117 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        let mut out = Mod_e222_117([0i64; 4]);
+        let mut bytes = [0u8; 28];
+        let mut out = ZERO;
 
-        for i in 0..4 {
-            out.0[i] = rng.gen_range(0, MODULUS.0[i]);
-        }
+        // Rejection sampling: draw 222-bit values until one is less
+        // than the modulus.  This is uniform, and almost never
+        // takes more than one try.
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[27] &= 0x3f;
 
-        out
+            if out.unpack_checked(&bytes).is_ok() {
+                return out;
+            }
+        }
     }
 }
 
@@ -797,6 +838,38 @@ impl Pack for Mod_e222_117 {
         out
     }
 
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 28 {
+            return Err(Error::InvalidLength);
+        }
+
+        if bytes[27] & 0xc0 != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        // Adding C carries out of the top digit exactly when the
+        // value is at least the modulus.
+        if (&out + (C_VAL as i32)).carry_out() != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        *self = out;
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
     fn pack(&mut self, bytes: &mut [u8]) {
         self.normalize();
         self.pack_normalized(bytes)
@@ -900,6 +973,38 @@ impl PrimeFieldMask for Mod_e222_117_Mask {
     }
 }
 
+impl ConditionallySelectable for Mod_e222_117 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = Mod_e222_117_Mask::filled(choice.into());
+
+        Mod_e222_117([ a.0[0] ^ ((a.0[0] ^ b.0[0]) & mask.0[0]),
+                       a.0[1] ^ ((a.0[1] ^ b.0[1]) & mask.0[1]),
+                       a.0[2] ^ ((a.0[2] ^ b.0[2]) & mask.0[2]),
+                       a.0[3] ^ ((a.0[3] ^ b.0[3]) & mask.0[3]) ])
+    }
+}
+
+impl ConditionallyNegatable for Mod_e222_117 {
+    fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -*self;
+
+        self.conditional_assign(&neg, choice);
+    }
+}
+
+impl ConstantTimeEq for Mod_e222_117 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.normalize();
+        b.normalize();
+
+        is_zero_i64((a.0[0] ^ b.0[0]) | (a.0[1] ^ b.0[1]) |
+                    (a.0[2] ^ b.0[2]) | (a.0[3] ^ b.0[3]))
+    }
+}
+
 impl PrimeField for Mod_e222_117 {
     fn fill(&mut self, bit: bool) {
         let mut mask = bit as i64;
@@ -1292,6 +1397,8 @@ impl PrimeField for Mod_e222_117 {
         self.0[1] = s1 & 0x03ffffffffffffff;
         self.0[2] = s2 & 0x03ffffffffffffff;
         self.0[3] = s3;
+
+        self.fix_sign();
     }
 
     fn small_add(&self, rhs: i32) -> Mod_e222_117 {
@@ -1323,6 +1430,8 @@ impl PrimeField for Mod_e222_117 {
         self.0[1] = s1 & 0x03ffffffffffffff;
         self.0[2] = s2 & 0x03ffffffffffffff;
         self.0[3] = s3;
+
+        self.fix_sign();
     }
 
     fn small_sub(&self, rhs: i32) -> Mod_e222_117 {
@@ -1341,7 +1450,7 @@ impl PrimeField for Mod_e222_117 {
         let a4 = self.0[2] & 0x1fffffff;
         let a5 = self.0[2] >> 29;
         let a6 = self.0[3] & 0x1fffffff;
-        let a7 = self.0[3] >> 29;
+        let a7 = (self.0[3] & 0x0000ffffffffffff) >> 29;
 
         let b = i64::from(rhs);
 
@@ -1355,18 +1464,31 @@ impl PrimeField for Mod_e222_117 {
         let m7 = a7 * b;
 
         let cin = self.carry_out();
-        let d0 = m0 + ((m1 & 0x1fffffff) << 29) + (cin * C_VAL);
+        let d0 = m0 + ((m1 & 0x1fffffff) << 29) + (cin * C_VAL * b);
         let c0 = d0 >> 58;
         let d1 = (m1 >> 29) + m2 + ((m3 & 0x1fffffff) << 29) + c0;
         let c1 = d1 >> 58;
         let d2 = (m3 >> 29) + m4 + ((m5 & 0x1fffffff) << 29) + c1;
         let c2 = d2 >> 58;
-        let d3 = (m5 >> 29) + m6 + (m7 << 29) + c2;
+        let d3 = (m5 >> 29) + m6 +
+                 ((m7 & 0x1fffffff) << 29) + c2;
+
+        // Fold everything above bit 222 back in, multiplied by C.
+        let over = (d3 >> 48) + ((m7 >> 29) << 10);
+        let s0 = (d0 & 0x03ffffffffffffff) + (over * C_VAL);
+        let k0 = s0 >> 58;
+        let s1 = (d1 & 0x03ffffffffffffff) + k0;
+        let k1 = s1 >> 58;
+        let s2 = (d2 & 0x03ffffffffffffff) + k1;
+        let k2 = s2 >> 58;
+        let s3 = (d3 & 0x0000ffffffffffff) + k2;
+
+        self.0[0] = s0 & 0x03ffffffffffffff;
+        self.0[1] = s1 & 0x03ffffffffffffff;
+        self.0[2] = s2 & 0x03ffffffffffffff;
+        self.0[3] = s3;
 
-        self.0[0] = d0 & 0x03ffffffffffffff;
-        self.0[1] = d1 & 0x03ffffffffffffff;
-        self.0[2] = d2 & 0x03ffffffffffffff;
-        self.0[3] = d3;
+        self.fix_sign();
     }
 
     fn small_mul(&self, b: i32) -> Mod_e222_117 {
@@ -1376,12 +1498,40 @@ impl PrimeField for Mod_e222_117 {
 
         out
     }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        let ndigits = (8 * bytes.len() + 221) / 222;
+        let mut buf = [0u8; 28];
+        let mut out = ZERO;
+
+        // Read the input as digits in base 2^222, and evaluate it
+        // by Horner's rule, using 2^222 = 117 mod p.
+        for j in 0..ndigits {
+            let base = (ndigits - 1 - j) * 222;
+
+            for i in 0..28 {
+                let off = base + (8 * i);
+                let lo = bytes.get(off / 8).map_or(0, |b| *b as u16);
+                let hi = bytes.get((off / 8) + 1).map_or(0, |b| *b as u16);
+
+                buf[i] = (((hi << 8) | lo) >> (off % 8)) as u8;
+            }
+
+            buf[27] &= 0x3f;
+            out = out.small_mul(C_VAL as i32) + Mod_e222_117::unpacked(&buf);
+        }
+
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use choice::*;
+    use error::Error;
     use field::prime_field::*;
     use field::mod_e222_117::*;
+    use rand::thread_rng;
 
     const TWO: Mod_e222_117 = Mod_e222_117([ 2, 0, 0, 0 ]);
 
@@ -3260,4 +3410,192 @@ mod tests {
             assert!(M_FOUR.normalize_eq(&mut val));
         }
     }
+
+    #[test]
+    fn test_neg() {
+        let l1_zeros: [&mut Mod_e222_117; 3] = [ &mut -ZERO,
+                                          &mut (&-ONE + &ONE),
+                                          &mut (&-M_TWO + &M_TWO) ];
+
+        let l1_mones: [&mut Mod_e222_117; 3] = [ &mut -ONE,
+                                          &mut -(&TWO - &ONE),
+                                          &mut (&-TWO + &ONE) ];
+
+        let l1_sixes: [&mut Mod_e222_117; 4] = [ &mut -M_SIX,
+                                          &mut (&-TWO * &M_THREE),
+                                          &mut (&(&M_ONE - &ONE) * &-THREE),
+                                          &mut (&(&ZERO - &TWO) -
+                                                &(&(&ONE - &NINE) * &ONE)) ];
+
+        for i in 0..3 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(M_ONE.normalize_eq(l1_mones[i]));
+        }
+
+        for i in 0..4 {
+            assert!(SIX.normalize_eq(l1_sixes[i]));
+        }
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+        let mut a = TWO;
+        let mut b = M_THREE;
+        let mut sel_f = Mod_e222_117::conditional_select(&TWO, &THREE, f);
+        let mut sel_t = Mod_e222_117::conditional_select(&TWO, &THREE, t);
+
+        assert!(TWO.normalize_eq(&mut sel_f));
+        assert!(THREE.normalize_eq(&mut sel_t));
+
+        Mod_e222_117::conditional_swap(&mut a, &mut b, f);
+        assert!(TWO.normalize_eq(&mut a));
+        assert!(M_THREE.normalize_eq(&mut b));
+
+        Mod_e222_117::conditional_swap(&mut a, &mut b, t);
+        assert!(M_THREE.normalize_eq(&mut a));
+        assert!(TWO.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_conditional_negate() {
+        let mut a = SIX;
+        let mut b = SIX;
+
+        a.conditional_negate(Choice::from(0));
+        b.conditional_negate(Choice::from(1));
+
+        assert!(SIX.normalize_eq(&mut a));
+        assert!(M_SIX.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(ZERO.ct_eq(&ZERO)));
+        assert!(bool::from(ZERO.ct_eq(&MODULUS)));
+        assert!(bool::from(ZERO.ct_eq(&(M_ONE + ONE))));
+        assert!(bool::from(SIX.ct_eq(&(TWO * THREE))));
+        assert!(bool::from(M_SIX.ct_eq(&(M_TWO * THREE))));
+        assert!(!bool::from(ZERO.ct_eq(&ONE)));
+        assert!(!bool::from(SIX.ct_eq(&M_SIX)));
+        assert!(!bool::from(TWO.ct_eq(&THREE)));
+    }
+
+    #[test]
+    fn test_sqrt_checked() {
+        let four_root = FOUR.sqrt_checked().unwrap();
+        let nine_root = NINE.sqrt_checked().unwrap();
+        let mut zero_root = ZERO.sqrt_checked().unwrap();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(ZERO.normalize_eq(&mut zero_root));
+        assert!(bool::from(SIXTEEN.sqrt_checked().is_some()));
+        assert!(bool::from(M_ONE.sqrt_checked().is_none()));
+        assert!(bool::from(M_FOUR.sqrt_checked().is_none()));
+    }
+
+    #[test]
+    fn test_invert_checked() {
+        let two_inv = TWO.invert_checked().unwrap();
+        let m_six_inv = M_SIX.invert_checked().unwrap();
+
+        assert!(ONE.normalize_eq(&mut (two_inv * TWO)));
+        assert!(ONE.normalize_eq(&mut (m_six_inv * M_SIX)));
+        assert!(bool::from(ZERO.invert_checked().is_none()));
+        assert!(bool::from(MODULUS.invert_checked().is_none()));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+        let mut long = [0u8; 29];
+        let mut actual = ZERO;
+
+        assert!(M_ONE.normalize_eq(&mut Mod_e222_117::unpacked_checked(&bytes)
+                                            .unwrap()));
+        assert!(actual.unpack_checked(&TWO.packed()).is_ok());
+        assert!(TWO.normalize_eq(&mut actual));
+        assert!(actual.unpack_checked(&ZERO.packed()).is_ok());
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        // The modulus itself.
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&bytes));
+
+        // Bits above the modulus.
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&[0xffu8; 28]));
+
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        long[0] = 1;
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long));
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long[0..27]));
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut rng = thread_rng();
+        let mut counts = [0u32; 222];
+
+        // Each bit of a uniform value is set about half the time.
+        for _ in 0..2000 {
+            let mut val = Mod_e222_117::rand(&mut rng);
+            let bytes = val.packed();
+
+            assert!(Mod_e222_117::unpacked_checked(&bytes).is_ok());
+
+            for i in 0..222 {
+                counts[i] += ((bytes[i / 8] >> (i % 8)) & 0x1) as u32;
+            }
+        }
+
+        for i in 0..222 {
+            assert!(counts[i] > 800 && counts[i] < 1200,
+                    "bit {} set {} times", i, counts[i]);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        const ONES: Mod_e222_117 = Mod_e222_117([ 0x3578f, 0, 0, 0 ]);
+
+        const SEQ: Mod_e222_117 =
+            Mod_e222_117([ 0x004b3e31241a5f5e, 0x0333f0ad6a26e416,
+                           0x02154473a2d2014d, 0x0000632efac69265 ]);
+
+        let mut bytes = [0u8; 56];
+        let mut out;
+
+        for i in 0..56 {
+            bytes[i] = ((i * 37) + 11) as u8;
+        }
+
+        out = Mod_e222_117::from_bytes_wide(&bytes);
+        assert!(SEQ.normalize_eq(&mut out));
+        out = Mod_e222_117::from_bytes_wide(&[0xff; 56]);
+        assert!(ONES.normalize_eq(&mut out));
+
+        // Values that fit in the field are unchanged.
+        bytes = [0u8; 56];
+        bytes[0..28].copy_from_slice(&M_ONE.packed());
+        out = Mod_e222_117::from_bytes_wide(&bytes);
+        assert!(M_ONE.normalize_eq(&mut out));
+
+        bytes[0] += 1;
+        out = Mod_e222_117::from_bytes_wide(&bytes);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e222_117::from_bytes_wide(&[]);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e222_117::from_bytes_wide(&[2]);
+        assert!(TWO.normalize_eq(&mut out));
+    }
 }
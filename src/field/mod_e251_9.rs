@@ -1,3 +1,9 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_i64;
+use error::Error;
 use field::prime_field::PrimeField;
 use field::prime_field::PrimeFieldMask;
 use normalize::Normalize;
@@ -80,12 +86,40 @@ pub const CURVE1174_BASE_Y: Mod_e251_9 =
                  0x001169840e0c4fe2, 0x00d47fb7cc665684,
                  0x0000000006b72f82 ]);
 
-/// The prime subgroup order of the curve Curve1174,
-/// 2^249 - 11332719920821432534773113288178349711
-pub const CURVE1174_ORDER: Mod_e251_9 =
-    Mod_e251_9([ 0x0044d45fd166c971, 0x0065c4dfd3073489,
-                 0x00fffffffffff779, 0x00ffffffffffffff,
-                 0x0000000001ffffff ]);
+/// The non-negative square root of -d for Curve1174, used by Decaf,
+/// 0x248735db918f0e01d73afa84a3003c60e32cee912213066fcc857b5f0244c18.
+pub const CURVE1174_SQRT_MINUS_D: Mod_e251_9 =
+    Mod_e251_9([ 0x00c857b5f0244c18, 0x00cee912213066fc,
+                 0x00a84a3003c60e32, 0x00b918f0e01d73af,
+                 0x000000000248735d ]);
+
+/// The inverse of the square root of -d for Curve1174, used by Decaf,
+/// 0x1c98c17697ac9b0d15c5648520d8f7c3e606cc4e303846702d4fec4306535b3.
+pub const CURVE1174_INVSQRT_MINUS_D: Mod_e251_9 =
+    Mod_e251_9([ 0x00d4fec4306535b3, 0x006cc4e303846702,
+                 0x0048520d8f7c3e60, 0x00697ac9b0d15c56,
+                 0x0000000001c98c17 ]);
+
+/// The parameter s of the Elligator 1 map for Curve1174,
+/// 0x3fe707f0d7004fd334ee813a5f1a74ab2449139c82c39d84a09ae74cc78c615.
+pub const CURVE1174_ELLIGATOR_S: Mod_e251_9 =
+    Mod_e251_9([ 0x0009ae74cc78c615, 0x009139c82c39d84a,
+                 0x0013a5f1a74ab244, 0x000d7004fd334ee8,
+                 0x0000000003fe707f ]);
+
+/// The parameter c = 2/s^2 of the Elligator 1 map for Curve1174,
+/// 0x4d1a3398ed42ceeb451d20824ca9cb49b69ef546bd7e6546aef19af1f9e49e1.
+pub const CURVE1174_ELLIGATOR_C: Mod_e251_9 =
+    Mod_e251_9([ 0x00ef19af1f9e49e1, 0x00ef546bd7e6546a,
+                 0x000824ca9cb49b69, 0x008ed42ceeb451d2,
+                 0x0000000004d1a339 ]);
+
+/// The parameter r = c + 1/c of the Elligator 1 map for Curve1174,
+/// 0x6006fbda7649c433816b286006fbda7649c433816b286006fbda7649c43383.
+pub const CURVE1174_ELLIGATOR_R: Mod_e251_9 =
+    Mod_e251_9([ 0x00fbda7649c43383, 0x00c433816b286006,
+                 0x00286006fbda7649, 0x00da7649c433816b,
+                 0x00000000006006fb ]);
 
 impl Debug for Mod_e251_9 {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -133,6 +167,29 @@ impl Mod_e251_9 {
         self.0[4] >> 27
     }
 
+    /// Add 2p to a value whose representation has gone negative.
+    /// Multiplication assumes that every digit is non-negative, so
+    /// this must follow any operation that can borrow out of the
+    /// highest digit.
+    fn fix_sign(&mut self) {
+        let m = self.0[4] >> 63;
+        let s0 = self.0[0] + ((MODULUS.0[0] << 1) & m);
+        let c0 = s0 >> 56;
+        let s1 = self.0[1] + ((MODULUS.0[1] << 1) & m) + c0;
+        let c1 = s1 >> 56;
+        let s2 = self.0[2] + ((MODULUS.0[2] << 1) & m) + c1;
+        let c2 = s2 >> 56;
+        let s3 = self.0[3] + ((MODULUS.0[3] << 1) & m) + c2;
+        let c3 = s3 >> 56;
+        let s4 = self.0[4] + ((MODULUS.0[4] << 1) & m) + c3;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4;
+    }
+
     /// Serialize a value as a little-endian byte array.  This has the
     /// effect of normalizing the representation.
     pub fn packed(&mut self) -> [u8; 32] {
@@ -146,9 +203,9 @@ impl Neg for Mod_e251_9 {
     type Output = Mod_e251_9;
 
     fn neg(self) -> Mod_e251_9 {
-        let mut out = self;
+        let mut out = ZERO;
 
-        out += &MODULUS;
+        out -= &self;
         out
     }
 }
@@ -344,7 +401,7 @@ impl<'b> SubAssign<&'b Mod_e251_9> for Mod_e251_9 {
         let b3 = rhs.0[3];
         let b4 = rhs.0[4] & 0x0000000007ffffff;
 
-        let cin = self.carry_out() + rhs.carry_out();
+        let cin = self.carry_out() - rhs.carry_out();
         let s0 = a0 - b0 + (cin * C_VAL);
         let c0 = s0 >> 56;
         let s1 = a1 - b1 + c0;
@@ -360,6 +417,8 @@ impl<'b> SubAssign<&'b Mod_e251_9> for Mod_e251_9 {
         self.0[2] = s2 & 0x00ffffffffffffff;
         self.0[3] = s3 & 0x00ffffffffffffff;
         self.0[4] = s4;
+
+        self.fix_sign();
     }
 }
 
@@ -784,13 +843,20 @@ impl Mul<Mod_e251_9> for Mod_e251_9 {
 
 impl Rand for Mod_e251_9 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        let mut out = Mod_e251_9([0i64; 5]);
+        let mut bytes = [0u8; 32];
+        let mut out = ZERO;
 
-        for i in 0..5 {
-            out.0[i] = rng.gen_range(0, MODULUS.0[i]);
-        }
+        // Rejection sampling: draw 251-bit values until one is less
+        // than the modulus.  This is uniform, and almost never
+        // takes more than one try.
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[31] &= 0x07;
 
-        out
+            if out.unpack_checked(&bytes).is_ok() {
+                return out;
+            }
+        }
     }
 }
 
@@ -836,6 +902,38 @@ impl Pack for Mod_e251_9 {
         out
     }
 
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 32 {
+            return Err(Error::InvalidLength);
+        }
+
+        if bytes[31] & 0xf8 != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        // Adding C carries out of the top digit exactly when the
+        // value is at least the modulus.
+        if (&out + (C_VAL as i32)).carry_out() != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        *self = out;
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
     fn pack(&mut self, bytes: &mut [u8]) {
         self.normalize();
         self.pack_normalized(bytes)
@@ -942,6 +1040,39 @@ impl PrimeFieldMask for Mod_e251_9_Mask {
     }
 }
 
+impl ConditionallySelectable for Mod_e251_9 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = Mod_e251_9_Mask::filled(choice.into());
+
+        Mod_e251_9([ a.0[0] ^ ((a.0[0] ^ b.0[0]) & mask.0[0]),
+                     a.0[1] ^ ((a.0[1] ^ b.0[1]) & mask.0[1]),
+                     a.0[2] ^ ((a.0[2] ^ b.0[2]) & mask.0[2]),
+                     a.0[3] ^ ((a.0[3] ^ b.0[3]) & mask.0[3]),
+                     a.0[4] ^ ((a.0[4] ^ b.0[4]) & mask.0[4]) ])
+    }
+}
+
+impl ConditionallyNegatable for Mod_e251_9 {
+    fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -*self;
+
+        self.conditional_assign(&neg, choice);
+    }
+}
+
+impl ConstantTimeEq for Mod_e251_9 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.normalize();
+        b.normalize();
+
+        is_zero_i64((a.0[0] ^ b.0[0]) | (a.0[1] ^ b.0[1]) |
+                    (a.0[2] ^ b.0[2]) | (a.0[3] ^ b.0[3]) | (a.0[4] ^ b.0[4]))
+    }
+}
+
 impl PrimeField for Mod_e251_9 {
     fn fill(&mut self, bit: bool) {
         let mut mask = bit as i64;
@@ -1341,6 +1472,8 @@ impl PrimeField for Mod_e251_9 {
         self.0[2] = s2 & 0x00ffffffffffffff;
         self.0[3] = s3 & 0x00ffffffffffffff;
         self.0[4] = s4;
+
+        self.fix_sign();
     }
 
     fn small_add(&self, rhs: i32) -> Self {
@@ -1376,6 +1509,8 @@ impl PrimeField for Mod_e251_9 {
         self.0[2] = s2 & 0x00ffffffffffffff;
         self.0[3] = s3 & 0x00ffffffffffffff;
         self.0[4] = s4;
+
+        self.fix_sign();
     }
 
     fn small_sub(&self, rhs: i32) -> Self {
@@ -1395,7 +1530,7 @@ impl PrimeField for Mod_e251_9 {
         let a5 = self.0[2] >> 28;
         let a6 = self.0[3] & 0x0fffffff;
         let a7 = self.0[3] >> 28;
-        let a8 = self.0[4];
+        let a8 = self.0[4] & 0x0000000007ffffff;
 
         let b = i64::from(rhs);
 
@@ -1410,7 +1545,7 @@ impl PrimeField for Mod_e251_9 {
         let m8 = a8 * b;
 
         let cin = self.carry_out();
-        let d0 = m0 + ((m1 & 0x0fffffff) << 28) + (cin * C_VAL);
+        let d0 = m0 + ((m1 & 0x0fffffff) << 28) + (cin * C_VAL * b);
         let c0 = d0 >> 56;
         let d1 = (m1 >> 28) + m2 + ((m3 & 0x0fffffff) << 28) + c0;
         let c1 = d1 >> 56;
@@ -1420,11 +1555,25 @@ impl PrimeField for Mod_e251_9 {
         let c3 = d3 >> 56;
         let d4 = (m7 >> 28) + m8 + c3;
 
-        self.0[0] = d0 & 0x00ffffffffffffff;
-        self.0[1] = d1 & 0x00ffffffffffffff;
-        self.0[2] = d2 & 0x00ffffffffffffff;
-        self.0[3] = d3 & 0x00ffffffffffffff;
-        self.0[4] = d4;
+        // Fold everything above bit 251 back in, multiplied by C.
+        let over = d4 >> 27;
+        let s0 = (d0 & 0x00ffffffffffffff) + (over * C_VAL);
+        let k0 = s0 >> 56;
+        let s1 = (d1 & 0x00ffffffffffffff) + k0;
+        let k1 = s1 >> 56;
+        let s2 = (d2 & 0x00ffffffffffffff) + k1;
+        let k2 = s2 >> 56;
+        let s3 = (d3 & 0x00ffffffffffffff) + k2;
+        let k3 = s3 >> 56;
+        let s4 = (d4 & 0x0000000007ffffff) + k3;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4;
+
+        self.fix_sign();
     }
 
     fn small_mul(&self, b: i32) -> Self {
@@ -1434,12 +1583,40 @@ impl PrimeField for Mod_e251_9 {
 
         out
     }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        let ndigits = (8 * bytes.len() + 250) / 251;
+        let mut buf = [0u8; 32];
+        let mut out = ZERO;
+
+        // Read the input as digits in base 2^251, and evaluate it
+        // by Horner's rule, using 2^251 = 9 mod p.
+        for j in 0..ndigits {
+            let base = (ndigits - 1 - j) * 251;
+
+            for i in 0..32 {
+                let off = base + (8 * i);
+                let lo = bytes.get(off / 8).map_or(0, |b| *b as u16);
+                let hi = bytes.get((off / 8) + 1).map_or(0, |b| *b as u16);
+
+                buf[i] = (((hi << 8) | lo) >> (off % 8)) as u8;
+            }
+
+            buf[31] &= 0x07;
+            out = out.small_mul(C_VAL as i32) + Mod_e251_9::unpacked(&buf);
+        }
+
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use choice::*;
+    use error::Error;
     use field::prime_field::*;
     use field::mod_e251_9::*;
+    use rand::thread_rng;
 
     const TWO: Mod_e251_9 = Mod_e251_9([ 2, 0, 0, 0, 0 ]);
 
@@ -3342,4 +3519,193 @@ mod tests {
             assert!(M_FOUR.normalize_eq(&mut val));
         }
     }
+
+    #[test]
+    fn test_neg() {
+        let l1_zeros: [&mut Mod_e251_9; 3] = [ &mut -ZERO,
+                                          &mut (&-ONE + &ONE),
+                                          &mut (&-M_TWO + &M_TWO) ];
+
+        let l1_mones: [&mut Mod_e251_9; 3] = [ &mut -ONE,
+                                          &mut -(&TWO - &ONE),
+                                          &mut (&-TWO + &ONE) ];
+
+        let l1_sixes: [&mut Mod_e251_9; 4] = [ &mut -M_SIX,
+                                          &mut (&-TWO * &M_THREE),
+                                          &mut (&(&M_ONE - &ONE) * &-THREE),
+                                          &mut (&(&ZERO - &TWO) -
+                                                &(&(&ONE - &NINE) * &ONE)) ];
+
+        for i in 0..3 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(M_ONE.normalize_eq(l1_mones[i]));
+        }
+
+        for i in 0..4 {
+            assert!(SIX.normalize_eq(l1_sixes[i]));
+        }
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+        let mut a = TWO;
+        let mut b = M_THREE;
+        let mut sel_f = Mod_e251_9::conditional_select(&TWO, &THREE, f);
+        let mut sel_t = Mod_e251_9::conditional_select(&TWO, &THREE, t);
+
+        assert!(TWO.normalize_eq(&mut sel_f));
+        assert!(THREE.normalize_eq(&mut sel_t));
+
+        Mod_e251_9::conditional_swap(&mut a, &mut b, f);
+        assert!(TWO.normalize_eq(&mut a));
+        assert!(M_THREE.normalize_eq(&mut b));
+
+        Mod_e251_9::conditional_swap(&mut a, &mut b, t);
+        assert!(M_THREE.normalize_eq(&mut a));
+        assert!(TWO.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_conditional_negate() {
+        let mut a = SIX;
+        let mut b = SIX;
+
+        a.conditional_negate(Choice::from(0));
+        b.conditional_negate(Choice::from(1));
+
+        assert!(SIX.normalize_eq(&mut a));
+        assert!(M_SIX.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(ZERO.ct_eq(&ZERO)));
+        assert!(bool::from(ZERO.ct_eq(&MODULUS)));
+        assert!(bool::from(ZERO.ct_eq(&(M_ONE + ONE))));
+        assert!(bool::from(SIX.ct_eq(&(TWO * THREE))));
+        assert!(bool::from(M_SIX.ct_eq(&(M_TWO * THREE))));
+        assert!(!bool::from(ZERO.ct_eq(&ONE)));
+        assert!(!bool::from(SIX.ct_eq(&M_SIX)));
+        assert!(!bool::from(TWO.ct_eq(&THREE)));
+    }
+
+    #[test]
+    fn test_sqrt_checked() {
+        let four_root = FOUR.sqrt_checked().unwrap();
+        let nine_root = NINE.sqrt_checked().unwrap();
+        let mut zero_root = ZERO.sqrt_checked().unwrap();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(ZERO.normalize_eq(&mut zero_root));
+        assert!(bool::from(SIXTEEN.sqrt_checked().is_some()));
+        assert!(bool::from(M_ONE.sqrt_checked().is_none()));
+        assert!(bool::from(M_FOUR.sqrt_checked().is_none()));
+    }
+
+    #[test]
+    fn test_invert_checked() {
+        let two_inv = TWO.invert_checked().unwrap();
+        let m_six_inv = M_SIX.invert_checked().unwrap();
+
+        assert!(ONE.normalize_eq(&mut (two_inv * TWO)));
+        assert!(ONE.normalize_eq(&mut (m_six_inv * M_SIX)));
+        assert!(bool::from(ZERO.invert_checked().is_none()));
+        assert!(bool::from(MODULUS.invert_checked().is_none()));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+        let mut long = [0u8; 33];
+        let mut actual = ZERO;
+
+        assert!(M_ONE.normalize_eq(&mut Mod_e251_9::unpacked_checked(&bytes)
+                                            .unwrap()));
+        assert!(actual.unpack_checked(&TWO.packed()).is_ok());
+        assert!(TWO.normalize_eq(&mut actual));
+        assert!(actual.unpack_checked(&ZERO.packed()).is_ok());
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        // The modulus itself.
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&bytes));
+
+        // Bits above the modulus.
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&[0xffu8; 32]));
+
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        long[0] = 1;
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long));
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long[0..31]));
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut rng = thread_rng();
+        let mut counts = [0u32; 251];
+
+        // Each bit of a uniform value is set about half the time.
+        for _ in 0..2000 {
+            let mut val = Mod_e251_9::rand(&mut rng);
+            let bytes = val.packed();
+
+            assert!(Mod_e251_9::unpacked_checked(&bytes).is_ok());
+
+            for i in 0..251 {
+                counts[i] += ((bytes[i / 8] >> (i % 8)) & 0x1) as u32;
+            }
+        }
+
+        for i in 0..251 {
+            assert!(counts[i] > 800 && counts[i] < 1200,
+                    "bit {} set {} times", i, counts[i]);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        const ONES: Mod_e251_9 = Mod_e251_9([ 0x143ff, 0, 0, 0, 0 ]);
+
+        const SEQ: Mod_e251_9 =
+            Mod_e251_9([ 0x007a8b9dcee0211c, 0x00e0f20435465769,
+                         0x0047586a9bacbdcf, 0x00adbed102132436,
+                         0x0000000000798a9c ]);
+
+        let mut bytes = [0u8; 64];
+        let mut out;
+
+        for i in 0..64 {
+            bytes[i] = ((i * 37) + 11) as u8;
+        }
+
+        out = Mod_e251_9::from_bytes_wide(&bytes);
+        assert!(SEQ.normalize_eq(&mut out));
+        out = Mod_e251_9::from_bytes_wide(&[0xff; 64]);
+        assert!(ONES.normalize_eq(&mut out));
+
+        // Values that fit in the field are unchanged.
+        bytes = [0u8; 64];
+        bytes[0..32].copy_from_slice(&M_ONE.packed());
+        out = Mod_e251_9::from_bytes_wide(&bytes);
+        assert!(M_ONE.normalize_eq(&mut out));
+
+        bytes[0] += 1;
+        out = Mod_e251_9::from_bytes_wide(&bytes);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e251_9::from_bytes_wide(&[]);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e251_9::from_bytes_wide(&[2]);
+        assert!(TWO.normalize_eq(&mut out));
+    }
 }
@@ -1,3 +1,9 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_i64;
+use error::Error;
 use field::prime_field::PrimeField;
 use field::prime_field::PrimeFieldMask;
 use normalize::Normalize;
@@ -79,12 +85,108 @@ pub const CURVE25519_BASE_Y: Mod_e255_19 =
                   0x002c7748d14c923d, 0x00b8a086b4e01edd,
                   0x0000000020ae19a1 ]);
 
-/// The prime subgroup order of the curve Curve25519,
-/// 2^252 + 27742317777372353535851937790883648493
-pub const CURVE25519_ORDER: Mod_e255_19 =
-    Mod_e255_19([ 0x0012631a5cf5d3ed, 0x00f9dea2f79cd658,
-                  0x00000000000014de, 0x0000000000000000,
-                  0x0000000010000000 ]);
+/// The A value 486662 in the Curve25519 equation y^2 = x^3 + Ax^2 + x.
+pub const CURVE25519_A: Mod_e255_19 = Mod_e255_19([ 0x76d06, 0, 0, 0, 0 ]);
+
+/// The value (A + 2) / 4 = 121666 for the Curve25519 equation
+/// y^2 = x^3 + Ax^2 + x, used by the Montgomery ladder.
+pub const CURVE25519_A24: Mod_e255_19 = Mod_e255_19([ 0x1db42, 0, 0, 0, 0 ]);
+
+/// The x-coordinate of the base point of the curve Curve25519 plus a
+/// point of order 8, which generates the whole group,
+/// 0xf47a44b3b56149459079c91cdbe90856cdccc1652d970779758dec8b76cfc34.
+pub const CURVE25519_DIRTY_BASE_X: Mod_e255_19 =
+    Mod_e255_19([ 0x0058dec8b76cfc34, 0x00cc1652d9707797,
+                  0x0091cdbe90856cdc, 0x003b56149459079c,
+                  0x000000000f47a44b ]);
+
+/// The d value -121665/121666 in the twisted Edwards curve equation
+/// -x^2 + y^2 = 1 + dx^2 y^2 for Ed25519,
+/// 0x52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3.
+pub const ED25519_D: Mod_e255_19 =
+    Mod_e255_19([ 0x00eb4dca135978a3, 0x000a4d4141d8ab75,
+                  0x00797779e8980070, 0x002b6ffe738cc740,
+                  0x0000000052036cee ]);
+
+/// Twice the d value for Ed25519, which the addition formula for
+/// a = -1 uses,
+/// 0x2406d9dc56dffce7198e80f2eef3d13000e0149a8283b156ebd69b9426b2f159.
+pub const ED25519_D2: Mod_e255_19 =
+    Mod_e255_19([ 0x00d69b9426b2f159, 0x00149a8283b156eb,
+                  0x00f2eef3d13000e0, 0x0056dffce7198e80,
+                  0x000000002406d9dc ]);
+
+/// The x-coordinate of the base point of the curve Ed25519,
+/// 0x216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a.
+pub const ED25519_BASE_X: Mod_e255_19 =
+    Mod_e255_19([ 0x00562d608f25d51a, 0x00c7609525a7b2c9,
+                  0x0031fdd6dc5c692c, 0x00cd6e53fec0a4e2,
+                  0x00000000216936d3 ]);
+
+/// The y-coordinate of the base point of the curve Ed25519, 4/5,
+/// 0x6666666666666666666666666666666666666666666666666666666666666658.
+pub const ED25519_BASE_Y: Mod_e255_19 =
+    Mod_e255_19([ 0x0066666666666658, 0x0066666666666666,
+                  0x0066666666666666, 0x0066666666666666,
+                  0x0000000066666666 ]);
+
+/// The x-coordinate of a point of order 8 on the curve Ed25519,
+/// 0x602a465ff9c6b5d716cc66cdc721b544a3e6c38fec1a1dc7215eb9b93aba2ea3.
+pub const ED25519_TORSION_X: Mod_e255_19 =
+    Mod_e255_19([ 0x005eb9b93aba2ea3, 0x00c38fec1a1dc721,
+                  0x00cdc721b544a3e6, 0x00f9c6b5d716cc66,
+                  0x00000000602a465f ]);
+
+/// The y-coordinate of a point of order 8 on the curve Ed25519,
+/// 0x05fc536d880238b13933c6d305acdfd5f098eff289f4c345b027b2c28f95e826.
+pub const ED25519_TORSION_Y: Mod_e255_19 =
+    Mod_e255_19([ 0x0027b2c28f95e826, 0x00eff289f4c345b0,
+                  0x00d305acdfd5f098, 0x00880238b13933c6,
+                  0x0000000005fc536d ]);
+
+/// The square root of -1, 2^((p - 1)/4),
+/// 0x2b8324804fc1df0b2b4d00993dfbd7a72f431806ad2fe478c4ee1b274a0ea0b0.
+pub const SQRT_M1: Mod_e255_19 =
+    Mod_e255_19([ 0x00ee1b274a0ea0b0, 0x001806ad2fe478c4,
+                  0x00993dfbd7a72f43, 0x004fc1df0b2b4d00,
+                  0x000000002b832480 ]);
+
+/// The square root of ad - 1 for Ed25519, used by Ristretto,
+/// 0x376931bf2b8348ac0f3cfcc931f5d1fdaf9d8e0c1b7854bd7e97f6a0497b2e1b.
+pub const RISTRETTO_SQRT_AD_MINUS_ONE: Mod_e255_19 =
+    Mod_e255_19([ 0x0097f6a0497b2e1b, 0x008e0c1b7854bd7e,
+                  0x00c931f5d1fdaf9d, 0x002b8348ac0f3cfc,
+                  0x00000000376931bf ]);
+
+/// The inverse of the square root of a - d for Ed25519, used by
+/// Ristretto,
+/// 0x786c8905cfaffca216c27b91fe01d8409d2f16175a4172be99c8fdaa805d40ea.
+pub const RISTRETTO_INVSQRT_A_MINUS_D: Mod_e255_19 =
+    Mod_e255_19([ 0x00c8fdaa805d40ea, 0x0016175a4172be99,
+                  0x0091fe01d8409d2f, 0x00cfaffca216c27b,
+                  0x00000000786c8905 ]);
+
+/// The value 1 - d^2 for Ed25519, used by Ristretto,
+/// 0x029072a8b2b3e0d79994abddbe70dfe42c81a138cd5e350fe27c09c1945fc176.
+pub const RISTRETTO_ONE_MINUS_D_SQ: Mod_e255_19 =
+    Mod_e255_19([ 0x007c09c1945fc176, 0x00a138cd5e350fe2,
+                  0x00ddbe70dfe42c81, 0x00b2b3e0d79994ab,
+                  0x00000000029072a8 ]);
+
+/// The value (d - 1)^2 for Ed25519, used by Ristretto,
+/// 0x5968b37af66c22414cdcd32f529b4eebd29e4a2cb01e199931ad5aaa44ed4d20.
+pub const RISTRETTO_D_MINUS_ONE_SQ: Mod_e255_19 =
+    Mod_e255_19([ 0x00ad5aaa44ed4d20, 0x004a2cb01e199931,
+                  0x002f529b4eebd29e, 0x00f66c22414cdcd3,
+                  0x000000005968b37a ]);
+
+/// The even square root of -486664, used by the map from Curve25519
+/// to Ed25519 in RFC 9380,
+/// 0x0f26edf460a006bbd27b08dc03fc4f7ec5a1d3d14b7d1a82cc6e04aaff457e06.
+pub const ED25519_SQRT_M486664: Mod_e255_19 =
+    Mod_e255_19([ 0x006e04aaff457e06, 0x00d3d14b7d1a82cc,
+                  0x00dc03fc4f7ec5a1, 0x0060a006bbd27b08,
+                  0x000000000f26edf4 ]);
 
 impl Debug for Mod_e255_19 {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -132,6 +234,29 @@ impl Mod_e255_19 {
         self.0[4] >> 31
     }
 
+    /// Add 2p to a value whose representation has gone negative.
+    /// Multiplication assumes that every digit is non-negative, so
+    /// this must follow any operation that can borrow out of the
+    /// highest digit.
+    fn fix_sign(&mut self) {
+        let m = self.0[4] >> 63;
+        let s0 = self.0[0] + ((MODULUS.0[0] << 1) & m);
+        let c0 = s0 >> 56;
+        let s1 = self.0[1] + ((MODULUS.0[1] << 1) & m) + c0;
+        let c1 = s1 >> 56;
+        let s2 = self.0[2] + ((MODULUS.0[2] << 1) & m) + c1;
+        let c2 = s2 >> 56;
+        let s3 = self.0[3] + ((MODULUS.0[3] << 1) & m) + c2;
+        let c3 = s3 >> 56;
+        let s4 = self.0[4] + ((MODULUS.0[4] << 1) & m) + c3;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4;
+    }
+
     /// Serialize a value as a little-endian byte array.  This has the
     /// effect of normalizing the representation.
     pub fn packed(&mut self) -> [u8; 32] {
@@ -160,15 +285,57 @@ impl Mod_e255_19 {
 
         out
     }
+
+    /// Raise to the power (p - 5)/8 = 2^252 - 3.
+    fn pow_p58(&self) -> Self {
+        // First digit is 1.
+        let mut sqval = self.clone();
+        let mut out = self.clone();
+
+        // Second digit is 0.
+        sqval.square();
+
+        // All the remaining digits are 1.
+        for _ in 2..252 {
+            sqval.square();
+            out *= &sqval;
+        }
+
+        out
+    }
+
+    /// Compute the non-negative square root of u/v with a single
+    /// exponentiation, as SQRT_RATIO_M1 in RFC 9496.  The flag is set
+    /// when u/v is square, or u is zero.  Otherwise, the result is
+    /// the square root of SQRT_M1 * u/v, or zero if v is zero.
+    pub fn sqrt_ratio(u: &Self, v: &Self) -> (Choice, Self) {
+        let v3 = &v.squared() * v;
+        let v7 = &v3.squared() * v;
+        let mut r = (u * &v3) * (u * &v7).pow_p58();
+        let check = v * &r.squared();
+        let neg_u = -*u;
+        let correct = check.ct_eq(u);
+        let flipped = check.ct_eq(&neg_u);
+        let flipped_i = check.ct_eq(&(neg_u * SQRT_M1));
+        let r_prime = r * SQRT_M1;
+
+        r.conditional_assign(&r_prime, flipped | flipped_i);
+
+        let negative = Choice::from(r.bit(0));
+
+        r.conditional_negate(negative);
+
+        (correct | flipped, r)
+    }
 }
 
 impl Neg for Mod_e255_19 {
     type Output = Mod_e255_19;
 
     fn neg(self) -> Mod_e255_19 {
-        let mut out = self;
+        let mut out = ZERO;
 
-        out += &MODULUS;
+        out -= &self;
         out
     }
 }
@@ -364,7 +531,7 @@ impl<'b> SubAssign<&'b Mod_e255_19> for Mod_e255_19 {
         let b3 = rhs.0[3];
         let b4 = rhs.0[4] & 0x000000007fffffff;
 
-        let cin = self.carry_out() + rhs.carry_out();
+        let cin = self.carry_out() - rhs.carry_out();
         let s0 = a0 - b0 + (cin * C_VAL);
         let c0 = s0 >> 56;
         let s1 = a1 - b1 + c0;
@@ -380,6 +547,8 @@ impl<'b> SubAssign<&'b Mod_e255_19> for Mod_e255_19 {
         self.0[2] = s2 & 0x00ffffffffffffff;
         self.0[3] = s3 & 0x00ffffffffffffff;
         self.0[4] = s4;
+
+        self.fix_sign();
     }
 }
 
@@ -836,13 +1005,20 @@ impl Mul<Mod_e255_19> for Mod_e255_19 {
 
 impl Rand for Mod_e255_19 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        let mut out = Mod_e255_19([0i64; 5]);
+        let mut bytes = [0u8; 32];
+        let mut out = ZERO;
 
-        for i in 0..5 {
-            out.0[i] = rng.gen_range(0, MODULUS.0[i]);
-        }
+        // Rejection sampling: draw 255-bit values until one is less
+        // than the modulus.  This is uniform, and almost never
+        // takes more than one try.
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[31] &= 0x7f;
 
-        out
+            if out.unpack_checked(&bytes).is_ok() {
+                return out;
+            }
+        }
     }
 }
 
@@ -888,6 +1064,38 @@ impl Pack for Mod_e255_19 {
         out
     }
 
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 32 {
+            return Err(Error::InvalidLength);
+        }
+
+        if bytes[31] & 0x80 != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        // Adding C carries out of the top digit exactly when the
+        // value is at least the modulus.
+        if (&out + (C_VAL as i32)).carry_out() != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        *self = out;
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
     fn pack(&mut self, bytes: &mut [u8]) {
         self.normalize();
         self.pack_normalized(bytes)
@@ -994,6 +1202,39 @@ impl PrimeFieldMask for Mod_e255_19_Mask {
     }
 }
 
+impl ConditionallySelectable for Mod_e255_19 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = Mod_e255_19_Mask::filled(choice.into());
+
+        Mod_e255_19([ a.0[0] ^ ((a.0[0] ^ b.0[0]) & mask.0[0]),
+                      a.0[1] ^ ((a.0[1] ^ b.0[1]) & mask.0[1]),
+                      a.0[2] ^ ((a.0[2] ^ b.0[2]) & mask.0[2]),
+                      a.0[3] ^ ((a.0[3] ^ b.0[3]) & mask.0[3]),
+                      a.0[4] ^ ((a.0[4] ^ b.0[4]) & mask.0[4]) ])
+    }
+}
+
+impl ConditionallyNegatable for Mod_e255_19 {
+    fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -*self;
+
+        self.conditional_assign(&neg, choice);
+    }
+}
+
+impl ConstantTimeEq for Mod_e255_19 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.normalize();
+        b.normalize();
+
+        is_zero_i64((a.0[0] ^ b.0[0]) | (a.0[1] ^ b.0[1]) |
+                    (a.0[2] ^ b.0[2]) | (a.0[3] ^ b.0[3]) | (a.0[4] ^ b.0[4]))
+    }
+}
+
 impl PrimeField for Mod_e255_19 {
     fn fill(&mut self, bit: bool) {
         let mut mask = bit as i64;
@@ -1436,6 +1677,8 @@ impl PrimeField for Mod_e255_19 {
         self.0[2] = s2 & 0x00ffffffffffffff;
         self.0[3] = s3 & 0x00ffffffffffffff;
         self.0[4] = s4;
+
+        self.fix_sign();
     }
 
     fn small_add(&self, rhs: i32) -> Self {
@@ -1471,6 +1714,8 @@ impl PrimeField for Mod_e255_19 {
         self.0[2] = s2 & 0x00ffffffffffffff;
         self.0[3] = s3 & 0x00ffffffffffffff;
         self.0[4] = s4;
+
+        self.fix_sign();
     }
 
     fn small_sub(&self, rhs: i32) -> Self {
@@ -1491,7 +1736,7 @@ impl PrimeField for Mod_e255_19 {
         let a6 = self.0[3] & 0x0fffffff;
         let a7 = self.0[3] >> 28;
         let a8 = self.0[4] & 0x0fffffff;
-        let a9 = self.0[4] >> 28;
+        let a9 = (self.0[4] & 0x000000007fffffff) >> 28;
 
         let b = i64::from(rhs);
 
@@ -1507,7 +1752,7 @@ impl PrimeField for Mod_e255_19 {
         let m9 = a9 * b;
 
         let cin = self.carry_out();
-        let d0 = m0 + ((m1 & 0x0fffffff) << 28) + (cin * C_VAL);
+        let d0 = m0 + ((m1 & 0x0fffffff) << 28) + (cin * C_VAL * b);
         let c0 = d0 >> 56;
         let d1 = (m1 >> 28) + m2 + ((m3 & 0x0fffffff) << 28) + c0;
         let c1 = d1 >> 56;
@@ -1515,13 +1760,28 @@ impl PrimeField for Mod_e255_19 {
         let c2 = d2 >> 56;
         let d3 = (m5 >> 28) + m6 + ((m7 & 0x0fffffff) << 28) + c2;
         let c3 = d3 >> 56;
-        let d4 = (m7 >> 28) + m8 + (m9 << 28) + c3;
+        let d4 = (m7 >> 28) + m8 +
+                 ((m9 & 0x0fffffff) << 28) + c3;
+
+        // Fold everything above bit 255 back in, multiplied by C.
+        let over = (d4 >> 31) + ((m9 >> 28) << 25);
+        let s0 = (d0 & 0x00ffffffffffffff) + (over * C_VAL);
+        let k0 = s0 >> 56;
+        let s1 = (d1 & 0x00ffffffffffffff) + k0;
+        let k1 = s1 >> 56;
+        let s2 = (d2 & 0x00ffffffffffffff) + k1;
+        let k2 = s2 >> 56;
+        let s3 = (d3 & 0x00ffffffffffffff) + k2;
+        let k3 = s3 >> 56;
+        let s4 = (d4 & 0x000000007fffffff) + k3;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4;
 
-        self.0[0] = d0 & 0x00ffffffffffffff;
-        self.0[1] = d1 & 0x00ffffffffffffff;
-        self.0[2] = d2 & 0x00ffffffffffffff;
-        self.0[3] = d3 & 0x00ffffffffffffff;
-        self.0[4] = d4;
+        self.fix_sign();
     }
 
     fn small_mul(&self, b: i32) -> Self {
@@ -1531,12 +1791,40 @@ impl PrimeField for Mod_e255_19 {
 
         out
     }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        let ndigits = (8 * bytes.len() + 254) / 255;
+        let mut buf = [0u8; 32];
+        let mut out = ZERO;
+
+        // Read the input as digits in base 2^255, and evaluate it
+        // by Horner's rule, using 2^255 = 19 mod p.
+        for j in 0..ndigits {
+            let base = (ndigits - 1 - j) * 255;
+
+            for i in 0..32 {
+                let off = base + (8 * i);
+                let lo = bytes.get(off / 8).map_or(0, |b| *b as u16);
+                let hi = bytes.get((off / 8) + 1).map_or(0, |b| *b as u16);
+
+                buf[i] = (((hi << 8) | lo) >> (off % 8)) as u8;
+            }
+
+            buf[31] &= 0x7f;
+            out = out.small_mul(C_VAL as i32) + Mod_e255_19::unpacked(&buf);
+        }
+
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use choice::*;
+    use error::Error;
     use field::prime_field::*;
     use field::mod_e255_19::*;
+    use rand::thread_rng;
 
     const TWO: Mod_e255_19 = Mod_e255_19([ 2, 0, 0, 0, 0 ]);
 
@@ -3540,4 +3828,225 @@ mod tests {
             assert!(M_FOUR.normalize_eq(&mut val));
         }
     }
+
+    #[test]
+    fn test_neg() {
+        let l1_zeros: [&mut Mod_e255_19; 3] = [ &mut -ZERO,
+                                          &mut (&-ONE + &ONE),
+                                          &mut (&-M_TWO + &M_TWO) ];
+
+        let l1_mones: [&mut Mod_e255_19; 3] = [ &mut -ONE,
+                                          &mut -(&TWO - &ONE),
+                                          &mut (&-TWO + &ONE) ];
+
+        let l1_sixes: [&mut Mod_e255_19; 4] = [ &mut -M_SIX,
+                                          &mut (&-TWO * &M_THREE),
+                                          &mut (&(&M_ONE - &ONE) * &-THREE),
+                                          &mut (&(&ZERO - &TWO) -
+                                                &(&(&ONE - &NINE) * &ONE)) ];
+
+        for i in 0..3 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(M_ONE.normalize_eq(l1_mones[i]));
+        }
+
+        for i in 0..4 {
+            assert!(SIX.normalize_eq(l1_sixes[i]));
+        }
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+        let mut a = TWO;
+        let mut b = M_THREE;
+        let mut sel_f = Mod_e255_19::conditional_select(&TWO, &THREE, f);
+        let mut sel_t = Mod_e255_19::conditional_select(&TWO, &THREE, t);
+
+        assert!(TWO.normalize_eq(&mut sel_f));
+        assert!(THREE.normalize_eq(&mut sel_t));
+
+        Mod_e255_19::conditional_swap(&mut a, &mut b, f);
+        assert!(TWO.normalize_eq(&mut a));
+        assert!(M_THREE.normalize_eq(&mut b));
+
+        Mod_e255_19::conditional_swap(&mut a, &mut b, t);
+        assert!(M_THREE.normalize_eq(&mut a));
+        assert!(TWO.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_conditional_negate() {
+        let mut a = SIX;
+        let mut b = SIX;
+
+        a.conditional_negate(Choice::from(0));
+        b.conditional_negate(Choice::from(1));
+
+        assert!(SIX.normalize_eq(&mut a));
+        assert!(M_SIX.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(ZERO.ct_eq(&ZERO)));
+        assert!(bool::from(ZERO.ct_eq(&MODULUS)));
+        assert!(bool::from(ZERO.ct_eq(&(M_ONE + ONE))));
+        assert!(bool::from(SIX.ct_eq(&(TWO * THREE))));
+        assert!(bool::from(M_SIX.ct_eq(&(M_TWO * THREE))));
+        assert!(!bool::from(ZERO.ct_eq(&ONE)));
+        assert!(!bool::from(SIX.ct_eq(&M_SIX)));
+        assert!(!bool::from(TWO.ct_eq(&THREE)));
+    }
+
+    #[test]
+    fn test_sqrt_checked() {
+        let four_root = FOUR.sqrt_checked().unwrap();
+        let nine_root = NINE.sqrt_checked().unwrap();
+        let mut zero_root = ZERO.sqrt_checked().unwrap();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(ZERO.normalize_eq(&mut zero_root));
+        assert!(bool::from(SIXTEEN.sqrt_checked().is_some()));
+        assert!(bool::from(TWO.sqrt_checked().is_none()));
+        assert!(bool::from(M_TWO.sqrt_checked().is_none()));
+
+        // -1 is a square when p = 5 mod 8.
+        let m_one_root = M_ONE.sqrt_checked().unwrap();
+
+        assert!(M_ONE.normalize_eq(&mut m_one_root.squared()));
+    }
+
+    #[test]
+    fn test_sqrt_ratio() {
+        let (square, mut root) = Mod_e255_19::sqrt_ratio(&NINE, &FOUR);
+
+        // The root is 3/2 or -3/2, whichever is even.
+        assert!(bool::from(square));
+        assert!(!root.bit(0));
+        assert!(NINE.normalize_eq(&mut (root.squared() * FOUR)));
+
+        let (square, mut root) = Mod_e255_19::sqrt_ratio(&ZERO, &THREE);
+
+        assert!(bool::from(square));
+        assert!(ZERO.normalize_eq(&mut root));
+
+        let (square, mut root) = Mod_e255_19::sqrt_ratio(&ONE, &ZERO);
+
+        assert!(!bool::from(square));
+        assert!(ZERO.normalize_eq(&mut root));
+
+        // For a non-square, this gives the root of SQRT_M1 * u/v.
+        let (square, mut root) = Mod_e255_19::sqrt_ratio(&TWO, &ONE);
+
+        assert!(!bool::from(square));
+        assert!(!root.bit(0));
+        assert!((TWO * SQRT_M1).normalize_eq(&mut root.squared()));
+    }
+
+    #[test]
+    fn test_invert_checked() {
+        let two_inv = TWO.invert_checked().unwrap();
+        let m_six_inv = M_SIX.invert_checked().unwrap();
+
+        assert!(ONE.normalize_eq(&mut (two_inv * TWO)));
+        assert!(ONE.normalize_eq(&mut (m_six_inv * M_SIX)));
+        assert!(bool::from(ZERO.invert_checked().is_none()));
+        assert!(bool::from(MODULUS.invert_checked().is_none()));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+        let mut long = [0u8; 33];
+        let mut actual = ZERO;
+
+        assert!(M_ONE.normalize_eq(&mut Mod_e255_19::unpacked_checked(&bytes)
+                                            .unwrap()));
+        assert!(actual.unpack_checked(&TWO.packed()).is_ok());
+        assert!(TWO.normalize_eq(&mut actual));
+        assert!(actual.unpack_checked(&ZERO.packed()).is_ok());
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        // The modulus itself.
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&bytes));
+
+        // Bits above the modulus.
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&[0xffu8; 32]));
+
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        long[0] = 1;
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long));
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long[0..31]));
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut rng = thread_rng();
+        let mut counts = [0u32; 255];
+
+        // Each bit of a uniform value is set about half the time.
+        for _ in 0..2000 {
+            let mut val = Mod_e255_19::rand(&mut rng);
+            let bytes = val.packed();
+
+            assert!(Mod_e255_19::unpacked_checked(&bytes).is_ok());
+
+            for i in 0..255 {
+                counts[i] += ((bytes[i / 8] >> (i % 8)) & 0x1) as u32;
+            }
+        }
+
+        for i in 0..255 {
+            assert!(counts[i] > 800 && counts[i] < 1200,
+                    "bit {} set {} times", i, counts[i]);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        const ONES: Mod_e255_19 = Mod_e255_19([ 0x5a3, 0, 0, 0, 0 ]);
+
+        const SEQ: Mod_e255_19 =
+            Mod_e255_19([ 0x004ea5fd7ad22a51, 0x00c41b72f0479ef7,
+                          0x003990e865bd146c, 0x00af065ddb3289e2,
+                          0x000000002aa7ff57 ]);
+
+        let mut bytes = [0u8; 64];
+        let mut out;
+
+        for i in 0..64 {
+            bytes[i] = ((i * 37) + 11) as u8;
+        }
+
+        out = Mod_e255_19::from_bytes_wide(&bytes);
+        assert!(SEQ.normalize_eq(&mut out));
+        out = Mod_e255_19::from_bytes_wide(&[0xff; 64]);
+        assert!(ONES.normalize_eq(&mut out));
+
+        // Values that fit in the field are unchanged.
+        bytes = [0u8; 64];
+        bytes[0..32].copy_from_slice(&M_ONE.packed());
+        out = Mod_e255_19::from_bytes_wide(&bytes);
+        assert!(M_ONE.normalize_eq(&mut out));
+
+        bytes[0] += 1;
+        out = Mod_e255_19::from_bytes_wide(&bytes);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e255_19::from_bytes_wide(&[]);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e255_19::from_bytes_wide(&[2]);
+        assert!(TWO.normalize_eq(&mut out));
+    }
 }
@@ -1,3 +1,9 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_i64;
+use error::Error;
 use field::prime_field::PrimeField;
 use field::prime_field::PrimeFieldMask;
 use normalize::Normalize;
@@ -79,13 +85,21 @@ pub const E382_BASE_X: Mod_e382_105 =
 /// The y-coordinate of the base point of the curve E-382, 0x11.
 pub const E382_BASE_Y: Mod_e382_105 = Mod_e382_105([ 0x11, 0, 0, 0, 0, 0, 0 ]);
 
-/// The prime subgroup order of the curve E-382,
-/// 2^380 - 1030303207694556153926491950732314247062623204330168346855
-pub const E382_ORDER: Mod_e382_105 =
-    Mod_e382_105([ 0x00e27e13fd3c9719, 0x0069281b102d2773,
-                   0x00f21e95eee17c5e, 0x00ffffffffd5fb21,
-                   0x00ffffffffffffff, 0x00ffffffffffffff,
-                   0x00000fffffffffff ]);
+/// The non-negative square root of -d for E-382, used by Decaf,
+/// 0x17eea405ca856f3aefa98f74f04a4a612597e9a6c0d4ceb8e5db7e99a34ed76ac37bd4c396206bdc44700ed7dc521ed4.
+pub const E382_SQRT_MINUS_D: Mod_e382_105 =
+    Mod_e382_105([ 0x00700ed7dc521ed4, 0x00d4c396206bdc44,
+                   0x0099a34ed76ac37b, 0x00c0d4ceb8e5db7e,
+                   0x004a4a612597e9a6, 0x006f3aefa98f74f0,
+                   0x000017eea405ca85 ]);
+
+/// The inverse of the square root of -d for E-382, used by Decaf,
+/// 0x1d73d781fdfe20e62b0a2487cfe1ee6e8c31852aec5dcfbcce7ca53b0f41d13b52902468f3e4a1b19fbe403dceb4951.
+pub const E382_INVSQRT_MINUS_D: Mod_e382_105 =
+    Mod_e382_105([ 0x00fbe403dceb4951, 0x0002468f3e4a1b19,
+                   0x0053b0f41d13b529, 0x00aec5dcfbcce7ca,
+                   0x00fe1ee6e8c31852, 0x00e20e62b0a2487c,
+                   0x000001d73d781fdf ]);
 
 impl Debug for Mod_e382_105 {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -133,6 +147,35 @@ impl Mod_e382_105 {
         self.0[6] >> 46
     }
 
+    /// Add 2p to a value whose representation has gone negative.
+    /// Multiplication assumes that every digit is non-negative, so
+    /// this must follow any operation that can borrow out of the
+    /// highest digit.
+    fn fix_sign(&mut self) {
+        let m = self.0[6] >> 63;
+        let s0 = self.0[0] + ((MODULUS.0[0] << 1) & m);
+        let c0 = s0 >> 56;
+        let s1 = self.0[1] + ((MODULUS.0[1] << 1) & m) + c0;
+        let c1 = s1 >> 56;
+        let s2 = self.0[2] + ((MODULUS.0[2] << 1) & m) + c1;
+        let c2 = s2 >> 56;
+        let s3 = self.0[3] + ((MODULUS.0[3] << 1) & m) + c2;
+        let c3 = s3 >> 56;
+        let s4 = self.0[4] + ((MODULUS.0[4] << 1) & m) + c3;
+        let c4 = s4 >> 56;
+        let s5 = self.0[5] + ((MODULUS.0[5] << 1) & m) + c4;
+        let c5 = s5 >> 56;
+        let s6 = self.0[6] + ((MODULUS.0[6] << 1) & m) + c5;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6;
+    }
+
     /// Serialize a value as a little-endian byte array.  This has the
     /// effect of normalizing the representation.
     pub fn packed(&mut self) -> [u8; 48] {
@@ -146,9 +189,9 @@ impl Neg for Mod_e382_105 {
     type Output = Mod_e382_105;
 
     fn neg(self) -> Mod_e382_105 {
-        let mut out = self;
+        let mut out = ZERO;
 
-        out += &MODULUS;
+        out -= &self;
         out
     }
 }
@@ -358,7 +401,7 @@ impl<'b> SubAssign<&'b Mod_e382_105> for Mod_e382_105 {
         let b5 = rhs.0[5];
         let b6 = rhs.0[6] & 0x00003fffffffffff;
 
-        let cin = self.carry_out() + rhs.carry_out();
+        let cin = self.carry_out() - rhs.carry_out();
         let s0 = a0 - b0 + (cin * C_VAL);
         let c0 = s0 >> 56;
         let s1 = a1 - b1 + c0;
@@ -380,6 +423,8 @@ impl<'b> SubAssign<&'b Mod_e382_105> for Mod_e382_105 {
         self.0[4] = s4 & 0x00ffffffffffffff;
         self.0[5] = s5 & 0x00ffffffffffffff;
         self.0[6] = s6;
+
+        self.fix_sign();
     }
 }
 
@@ -1015,13 +1060,20 @@ impl Mul<Mod_e382_105> for Mod_e382_105 {
 
 impl Rand for Mod_e382_105 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        let mut out = Mod_e382_105([0i64; 7]);
+        let mut bytes = [0u8; 48];
+        let mut out = ZERO;
 
-        for i in 0..7 {
-            out.0[i] = rng.gen_range(0, MODULUS.0[i]);
-        }
+        // Rejection sampling: draw 382-bit values until one is less
+        // than the modulus.  This is uniform, and almost never
+        // takes more than one try.
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[47] &= 0x3f;
 
-        out
+            if out.unpack_checked(&bytes).is_ok() {
+                return out;
+            }
+        }
     }
 }
 
@@ -1083,6 +1135,38 @@ impl Pack for Mod_e382_105 {
         out
     }
 
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 48 {
+            return Err(Error::InvalidLength);
+        }
+
+        if bytes[47] & 0xc0 != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        // Adding C carries out of the top digit exactly when the
+        // value is at least the modulus.
+        if (&out + (C_VAL as i32)).carry_out() != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        *self = out;
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
     fn pack(&mut self, bytes: &mut [u8]) {
         self.normalize();
         self.pack_normalized(bytes)
@@ -1209,6 +1293,42 @@ impl PrimeFieldMask for Mod_e382_105_Mask {
     }
 }
 
+impl ConditionallySelectable for Mod_e382_105 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = Mod_e382_105_Mask::filled(choice.into());
+
+        Mod_e382_105([ a.0[0] ^ ((a.0[0] ^ b.0[0]) & mask.0[0]),
+                       a.0[1] ^ ((a.0[1] ^ b.0[1]) & mask.0[1]),
+                       a.0[2] ^ ((a.0[2] ^ b.0[2]) & mask.0[2]),
+                       a.0[3] ^ ((a.0[3] ^ b.0[3]) & mask.0[3]),
+                       a.0[4] ^ ((a.0[4] ^ b.0[4]) & mask.0[4]),
+                       a.0[5] ^ ((a.0[5] ^ b.0[5]) & mask.0[5]),
+                       a.0[6] ^ ((a.0[6] ^ b.0[6]) & mask.0[6]) ])
+    }
+}
+
+impl ConditionallyNegatable for Mod_e382_105 {
+    fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -*self;
+
+        self.conditional_assign(&neg, choice);
+    }
+}
+
+impl ConstantTimeEq for Mod_e382_105 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.normalize();
+        b.normalize();
+
+        is_zero_i64((a.0[0] ^ b.0[0]) | (a.0[1] ^ b.0[1]) |
+                    (a.0[2] ^ b.0[2]) | (a.0[3] ^ b.0[3]) |
+                    (a.0[4] ^ b.0[4]) | (a.0[5] ^ b.0[5]) | (a.0[6] ^ b.0[6]))
+    }
+}
+
 impl PrimeField for Mod_e382_105 {
     fn fill(&mut self, bit: bool) {
         let mut mask = bit as i64;
@@ -1853,6 +1973,8 @@ impl PrimeField for Mod_e382_105 {
         self.0[4] = s4 & 0x00ffffffffffffff;
         self.0[5] = s5 & 0x00ffffffffffffff;
         self.0[6] = s6;
+
+        self.fix_sign();
     }
 
     fn small_add(&self, rhs: i32) -> Mod_e382_105 {
@@ -1896,6 +2018,8 @@ impl PrimeField for Mod_e382_105 {
         self.0[4] = s4 & 0x00ffffffffffffff;
         self.0[5] = s5 & 0x00ffffffffffffff;
         self.0[6] = s6;
+
+        self.fix_sign();
     }
 
     fn small_sub(&self, rhs: i32) -> Mod_e382_105 {
@@ -1920,7 +2044,7 @@ impl PrimeField for Mod_e382_105 {
         let a10 = self.0[5] & 0x0fffffff;
         let a11 = self.0[5] >> 28;
         let a12 = self.0[6] & 0x0fffffff;
-        let a13 = self.0[6] >> 28;
+        let a13 = (self.0[6] & 0x00003fffffffffff) >> 28;
 
         let b = i64::from(rhs);
 
@@ -1940,7 +2064,7 @@ impl PrimeField for Mod_e382_105 {
         let m13 = a13 * b;
 
         let cin = self.carry_out();
-        let d0 = m0 + ((m1 & 0x0fffffff) << 28) + (cin * C_VAL);
+        let d0 = m0 + ((m1 & 0x0fffffff) << 28) + (cin * C_VAL * b);
         let c0 = d0 >> 56;
         let d1 = (m1 >> 28) + m2 + ((m3 & 0x0fffffff) << 28) + c0;
         let c1 = d1 >> 56;
@@ -1952,15 +2076,34 @@ impl PrimeField for Mod_e382_105 {
         let c4 = d4 >> 56;
         let d5 = (m9 >> 28) + m10 + ((m11 & 0x0fffffff) << 28) + c4;
         let c5 = d5 >> 56;
-        let d6 = (m11 >> 28) + m12 + (m13 << 28) + c5;
+        let d6 = (m11 >> 28) + m12 +
+                 ((m13 & 0x0fffffff) << 28) + c5;
+
+        // Fold everything above bit 382 back in, multiplied by C.
+        let over = (d6 >> 46) + ((m13 >> 28) << 10);
+        let s0 = (d0 & 0x00ffffffffffffff) + (over * C_VAL);
+        let k0 = s0 >> 56;
+        let s1 = (d1 & 0x00ffffffffffffff) + k0;
+        let k1 = s1 >> 56;
+        let s2 = (d2 & 0x00ffffffffffffff) + k1;
+        let k2 = s2 >> 56;
+        let s3 = (d3 & 0x00ffffffffffffff) + k2;
+        let k3 = s3 >> 56;
+        let s4 = (d4 & 0x00ffffffffffffff) + k3;
+        let k4 = s4 >> 56;
+        let s5 = (d5 & 0x00ffffffffffffff) + k4;
+        let k5 = s5 >> 56;
+        let s6 = (d6 & 0x00003fffffffffff) + k5;
 
-        self.0[0] = d0 & 0x00ffffffffffffff;
-        self.0[1] = d1 & 0x00ffffffffffffff;
-        self.0[2] = d2 & 0x00ffffffffffffff;
-        self.0[3] = d3 & 0x00ffffffffffffff;
-        self.0[4] = d4 & 0x00ffffffffffffff;
-        self.0[5] = d5 & 0x00ffffffffffffff;
-        self.0[6] = d6;
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6;
+
+        self.fix_sign();
     }
 
     fn small_mul(&self, b: i32) -> Mod_e382_105 {
@@ -1970,12 +2113,40 @@ impl PrimeField for Mod_e382_105 {
 
         out
     }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        let ndigits = (8 * bytes.len() + 381) / 382;
+        let mut buf = [0u8; 48];
+        let mut out = ZERO;
+
+        // Read the input as digits in base 2^382, and evaluate it
+        // by Horner's rule, using 2^382 = 105 mod p.
+        for j in 0..ndigits {
+            let base = (ndigits - 1 - j) * 382;
+
+            for i in 0..48 {
+                let off = base + (8 * i);
+                let lo = bytes.get(off / 8).map_or(0, |b| *b as u16);
+                let hi = bytes.get((off / 8) + 1).map_or(0, |b| *b as u16);
+
+                buf[i] = (((hi << 8) | lo) >> (off % 8)) as u8;
+            }
+
+            buf[47] &= 0x3f;
+            out = out.small_mul(C_VAL as i32) + Mod_e382_105::unpacked(&buf);
+        }
+
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use choice::*;
+    use error::Error;
     use field::prime_field::*;
     use field::mod_e382_105::*;
+    use rand::thread_rng;
 
     const TWO: Mod_e382_105 = Mod_e382_105([ 2, 0, 0, 0, 0, 0, 0 ]);
 
@@ -3932,4 +4103,194 @@ mod tests {
             assert!(M_FOUR.normalize_eq(&mut val));
         }
     }
+
+    #[test]
+    fn test_neg() {
+        let l1_zeros: [&mut Mod_e382_105; 3] = [ &mut -ZERO,
+                                          &mut (&-ONE + &ONE),
+                                          &mut (&-M_TWO + &M_TWO) ];
+
+        let l1_mones: [&mut Mod_e382_105; 3] = [ &mut -ONE,
+                                          &mut -(&TWO - &ONE),
+                                          &mut (&-TWO + &ONE) ];
+
+        let l1_sixes: [&mut Mod_e382_105; 4] = [ &mut -M_SIX,
+                                          &mut (&-TWO * &M_THREE),
+                                          &mut (&(&M_ONE - &ONE) * &-THREE),
+                                          &mut (&(&ZERO - &TWO) -
+                                                &(&(&ONE - &NINE) * &ONE)) ];
+
+        for i in 0..3 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(M_ONE.normalize_eq(l1_mones[i]));
+        }
+
+        for i in 0..4 {
+            assert!(SIX.normalize_eq(l1_sixes[i]));
+        }
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+        let mut a = TWO;
+        let mut b = M_THREE;
+        let mut sel_f = Mod_e382_105::conditional_select(&TWO, &THREE, f);
+        let mut sel_t = Mod_e382_105::conditional_select(&TWO, &THREE, t);
+
+        assert!(TWO.normalize_eq(&mut sel_f));
+        assert!(THREE.normalize_eq(&mut sel_t));
+
+        Mod_e382_105::conditional_swap(&mut a, &mut b, f);
+        assert!(TWO.normalize_eq(&mut a));
+        assert!(M_THREE.normalize_eq(&mut b));
+
+        Mod_e382_105::conditional_swap(&mut a, &mut b, t);
+        assert!(M_THREE.normalize_eq(&mut a));
+        assert!(TWO.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_conditional_negate() {
+        let mut a = SIX;
+        let mut b = SIX;
+
+        a.conditional_negate(Choice::from(0));
+        b.conditional_negate(Choice::from(1));
+
+        assert!(SIX.normalize_eq(&mut a));
+        assert!(M_SIX.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(ZERO.ct_eq(&ZERO)));
+        assert!(bool::from(ZERO.ct_eq(&MODULUS)));
+        assert!(bool::from(ZERO.ct_eq(&(M_ONE + ONE))));
+        assert!(bool::from(SIX.ct_eq(&(TWO * THREE))));
+        assert!(bool::from(M_SIX.ct_eq(&(M_TWO * THREE))));
+        assert!(!bool::from(ZERO.ct_eq(&ONE)));
+        assert!(!bool::from(SIX.ct_eq(&M_SIX)));
+        assert!(!bool::from(TWO.ct_eq(&THREE)));
+    }
+
+    #[test]
+    fn test_sqrt_checked() {
+        let four_root = FOUR.sqrt_checked().unwrap();
+        let nine_root = NINE.sqrt_checked().unwrap();
+        let mut zero_root = ZERO.sqrt_checked().unwrap();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(ZERO.normalize_eq(&mut zero_root));
+        assert!(bool::from(SIXTEEN.sqrt_checked().is_some()));
+        assert!(bool::from(M_ONE.sqrt_checked().is_none()));
+        assert!(bool::from(M_FOUR.sqrt_checked().is_none()));
+    }
+
+    #[test]
+    fn test_invert_checked() {
+        let two_inv = TWO.invert_checked().unwrap();
+        let m_six_inv = M_SIX.invert_checked().unwrap();
+
+        assert!(ONE.normalize_eq(&mut (two_inv * TWO)));
+        assert!(ONE.normalize_eq(&mut (m_six_inv * M_SIX)));
+        assert!(bool::from(ZERO.invert_checked().is_none()));
+        assert!(bool::from(MODULUS.invert_checked().is_none()));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+        let mut long = [0u8; 49];
+        let mut actual = ZERO;
+
+        assert!(M_ONE.normalize_eq(&mut Mod_e382_105::unpacked_checked(&bytes)
+                                            .unwrap()));
+        assert!(actual.unpack_checked(&TWO.packed()).is_ok());
+        assert!(TWO.normalize_eq(&mut actual));
+        assert!(actual.unpack_checked(&ZERO.packed()).is_ok());
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        // The modulus itself.
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&bytes));
+
+        // Bits above the modulus.
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&[0xffu8; 48]));
+
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        long[0] = 1;
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long));
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long[0..47]));
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut rng = thread_rng();
+        let mut counts = [0u32; 382];
+
+        // Each bit of a uniform value is set about half the time.
+        for _ in 0..2000 {
+            let mut val = Mod_e382_105::rand(&mut rng);
+            let bytes = val.packed();
+
+            assert!(Mod_e382_105::unpacked_checked(&bytes).is_ok());
+
+            for i in 0..382 {
+                counts[i] += ((bytes[i / 8] >> (i % 8)) & 0x1) as u32;
+            }
+        }
+
+        for i in 0..382 {
+            assert!(counts[i] > 800 && counts[i] < 1200,
+                    "bit {} set {} times", i, counts[i]);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        const ONES: Mod_e382_105 = Mod_e382_105([ 0x2b10f, 0, 0, 0, 0, 0, 0 ]);
+
+        const SEQ: Mod_e382_105 =
+            Mod_e382_105([ 0x0015ffe9d3c16381, 0x0009f3ddc7b3412c,
+                           0x00fde7d1bba59120, 0x00f1dbc5af998513,
+                           0x00e5cfb9a38d7907, 0x00d9c3ad97816cfb,
+                           0x000037a18b7560ef ]);
+
+        let mut bytes = [0u8; 96];
+        let mut out;
+
+        for i in 0..96 {
+            bytes[i] = ((i * 37) + 11) as u8;
+        }
+
+        out = Mod_e382_105::from_bytes_wide(&bytes);
+        assert!(SEQ.normalize_eq(&mut out));
+        out = Mod_e382_105::from_bytes_wide(&[0xff; 96]);
+        assert!(ONES.normalize_eq(&mut out));
+
+        // Values that fit in the field are unchanged.
+        bytes = [0u8; 96];
+        bytes[0..48].copy_from_slice(&M_ONE.packed());
+        out = Mod_e382_105::from_bytes_wide(&bytes);
+        assert!(M_ONE.normalize_eq(&mut out));
+
+        bytes[0] += 1;
+        out = Mod_e382_105::from_bytes_wide(&bytes);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e382_105::from_bytes_wide(&[]);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e382_105::from_bytes_wide(&[2]);
+        assert!(TWO.normalize_eq(&mut out));
+    }
 }
@@ -1,3 +1,9 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_i64;
+use error::Error;
 use field::prime_field::PrimeField;
 use field::prime_field::PrimeFieldMask;
 use normalize::Normalize;
@@ -84,12 +90,21 @@ pub const M383_BASE_Y: Mod_e383_187 =
                    0x00a0f328e7c165f0, 0x0034af310e304b2d,
                    0x00001ec7ed04aaf8 ]);
 
-/// The prime subgroup order of the curve M-383,
-/// 2^380 + 166236275931373516105219794935542153308039234455761613271.
-pub const M383_ORDER: Mod_e383_187 =
-    Mod_e383_187([ 0x00bbc225be9071d7, 0x00576f7b1b249e46,
-                   0x0073ac36ba6e7a32, 0x000000000006c796,
-                   0, 0, 0x0000100000000000 ]);
+/// The A value 2065150 in the M-383 curve equation y^2 = x^3 + Ax^2 + x.
+pub const M383_A: Mod_e383_187 = Mod_e383_187([ 0x1f82fe, 0, 0, 0, 0, 0, 0 ]);
+
+/// The value (A + 2) / 4 = 516288 for the M-383 curve equation
+/// y^2 = x^3 + Ax^2 + x, used by the Montgomery ladder.
+pub const M383_A24: Mod_e383_187 = Mod_e383_187([ 0x7e0c0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The x-coordinate of the base point of the curve M-383 plus a
+/// point of order 8, which generates the whole group,
+/// 0x9e3be1c0ccdf21f5805784647603ac1eeb22792c15205cfc9b894e0349b895b50ee81086228a5a84b26e5f075c3b6bf.
+pub const M383_DIRTY_BASE_X: Mod_e383_187 =
+    Mod_e383_187([ 0x0026e5f075c3b6bf, 0x0081086228a5a84b,
+                   0x00e0349b895b50ee, 0x00c15205cfc9b894,
+                   0x00603ac1eeb22792, 0x00f21f5805784647,
+                   0x000009e3be1c0ccd ]);
 
 /// The x-coordinate of the base point of the curve Curve383187, 0x5.
 pub const CURVE383187_BASE_X: Mod_e383_187 =
@@ -103,12 +118,26 @@ pub const CURVE383187_BASE_Y: Mod_e383_187 =
                    0x004a32370471965c, 0x001896732b12d550,
                    0x00001eebe07dc187 ]);
 
-/// The prime subgroup order of the curve Curve383187,
-/// 2^380 + 356080847217269887368687156533236720299699248977882517025
-pub const CURVE383187_ORDER: Mod_e383_187 =
-    Mod_e383_187([ 0x00f72088b00a0e21, 0x00ae84b2b7030446,
-                   0x005287a1488acd41, 0x00000000000e85a8,
-                   0, 0, 0x0000100000000000 ]);
+/// The A value 229969 in the Curve383187 equation y^2 = x^3 + Ax^2 + x.
+pub const CURVE383187_A: Mod_e383_187 =
+    Mod_e383_187([ 0x38251, 0, 0, 0, 0, 0, 0 ]);
+
+/// The value (A + 2) / 4 = 229971 / 4 mod p for the Curve383187 equation
+/// y^2 = x^3 + Ax^2 + x, used by the Montgomery ladder.
+pub const CURVE383187_A24: Mod_e383_187 =
+    Mod_e383_187([ 0x000000000000e066, 0x0000000000000000,
+                   0x0000000000000000, 0x0000000000000000,
+                   0x0000000000000000, 0x0000000000000000,
+                   0x0000200000000000 ]);
+
+/// The x-coordinate of the base point of the curve Curve383187 plus a
+/// point of order 8, which generates the whole group,
+/// 0x2e3603dffa474c6892b00a2826af896d54443386398891ce20e9b6e0c0acf6e447326c8aeb2cd987b3374dca71bf5e2c.
+pub const CURVE383187_DIRTY_BASE_X: Mod_e383_187 =
+    Mod_e383_187([ 0x00374dca71bf5e2c, 0x006c8aeb2cd987b3,
+                   0x00e0c0acf6e44732, 0x00398891ce20e9b6,
+                   0x00af896d54443386, 0x004c6892b00a2826,
+                   0x00002e3603dffa47 ]);
 
 impl Debug for Mod_e383_187 {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -156,6 +185,35 @@ impl Mod_e383_187 {
         self.0[6] >> 47
     }
 
+    /// Add 2p to a value whose representation has gone negative.
+    /// Multiplication assumes that every digit is non-negative, so
+    /// this must follow any operation that can borrow out of the
+    /// highest digit.
+    fn fix_sign(&mut self) {
+        let m = self.0[6] >> 63;
+        let s0 = self.0[0] + ((MODULUS.0[0] << 1) & m);
+        let c0 = s0 >> 56;
+        let s1 = self.0[1] + ((MODULUS.0[1] << 1) & m) + c0;
+        let c1 = s1 >> 56;
+        let s2 = self.0[2] + ((MODULUS.0[2] << 1) & m) + c1;
+        let c2 = s2 >> 56;
+        let s3 = self.0[3] + ((MODULUS.0[3] << 1) & m) + c2;
+        let c3 = s3 >> 56;
+        let s4 = self.0[4] + ((MODULUS.0[4] << 1) & m) + c3;
+        let c4 = s4 >> 56;
+        let s5 = self.0[5] + ((MODULUS.0[5] << 1) & m) + c4;
+        let c5 = s5 >> 56;
+        let s6 = self.0[6] + ((MODULUS.0[6] << 1) & m) + c5;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6;
+    }
+
     /// Serialize a value as a little-endian byte array.  This has the
     /// effect of normalizing the representation.
     pub fn packed(&mut self) -> [u8; 48] {
@@ -195,9 +253,9 @@ impl Neg for Mod_e383_187 {
     type Output = Mod_e383_187;
 
     fn neg(self) -> Mod_e383_187 {
-        let mut out = self;
+        let mut out = ZERO;
 
-        out += &MODULUS;
+        out -= &self;
         out
     }
 }
@@ -407,7 +465,7 @@ impl<'b> SubAssign<&'b Mod_e383_187> for Mod_e383_187 {
         let b5 = rhs.0[5];
         let b6 = rhs.0[6] & 0x00007fffffffffff;
 
-        let cin = self.carry_out() + rhs.carry_out();
+        let cin = self.carry_out() - rhs.carry_out();
         let s0 = a0 - b0 + (cin * C_VAL);
         let c0 = s0 >> 56;
         let s1 = a1 - b1 + c0;
@@ -429,6 +487,8 @@ impl<'b> SubAssign<&'b Mod_e383_187> for Mod_e383_187 {
         self.0[4] = s4 & 0x00ffffffffffffff;
         self.0[5] = s5 & 0x00ffffffffffffff;
         self.0[6] = s6;
+
+        self.fix_sign();
     }
 }
 
@@ -1098,13 +1158,20 @@ impl Mul<Mod_e383_187> for Mod_e383_187 {
 
 impl Rand for Mod_e383_187 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        let mut out = Mod_e383_187([0i64; 7]);
+        let mut bytes = [0u8; 48];
+        let mut out = ZERO;
 
-        for i in 0..7 {
-            out.0[i] = rng.gen_range(0, MODULUS.0[i]);
-        }
+        // Rejection sampling: draw 383-bit values until one is less
+        // than the modulus.  This is uniform, and almost never
+        // takes more than one try.
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[47] &= 0x7f;
 
-        out
+            if out.unpack_checked(&bytes).is_ok() {
+                return out;
+            }
+        }
     }
 }
 
@@ -1166,6 +1233,38 @@ impl Pack for Mod_e383_187 {
         out
     }
 
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 48 {
+            return Err(Error::InvalidLength);
+        }
+
+        if bytes[47] & 0x80 != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        // Adding C carries out of the top digit exactly when the
+        // value is at least the modulus.
+        if (&out + (C_VAL as i32)).carry_out() != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        *self = out;
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
     fn pack(&mut self, bytes: &mut [u8]) {
         self.normalize();
         self.pack_normalized(bytes)
@@ -1292,6 +1391,42 @@ impl PrimeFieldMask for Mod_e383_187_Mask {
     }
 }
 
+impl ConditionallySelectable for Mod_e383_187 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = Mod_e383_187_Mask::filled(choice.into());
+
+        Mod_e383_187([ a.0[0] ^ ((a.0[0] ^ b.0[0]) & mask.0[0]),
+                       a.0[1] ^ ((a.0[1] ^ b.0[1]) & mask.0[1]),
+                       a.0[2] ^ ((a.0[2] ^ b.0[2]) & mask.0[2]),
+                       a.0[3] ^ ((a.0[3] ^ b.0[3]) & mask.0[3]),
+                       a.0[4] ^ ((a.0[4] ^ b.0[4]) & mask.0[4]),
+                       a.0[5] ^ ((a.0[5] ^ b.0[5]) & mask.0[5]),
+                       a.0[6] ^ ((a.0[6] ^ b.0[6]) & mask.0[6]) ])
+    }
+}
+
+impl ConditionallyNegatable for Mod_e383_187 {
+    fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -*self;
+
+        self.conditional_assign(&neg, choice);
+    }
+}
+
+impl ConstantTimeEq for Mod_e383_187 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.normalize();
+        b.normalize();
+
+        is_zero_i64((a.0[0] ^ b.0[0]) | (a.0[1] ^ b.0[1]) |
+                    (a.0[2] ^ b.0[2]) | (a.0[3] ^ b.0[3]) |
+                    (a.0[4] ^ b.0[4]) | (a.0[5] ^ b.0[5]) | (a.0[6] ^ b.0[6]))
+    }
+}
+
 impl PrimeField for Mod_e383_187 {
     fn fill(&mut self, bit: bool) {
         let mut mask = bit as i64;
@@ -1975,6 +2110,8 @@ impl PrimeField for Mod_e383_187 {
         self.0[4] = s4 & 0x00ffffffffffffff;
         self.0[5] = s5 & 0x00ffffffffffffff;
         self.0[6] = s6;
+
+        self.fix_sign();
     }
 
     fn small_add(&self, rhs: i32) -> Mod_e383_187 {
@@ -2018,6 +2155,8 @@ impl PrimeField for Mod_e383_187 {
         self.0[4] = s4 & 0x00ffffffffffffff;
         self.0[5] = s5 & 0x00ffffffffffffff;
         self.0[6] = s6;
+
+        self.fix_sign();
     }
 
     fn small_sub(&self, rhs: i32) -> Mod_e383_187 {
@@ -2042,7 +2181,7 @@ impl PrimeField for Mod_e383_187 {
         let a10 = self.0[5] & 0x0fffffff;
         let a11 = self.0[5] >> 28;
         let a12 = self.0[6] & 0x0fffffff;
-        let a13 = self.0[6] >> 28;
+        let a13 = (self.0[6] & 0x00007fffffffffff) >> 28;
 
         let b = i64::from(rhs);
 
@@ -2062,7 +2201,7 @@ impl PrimeField for Mod_e383_187 {
         let m13 = a13 * b;
 
         let cin = self.carry_out();
-        let d0 = m0 + ((m1 & 0x0fffffff) << 28) + (cin * C_VAL);
+        let d0 = m0 + ((m1 & 0x0fffffff) << 28) + (cin * C_VAL * b);
         let c0 = d0 >> 56;
         let d1 = (m1 >> 28) + m2 + ((m3 & 0x0fffffff) << 28) + c0;
         let c1 = d1 >> 56;
@@ -2074,15 +2213,34 @@ impl PrimeField for Mod_e383_187 {
         let c4 = d4 >> 56;
         let d5 = (m9 >> 28) + m10 + ((m11 & 0x0fffffff) << 28) + c4;
         let c5 = d5 >> 56;
-        let d6 = (m11 >> 28) + m12 + (m13 << 28) + c5;
-
-        self.0[0] = d0 & 0x00ffffffffffffff;
-        self.0[1] = d1 & 0x00ffffffffffffff;
-        self.0[2] = d2 & 0x00ffffffffffffff;
-        self.0[3] = d3 & 0x00ffffffffffffff;
-        self.0[4] = d4 & 0x00ffffffffffffff;
-        self.0[5] = d5 & 0x00ffffffffffffff;
-        self.0[6] = d6;
+        let d6 = (m11 >> 28) + m12 +
+                 ((m13 & 0x0fffffff) << 28) + c5;
+
+        // Fold everything above bit 383 back in, multiplied by C.
+        let over = (d6 >> 47) + ((m13 >> 28) << 9);
+        let s0 = (d0 & 0x00ffffffffffffff) + (over * C_VAL);
+        let k0 = s0 >> 56;
+        let s1 = (d1 & 0x00ffffffffffffff) + k0;
+        let k1 = s1 >> 56;
+        let s2 = (d2 & 0x00ffffffffffffff) + k1;
+        let k2 = s2 >> 56;
+        let s3 = (d3 & 0x00ffffffffffffff) + k2;
+        let k3 = s3 >> 56;
+        let s4 = (d4 & 0x00ffffffffffffff) + k3;
+        let k4 = s4 >> 56;
+        let s5 = (d5 & 0x00ffffffffffffff) + k4;
+        let k5 = s5 >> 56;
+        let s6 = (d6 & 0x00007fffffffffff) + k5;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6;
+
+        self.fix_sign();
     }
 
     fn small_mul(&self, b: i32) -> Mod_e383_187 {
@@ -2092,12 +2250,40 @@ impl PrimeField for Mod_e383_187 {
 
         out
     }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        let ndigits = (8 * bytes.len() + 382) / 383;
+        let mut buf = [0u8; 48];
+        let mut out = ZERO;
+
+        // Read the input as digits in base 2^383, and evaluate it
+        // by Horner's rule, using 2^383 = 187 mod p.
+        for j in 0..ndigits {
+            let base = (ndigits - 1 - j) * 383;
+
+            for i in 0..48 {
+                let off = base + (8 * i);
+                let lo = bytes.get(off / 8).map_or(0, |b| *b as u16);
+                let hi = bytes.get((off / 8) + 1).map_or(0, |b| *b as u16);
+
+                buf[i] = (((hi << 8) | lo) >> (off % 8)) as u8;
+            }
+
+            buf[47] &= 0x7f;
+            out = out.small_mul(C_VAL as i32) + Mod_e383_187::unpacked(&buf);
+        }
+
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use choice::*;
+    use error::Error;
     use field::prime_field::*;
     use field::mod_e383_187::*;
+    use rand::thread_rng;
 
     const TWO: Mod_e383_187 = Mod_e383_187([ 2, 0, 0, 0, 0, 0, 0 ]);
 
@@ -4156,4 +4342,199 @@ mod tests {
             assert!(M_FOUR.normalize_eq(&mut val));
         }
     }
+
+    #[test]
+    fn test_neg() {
+        let l1_zeros: [&mut Mod_e383_187; 3] = [ &mut -ZERO,
+                                          &mut (&-ONE + &ONE),
+                                          &mut (&-M_TWO + &M_TWO) ];
+
+        let l1_mones: [&mut Mod_e383_187; 3] = [ &mut -ONE,
+                                          &mut -(&TWO - &ONE),
+                                          &mut (&-TWO + &ONE) ];
+
+        let l1_sixes: [&mut Mod_e383_187; 4] = [ &mut -M_SIX,
+                                          &mut (&-TWO * &M_THREE),
+                                          &mut (&(&M_ONE - &ONE) * &-THREE),
+                                          &mut (&(&ZERO - &TWO) -
+                                                &(&(&ONE - &NINE) * &ONE)) ];
+
+        for i in 0..3 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(M_ONE.normalize_eq(l1_mones[i]));
+        }
+
+        for i in 0..4 {
+            assert!(SIX.normalize_eq(l1_sixes[i]));
+        }
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+        let mut a = TWO;
+        let mut b = M_THREE;
+        let mut sel_f = Mod_e383_187::conditional_select(&TWO, &THREE, f);
+        let mut sel_t = Mod_e383_187::conditional_select(&TWO, &THREE, t);
+
+        assert!(TWO.normalize_eq(&mut sel_f));
+        assert!(THREE.normalize_eq(&mut sel_t));
+
+        Mod_e383_187::conditional_swap(&mut a, &mut b, f);
+        assert!(TWO.normalize_eq(&mut a));
+        assert!(M_THREE.normalize_eq(&mut b));
+
+        Mod_e383_187::conditional_swap(&mut a, &mut b, t);
+        assert!(M_THREE.normalize_eq(&mut a));
+        assert!(TWO.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_conditional_negate() {
+        let mut a = SIX;
+        let mut b = SIX;
+
+        a.conditional_negate(Choice::from(0));
+        b.conditional_negate(Choice::from(1));
+
+        assert!(SIX.normalize_eq(&mut a));
+        assert!(M_SIX.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(ZERO.ct_eq(&ZERO)));
+        assert!(bool::from(ZERO.ct_eq(&MODULUS)));
+        assert!(bool::from(ZERO.ct_eq(&(M_ONE + ONE))));
+        assert!(bool::from(SIX.ct_eq(&(TWO * THREE))));
+        assert!(bool::from(M_SIX.ct_eq(&(M_TWO * THREE))));
+        assert!(!bool::from(ZERO.ct_eq(&ONE)));
+        assert!(!bool::from(SIX.ct_eq(&M_SIX)));
+        assert!(!bool::from(TWO.ct_eq(&THREE)));
+    }
+
+    #[test]
+    fn test_sqrt_checked() {
+        let four_root = FOUR.sqrt_checked().unwrap();
+        let nine_root = NINE.sqrt_checked().unwrap();
+        let mut zero_root = ZERO.sqrt_checked().unwrap();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(ZERO.normalize_eq(&mut zero_root));
+        assert!(bool::from(SIXTEEN.sqrt_checked().is_some()));
+        assert!(bool::from(TWO.sqrt_checked().is_none()));
+        assert!(bool::from(M_TWO.sqrt_checked().is_none()));
+
+        // -1 is a square when p = 5 mod 8.
+        let m_one_root = M_ONE.sqrt_checked().unwrap();
+
+        assert!(M_ONE.normalize_eq(&mut m_one_root.squared()));
+    }
+
+    #[test]
+    fn test_invert_checked() {
+        let two_inv = TWO.invert_checked().unwrap();
+        let m_six_inv = M_SIX.invert_checked().unwrap();
+
+        assert!(ONE.normalize_eq(&mut (two_inv * TWO)));
+        assert!(ONE.normalize_eq(&mut (m_six_inv * M_SIX)));
+        assert!(bool::from(ZERO.invert_checked().is_none()));
+        assert!(bool::from(MODULUS.invert_checked().is_none()));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+        let mut long = [0u8; 49];
+        let mut actual = ZERO;
+
+        assert!(M_ONE.normalize_eq(&mut Mod_e383_187::unpacked_checked(&bytes)
+                                            .unwrap()));
+        assert!(actual.unpack_checked(&TWO.packed()).is_ok());
+        assert!(TWO.normalize_eq(&mut actual));
+        assert!(actual.unpack_checked(&ZERO.packed()).is_ok());
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        // The modulus itself.
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&bytes));
+
+        // Bits above the modulus.
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&[0xffu8; 48]));
+
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        long[0] = 1;
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long));
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long[0..47]));
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut rng = thread_rng();
+        let mut counts = [0u32; 383];
+
+        // Each bit of a uniform value is set about half the time.
+        for _ in 0..2000 {
+            let mut val = Mod_e383_187::rand(&mut rng);
+            let bytes = val.packed();
+
+            assert!(Mod_e383_187::unpacked_checked(&bytes).is_ok());
+
+            for i in 0..383 {
+                counts[i] += ((bytes[i / 8] >> (i % 8)) & 0x1) as u32;
+            }
+        }
+
+        for i in 0..383 {
+            assert!(counts[i] > 800 && counts[i] < 1200,
+                    "bit {} set {} times", i, counts[i]);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        const ONES: Mod_e383_187 = Mod_e383_187([ 0x22263, 0, 0, 0, 0, 0, 0 ]);
+
+        const SEQ: Mod_e383_187 =
+            Mod_e383_187([ 0x00f78e24bb5507df, 0x0060f78e24bcc960,
+                           0x00ca60f78e24bcca, 0x0033ca60f78e2633,
+                           0x009d33ca60f78f9d, 0x00069d33ca60f906,
+                           0x0000069d33ca6270 ]);
+
+        let mut bytes = [0u8; 96];
+        let mut out;
+
+        for i in 0..96 {
+            bytes[i] = ((i * 37) + 11) as u8;
+        }
+
+        out = Mod_e383_187::from_bytes_wide(&bytes);
+        assert!(SEQ.normalize_eq(&mut out));
+        out = Mod_e383_187::from_bytes_wide(&[0xff; 96]);
+        assert!(ONES.normalize_eq(&mut out));
+
+        // Values that fit in the field are unchanged.
+        bytes = [0u8; 96];
+        bytes[0..48].copy_from_slice(&M_ONE.packed());
+        out = Mod_e383_187::from_bytes_wide(&bytes);
+        assert!(M_ONE.normalize_eq(&mut out));
+
+        bytes[0] += 1;
+        out = Mod_e383_187::from_bytes_wide(&bytes);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e383_187::from_bytes_wide(&[]);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e383_187::from_bytes_wide(&[2]);
+        assert!(TWO.normalize_eq(&mut out));
+    }
 }
@@ -0,0 +1,1941 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_i64;
+use error::Error;
+use field::prime_field::PrimeField;
+use field::prime_field::PrimeFieldMask;
+use normalize::Normalize;
+use normalize::NormalizeEq;
+use pack::Pack;
+use rand::Rand;
+use rand::Rng;
+use std::clone::Clone;
+use std::fmt::Debug;
+use std::fmt::LowerHex;
+use std::fmt::UpperHex;
+use std::ops::AddAssign;
+use std::ops::Add;
+use std::ops::DivAssign;
+use std::ops::Div;
+use std::ops::MulAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::SubAssign;
+use std::ops::Sub;
+
+/// Elements of the finite field of integers mod 2^448 - 2^224 - 1.
+/// Used by the Ed448-Goldilocks curve.
+///
+/// This is represented using sixteen 28-bit digits, stored in an
+/// eight-element i64 array with two digits per word, just like the
+/// pseudo-Mersenne fields.  The modulus is not of the form 2^n - c,
+/// however.  It is a "golden-ratio" prime phi^2 - phi - 1, where
+/// phi = 2^224, so phi^2 = phi + 1.  Anything that carries out of
+/// the top digit is folded back in at both bit 0 and bit 224, and
+/// multiplication splits each value into halves a0 + a1 * phi, which
+/// needs only three half-size products with Karatsuba.
+///
+/// The internal representation is lazily normalized in the same way
+/// as the other fields: it may leave carry values in the highest-order
+/// digit, and it may hold a value greater than the modulus.  All
+/// operations are guaranteed to work on non-normal values of this
+/// kind.
+
+#[derive(Copy, Clone)]
+pub struct Mod_e448_Goldilocks([i64; 8]);
+
+/// Bitmasks corresponding to Mod_e448_Goldilocks.
+#[derive(Copy, Clone)]
+pub struct Mod_e448_Goldilocks_Mask([i64; 8]);
+
+/// The normalized representation of the value 0.
+pub const ZERO: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The normalized representation of the value 1.
+pub const ONE: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 1, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The normalized representation of the value -1.
+pub const M_ONE: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x00fffffffffffffe, 0x00ffffffffffffff,
+                          0x00ffffffffffffff, 0x00ffffffffffffff,
+                          0x00fffffffffffffe, 0x00ffffffffffffff,
+                          0x00ffffffffffffff, 0x00ffffffffffffff ]);
+
+/// The normalized representation of the modulus 2^448 - 2^224 - 1.
+pub const MODULUS: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x00ffffffffffffff, 0x00ffffffffffffff,
+                          0x00ffffffffffffff, 0x00ffffffffffffff,
+                          0x00fffffffffffffe, 0x00ffffffffffffff,
+                          0x00ffffffffffffff, 0x00ffffffffffffff ]);
+
+/// The value phi = 2^224, where 2^448 = phi + 1.
+const PHI: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x0000000000000000, 0x0000000000000000,
+                          0x0000000000000000, 0x0000000000000000,
+                          0x0000000000000001, 0x0000000000000000,
+                          0x0000000000000000, 0x0000000000000000 ]);
+
+/// The x-coordinate of the base point of the curve Ed448-Goldilocks,
+/// 0x4f1970c66bed0ded221d15a622bf36da9e146570470f1767ea6de324a3d3a46412ae1af72ab66511433b80e18b00938e2626a82bc70cc05e.
+pub const ED448_BASE_X: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x0026a82bc70cc05e, 0x0080e18b00938e26,
+                          0x00f72ab66511433b, 0x00a3d3a46412ae1a,
+                          0x000f1767ea6de324, 0x0036da9e14657047,
+                          0x00ed221d15a622bf, 0x004f1970c66bed0d ]);
+
+/// The y-coordinate of the base point of the curve Ed448-Goldilocks,
+/// 0x693f46716eb6bc248876203756c9c7624bea73736ca3984087789c1e05a0c2d73ad3ff1ce67c39c4fdbd132c4ed7c8ad9808795bf230fa14.
+pub const ED448_BASE_Y: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x0008795bf230fa14, 0x00132c4ed7c8ad98,
+                          0x001ce67c39c4fdbd, 0x0005a0c2d73ad3ff,
+                          0x00a3984087789c1e, 0x00c7624bea73736c,
+                          0x00248876203756c9, 0x00693f46716eb6bc ]);
+
+/// The d value -39081 in the Ed448-Goldilocks curve equation
+/// x^2 + y^2 = 1 + dx^2y^2.
+pub const ED448_D: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x00ffffffffff6756, 0x00ffffffffffffff,
+                          0x00ffffffffffffff, 0x00ffffffffffffff,
+                          0x00fffffffffffffe, 0x00ffffffffffffff,
+                          0x00ffffffffffffff, 0x00ffffffffffffff ]);
+
+/// The non-negative square root of -d for Ed448-Goldilocks, used by
+/// Decaf, 0x22d962fbeb24f7683bf68d722fa26aa0a1f1a7b8a5b8d54b64a2d780968c14ba839a66f4fd6eded260337bf6aa20ce529642ef0f45572736.
+pub const ED448_SQRT_MINUS_D: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x0042ef0f45572736, 0x007bf6aa20ce5296,
+                          0x00f4fd6eded26033, 0x00968c14ba839a66,
+                          0x00b8d54b64a2d780, 0x006aa0a1f1a7b8a5,
+                          0x00683bf68d722fa2, 0x0022d962fbeb24f7 ]);
+
+/// The inverse of the square root of -d for Ed448-Goldilocks, used by
+/// Decaf, 0x6ef40652e222c057902be35a0bcac8075a90950c3a5b27a7d6ba56f128a6521abe707ee2c21fba15efbb2479f19e94f353afbb5eb878682c.
+pub const ED448_INVSQRT_MINUS_D: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x00afbb5eb878682c, 0x002479f19e94f353,
+                          0x00e2c21fba15efbb, 0x0028a6521abe707e,
+                          0x005b27a7d6ba56f1, 0x00c8075a90950c3a,
+                          0x0057902be35a0bca, 0x006ef40652e222c0 ]);
+
+/// The x-coordinate of the base point of the curve Curve448, 0x5.
+pub const CURVE448_BASE_X: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x5, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The A value 156326 in the Curve448 equation y^2 = x^3 + Ax^2 + x.
+pub const CURVE448_A: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x262a6, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The value (A + 2) / 4 = 39082 for the Curve448 equation
+/// y^2 = x^3 + Ax^2 + x, used by the Montgomery ladder.
+pub const CURVE448_A24: Mod_e448_Goldilocks =
+    Mod_e448_Goldilocks([ 0x98aa, 0, 0, 0, 0, 0, 0, 0 ]);
+
+impl Debug for Mod_e448_Goldilocks {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Mod_e448_Goldilocks: [ {:x}", &self.0[0])?;
+
+        for i in 1..8 {
+            write!(f, ", {:x}", &self.0[i])?;
+        }
+
+        write!(f, " ]")
+    }
+}
+
+impl LowerHex for Mod_e448_Goldilocks {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let mut cpy = self.clone();
+        let bytes = cpy.packed();
+
+        for i in 0..56 {
+            write!(f, "{:02x}", bytes[55 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl UpperHex for Mod_e448_Goldilocks {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let mut cpy = self.clone();
+        let bytes = cpy.packed();
+
+        for i in 0..56 {
+            write!(f, "{:02X}", bytes[55 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Multiply two eight-digit halves, giving a sixteen-digit product
+/// with each digit but the last reduced to 28 bits.
+fn mul_half(a: &[i64], b: &[i64]) -> [i64; 16] {
+    let mut out = [0i64; 16];
+
+    for i in 0..8 {
+        for j in 0..8 {
+            out[i + j] += a[i] * b[j];
+        }
+    }
+
+    for i in 0..15 {
+        out[i + 1] += out[i] >> 28;
+        out[i] &= 0x0fffffff;
+    }
+
+    out
+}
+
+impl Mod_e448_Goldilocks {
+    /// Get the carry-in value.  We use the highest carry slot to
+    /// stash the carry-out value of each operation, and feed that
+    /// back into the next one.
+    fn carry_out(&self) -> i64 {
+        self.0[7] >> 56
+    }
+
+    /// Add 2p to a value whose representation has gone negative.
+    /// Multiplication assumes that every digit is non-negative, so
+    /// this must follow any operation that can borrow out of the
+    /// highest digit.
+    fn fix_sign(&mut self) {
+        let m = self.0[7] >> 63;
+        let s0 = self.0[0] + ((MODULUS.0[0] << 1) & m);
+        let c0 = s0 >> 56;
+        let s1 = self.0[1] + ((MODULUS.0[1] << 1) & m) + c0;
+        let c1 = s1 >> 56;
+        let s2 = self.0[2] + ((MODULUS.0[2] << 1) & m) + c1;
+        let c2 = s2 >> 56;
+        let s3 = self.0[3] + ((MODULUS.0[3] << 1) & m) + c2;
+        let c3 = s3 >> 56;
+        let s4 = self.0[4] + ((MODULUS.0[4] << 1) & m) + c3;
+        let c4 = s4 >> 56;
+        let s5 = self.0[5] + ((MODULUS.0[5] << 1) & m) + c4;
+        let c5 = s5 >> 56;
+        let s6 = self.0[6] + ((MODULUS.0[6] << 1) & m) + c5;
+        let c6 = s6 >> 56;
+        let s7 = self.0[7] + ((MODULUS.0[7] << 1) & m) + c6;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6 & 0x00ffffffffffffff;
+        self.0[7] = s7;
+    }
+
+    /// Fold the carry slot back into the low digits, using
+    /// 2^448 = 2^224 + 1.
+    fn fold_carry(&mut self) {
+        let cin = self.carry_out();
+        let s0 = self.0[0] + cin;
+        let c0 = s0 >> 56;
+        let s1 = self.0[1] + c0;
+        let c1 = s1 >> 56;
+        let s2 = self.0[2] + c1;
+        let c2 = s2 >> 56;
+        let s3 = self.0[3] + c2;
+        let c3 = s3 >> 56;
+        let s4 = self.0[4] + cin + c3;
+        let c4 = s4 >> 56;
+        let s5 = self.0[5] + c4;
+        let c5 = s5 >> 56;
+        let s6 = self.0[6] + c5;
+        let c6 = s6 >> 56;
+        let s7 = (self.0[7] & 0x00ffffffffffffff) + c6;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6 & 0x00ffffffffffffff;
+        self.0[7] = s7;
+    }
+
+    /// Split into sixteen 28-bit digits.  The last digit also holds
+    /// the carry slot.
+    fn digits(&self) -> [i64; 16] {
+        let mut out = [0i64; 16];
+
+        for i in 0..8 {
+            out[2 * i] = self.0[i] & 0x0fffffff;
+            out[(2 * i) + 1] = self.0[i] >> 28;
+        }
+
+        out
+    }
+
+    /// Build a value from sixteen digits of any size, folding
+    /// anything above bit 448 back in.
+    fn from_digits(digits: &[i64; 16]) -> Self {
+        let mut d = *digits;
+        let mut out = ZERO;
+
+        for i in 0..15 {
+            d[i + 1] += d[i] >> 28;
+            d[i] &= 0x0fffffff;
+        }
+
+        let over = d[15] >> 28;
+
+        d[15] &= 0x0fffffff;
+        d[0] += over;
+        d[8] += over;
+
+        for i in 0..15 {
+            d[i + 1] += d[i] >> 28;
+            d[i] &= 0x0fffffff;
+        }
+
+        for i in 0..8 {
+            out.0[i] = d[2 * i] | (d[(2 * i) + 1] << 28);
+        }
+
+        out.fix_sign();
+
+        out
+    }
+
+    /// Serialize a value as a little-endian byte array.  This has the
+    /// effect of normalizing the representation.
+    pub fn packed(&mut self) -> [u8; 56] {
+        let mut out = [0u8; 56];
+        self.pack(&mut out);
+        out
+    }
+}
+
+impl Neg for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn neg(self) -> Mod_e448_Goldilocks {
+        let mut out = ZERO;
+
+        out -= &self;
+        out
+    }
+}
+
+impl<'b> AddAssign<&'b Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    fn add_assign(&mut self, rhs: &'b Mod_e448_Goldilocks) {
+        let a0 = self.0[0];
+        let a1 = self.0[1];
+        let a2 = self.0[2];
+        let a3 = self.0[3];
+        let a4 = self.0[4];
+        let a5 = self.0[5];
+        let a6 = self.0[6];
+        let a7 = self.0[7] & 0x00ffffffffffffff;
+
+        let b0 = rhs.0[0];
+        let b1 = rhs.0[1];
+        let b2 = rhs.0[2];
+        let b3 = rhs.0[3];
+        let b4 = rhs.0[4];
+        let b5 = rhs.0[5];
+        let b6 = rhs.0[6];
+        let b7 = rhs.0[7] & 0x00ffffffffffffff;
+
+        let cin = self.carry_out() + rhs.carry_out();
+        let s0 = a0 + b0 + cin;
+        let c0 = s0 >> 56;
+        let s1 = a1 + b1 + c0;
+        let c1 = s1 >> 56;
+        let s2 = a2 + b2 + c1;
+        let c2 = s2 >> 56;
+        let s3 = a3 + b3 + c2;
+        let c3 = s3 >> 56;
+        let s4 = a4 + b4 + cin + c3;
+        let c4 = s4 >> 56;
+        let s5 = a5 + b5 + c4;
+        let c5 = s5 >> 56;
+        let s6 = a6 + b6 + c5;
+        let c6 = s6 >> 56;
+        let s7 = a7 + b7 + c6;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6 & 0x00ffffffffffffff;
+        self.0[7] = s7;
+    }
+}
+
+impl AddAssign<Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    fn add_assign(&mut self, rhs: Mod_e448_Goldilocks) {
+        *self += &rhs;
+    }
+}
+
+impl AddAssign<i32> for Mod_e448_Goldilocks {
+    fn add_assign(&mut self, rhs: i32) {
+        self.small_add_assign(rhs);
+    }
+}
+
+impl AddAssign<i16> for Mod_e448_Goldilocks {
+    fn add_assign(&mut self, rhs: i16) {
+        self.small_add_assign(rhs as i32);
+    }
+}
+
+impl AddAssign<i8> for Mod_e448_Goldilocks {
+    fn add_assign(&mut self, rhs: i8) {
+        self.small_add_assign(rhs as i32);
+    }
+}
+
+impl<'a, 'b> Add<&'b Mod_e448_Goldilocks> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: &'b Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        let mut out = self.clone();
+        out += a;
+        out
+    }
+}
+
+impl Add<Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        &self + &a
+    }
+}
+
+impl<'a> Add<&'a Mod_e448_Goldilocks> for i32 {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: &'a Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        a.small_add(self)
+    }
+}
+
+impl<'a> Add<&'a Mod_e448_Goldilocks> for i16 {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: &'a Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        a.small_add(self as i32)
+    }
+}
+
+impl<'a> Add<&'a Mod_e448_Goldilocks> for i8 {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: &'a Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        a.small_add(self as i32)
+    }
+}
+
+impl<'a> Add<i32> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: i32) -> Mod_e448_Goldilocks {
+        self.small_add(a)
+    }
+}
+
+impl<'a> Add<i16> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: i16) -> Mod_e448_Goldilocks {
+        self.small_add(a as i32)
+    }
+}
+
+impl<'a> Add<i8> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: i8) -> Mod_e448_Goldilocks {
+        self.small_add(a as i32)
+    }
+}
+
+impl Add<i32> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: i32) -> Mod_e448_Goldilocks {
+        &self + a
+    }
+}
+
+impl Add<i16> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: i16) -> Mod_e448_Goldilocks {
+        &self + a
+    }
+}
+
+impl Add<i8> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn add(self, a: i8) -> Mod_e448_Goldilocks {
+        &self + a
+    }
+}
+
+impl<'b> DivAssign<&'b Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    fn div_assign(&mut self, rhs: &'b Mod_e448_Goldilocks) {
+        *self *= &rhs.inverted();
+    }
+}
+
+impl DivAssign<Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    fn div_assign(&mut self, rhs: Mod_e448_Goldilocks) {
+        *self /= &rhs;
+    }
+}
+
+impl<'a, 'b> Div<&'b Mod_e448_Goldilocks> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn div(self, a: &'b Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        let mut out = self.clone();
+        out /= a;
+        out
+    }
+}
+
+impl Div<Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn div(self, a: Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        &self / &a
+    }
+}
+
+impl<'b> SubAssign<&'b Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    fn sub_assign(&mut self, rhs: &'b Mod_e448_Goldilocks) {
+        let a0 = self.0[0];
+        let a1 = self.0[1];
+        let a2 = self.0[2];
+        let a3 = self.0[3];
+        let a4 = self.0[4];
+        let a5 = self.0[5];
+        let a6 = self.0[6];
+        let a7 = self.0[7] & 0x00ffffffffffffff;
+
+        let b0 = rhs.0[0];
+        let b1 = rhs.0[1];
+        let b2 = rhs.0[2];
+        let b3 = rhs.0[3];
+        let b4 = rhs.0[4];
+        let b5 = rhs.0[5];
+        let b6 = rhs.0[6];
+        let b7 = rhs.0[7] & 0x00ffffffffffffff;
+
+        let cin = self.carry_out() - rhs.carry_out();
+        let s0 = a0 - b0 + cin;
+        let c0 = s0 >> 56;
+        let s1 = a1 - b1 + c0;
+        let c1 = s1 >> 56;
+        let s2 = a2 - b2 + c1;
+        let c2 = s2 >> 56;
+        let s3 = a3 - b3 + c2;
+        let c3 = s3 >> 56;
+        let s4 = a4 - b4 + cin + c3;
+        let c4 = s4 >> 56;
+        let s5 = a5 - b5 + c4;
+        let c5 = s5 >> 56;
+        let s6 = a6 - b6 + c5;
+        let c6 = s6 >> 56;
+        let s7 = a7 - b7 + c6;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6 & 0x00ffffffffffffff;
+        self.0[7] = s7;
+
+        self.fix_sign();
+    }
+}
+
+impl SubAssign<Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    fn sub_assign(&mut self, rhs: Mod_e448_Goldilocks) {
+        *self -= &rhs
+    }
+}
+
+impl SubAssign<i32> for Mod_e448_Goldilocks {
+    fn sub_assign(&mut self, rhs: i32) {
+        self.small_sub_assign(rhs);
+    }
+}
+
+impl SubAssign<i16> for Mod_e448_Goldilocks {
+    fn sub_assign(&mut self, rhs: i16) {
+        self.small_sub_assign(rhs as i32);
+    }
+}
+
+impl SubAssign<i8> for Mod_e448_Goldilocks {
+    fn sub_assign(&mut self, rhs: i8) {
+        self.small_sub_assign(rhs as i32);
+    }
+}
+
+impl Sub<i32> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn sub(self, a: i32) -> Mod_e448_Goldilocks {
+        &self - a
+    }
+}
+
+impl Sub<i16> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn sub(self, a: i16) -> Mod_e448_Goldilocks {
+        &self - a
+    }
+}
+
+impl Sub<i8> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn sub(self, a: i8) -> Mod_e448_Goldilocks {
+        &self - a
+    }
+}
+
+impl<'a, 'b> Sub<&'b Mod_e448_Goldilocks> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn sub(self, a: &'b Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        let mut out = self.clone();
+        out -= a;
+        out
+    }
+}
+
+impl Sub<Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn sub(self, a: Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        &self - &a
+    }
+}
+
+impl<'a> Sub<i32> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn sub(self, a: i32) -> Mod_e448_Goldilocks {
+        self.small_sub(a)
+    }
+}
+
+impl<'a> Sub<i16> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn sub(self, a: i16) -> Mod_e448_Goldilocks {
+        self.small_sub(a as i32)
+    }
+}
+
+impl<'a> Sub<i8> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn sub(self, a: i8) -> Mod_e448_Goldilocks {
+        self.small_sub(a as i32)
+    }
+}
+
+impl MulAssign<i32> for Mod_e448_Goldilocks {
+    fn mul_assign(&mut self, rhs: i32) {
+        self.small_mul_assign(rhs);
+    }
+}
+
+impl MulAssign<i16> for Mod_e448_Goldilocks {
+    fn mul_assign(&mut self, rhs: i16) {
+        self.small_mul_assign(rhs as i32);
+    }
+}
+
+impl MulAssign<i8> for Mod_e448_Goldilocks {
+    fn mul_assign(&mut self, rhs: i8) {
+        self.small_mul_assign(rhs as i32);
+    }
+}
+
+impl<'b> MulAssign<&'b Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    fn mul_assign(&mut self, rhs: &'b Mod_e448_Goldilocks) {
+        let a = self.digits();
+        let b = rhs.digits();
+        let mut sa = [0i64; 8];
+        let mut sb = [0i64; 8];
+
+        for i in 0..8 {
+            sa[i] = a[i] + a[i + 8];
+            sb[i] = b[i] + b[i + 8];
+        }
+
+        // With a = a0 + a1 * phi and b = b0 + b1 * phi, Karatsuba and
+        // phi^2 = phi + 1 give
+        // ab = (a0b0 + a1b1) + ((a0 + a1)(b0 + b1) - a0b0) * phi.
+        let lo = mul_half(&a[0..8], &b[0..8]);
+        let hi = mul_half(&a[8..16], &b[8..16]);
+        let mid = mul_half(&sa, &sb);
+        let mut d = [0i64; 24];
+
+        for i in 0..16 {
+            d[i] += lo[i] + hi[i];
+            d[i + 8] += mid[i] - lo[i];
+        }
+
+        // Fold the digits at phi^2 and above back down.
+        for i in 0..8 {
+            let k = 23 - i;
+
+            d[k - 16] += d[k];
+            d[k - 8] += d[k];
+        }
+
+        let mut out = [0i64; 16];
+
+        out.copy_from_slice(&d[0..16]);
+        *self = Mod_e448_Goldilocks::from_digits(&out);
+    }
+}
+
+impl MulAssign<Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    fn mul_assign(&mut self, rhs: Mod_e448_Goldilocks) {
+        *self *= &rhs;
+    }
+}
+
+impl<'a> Mul<&'a Mod_e448_Goldilocks> for i32 {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: &'a Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        a.small_mul(self)
+    }
+}
+
+impl<'a> Mul<&'a Mod_e448_Goldilocks> for i16 {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: &'a Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        a.small_mul(self as i32)
+    }
+}
+
+impl<'a> Mul<&'a Mod_e448_Goldilocks> for i8 {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: &'a Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        a.small_mul(self as i32)
+    }
+}
+
+impl<'a> Mul<i32> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: i32) -> Mod_e448_Goldilocks {
+        self.small_mul(a)
+    }
+}
+
+impl<'a> Mul<i16> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: i16) -> Mod_e448_Goldilocks {
+        self.small_mul(a as i32)
+    }
+}
+
+impl<'a> Mul<i8> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: i8) -> Mod_e448_Goldilocks {
+        self.small_mul(a as i32)
+    }
+}
+
+impl Mul<i32> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: i32) -> Mod_e448_Goldilocks {
+        &self * a
+    }
+}
+
+impl Mul<i16> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: i16) -> Mod_e448_Goldilocks {
+        &self * a
+    }
+}
+
+impl Mul<i8> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: i8) -> Mod_e448_Goldilocks {
+        &self * a
+    }
+}
+
+impl<'a, 'b> Mul<&'b Mod_e448_Goldilocks> for &'a Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: &'b Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        let mut out = self.clone();
+        out *= a;
+        out
+    }
+}
+
+impl Mul<Mod_e448_Goldilocks> for Mod_e448_Goldilocks {
+    type Output = Mod_e448_Goldilocks;
+
+    fn mul(self, a: Mod_e448_Goldilocks) -> Mod_e448_Goldilocks {
+        &self * &a
+    }
+}
+
+impl Rand for Mod_e448_Goldilocks {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 56];
+        let mut out = ZERO;
+
+        // Rejection sampling: draw 448-bit values until one is less
+        // than the modulus.  This is uniform, and almost never
+        // takes more than one try.
+        loop {
+            rng.fill_bytes(&mut bytes);
+
+            if out.unpack_checked(&bytes).is_ok() {
+                return out;
+            }
+        }
+    }
+}
+
+impl Pack for Mod_e448_Goldilocks {
+    fn unpack(&mut self, bytes: &[u8]) {
+        self.0[0] = ((bytes[0] as i64) & 0x00000000000000ff) |
+                  (((bytes[1] as i64) << 8) & 0x000000000000ff00) |
+                  (((bytes[2] as i64) << 16) & 0x0000000000ff0000) |
+                  (((bytes[3] as i64) << 24) & 0x00000000ff000000) |
+                  (((bytes[4] as i64) << 32) & 0x000000ff00000000) |
+                  (((bytes[5] as i64) << 40) & 0x0000ff0000000000) |
+                  (((bytes[6] as i64) << 48) & 0x00ff000000000000);
+        self.0[1] = ((bytes[7] as i64) & 0x00000000000000ff) |
+                  (((bytes[8] as i64) << 8) & 0x000000000000ff00) |
+                  (((bytes[9] as i64) << 16) & 0x0000000000ff0000) |
+                  (((bytes[10] as i64) << 24) & 0x00000000ff000000) |
+                  (((bytes[11] as i64) << 32) & 0x000000ff00000000) |
+                  (((bytes[12] as i64) << 40) & 0x0000ff0000000000) |
+                  (((bytes[13] as i64) << 48) & 0x00ff000000000000);
+        self.0[2] = ((bytes[14] as i64) & 0x00000000000000ff) |
+                  (((bytes[15] as i64) << 8) & 0x000000000000ff00) |
+                  (((bytes[16] as i64) << 16) & 0x0000000000ff0000) |
+                  (((bytes[17] as i64) << 24) & 0x00000000ff000000) |
+                  (((bytes[18] as i64) << 32) & 0x000000ff00000000) |
+                  (((bytes[19] as i64) << 40) & 0x0000ff0000000000) |
+                  (((bytes[20] as i64) << 48) & 0x00ff000000000000);
+        self.0[3] = ((bytes[21] as i64) & 0x00000000000000ff) |
+                  (((bytes[22] as i64) << 8) & 0x000000000000ff00) |
+                  (((bytes[23] as i64) << 16) & 0x0000000000ff0000) |
+                  (((bytes[24] as i64) << 24) & 0x00000000ff000000) |
+                  (((bytes[25] as i64) << 32) & 0x000000ff00000000) |
+                  (((bytes[26] as i64) << 40) & 0x0000ff0000000000) |
+                  (((bytes[27] as i64) << 48) & 0x00ff000000000000);
+        self.0[4] = ((bytes[28] as i64) & 0x00000000000000ff) |
+                  (((bytes[29] as i64) << 8) & 0x000000000000ff00) |
+                  (((bytes[30] as i64) << 16) & 0x0000000000ff0000) |
+                  (((bytes[31] as i64) << 24) & 0x00000000ff000000) |
+                  (((bytes[32] as i64) << 32) & 0x000000ff00000000) |
+                  (((bytes[33] as i64) << 40) & 0x0000ff0000000000) |
+                  (((bytes[34] as i64) << 48) & 0x00ff000000000000);
+        self.0[5] = ((bytes[35] as i64) & 0x00000000000000ff) |
+                  (((bytes[36] as i64) << 8) & 0x000000000000ff00) |
+                  (((bytes[37] as i64) << 16) & 0x0000000000ff0000) |
+                  (((bytes[38] as i64) << 24) & 0x00000000ff000000) |
+                  (((bytes[39] as i64) << 32) & 0x000000ff00000000) |
+                  (((bytes[40] as i64) << 40) & 0x0000ff0000000000) |
+                  (((bytes[41] as i64) << 48) & 0x00ff000000000000);
+        self.0[6] = ((bytes[42] as i64) & 0x00000000000000ff) |
+                  (((bytes[43] as i64) << 8) & 0x000000000000ff00) |
+                  (((bytes[44] as i64) << 16) & 0x0000000000ff0000) |
+                  (((bytes[45] as i64) << 24) & 0x00000000ff000000) |
+                  (((bytes[46] as i64) << 32) & 0x000000ff00000000) |
+                  (((bytes[47] as i64) << 40) & 0x0000ff0000000000) |
+                  (((bytes[48] as i64) << 48) & 0x00ff000000000000);
+        self.0[7] = ((bytes[49] as i64) & 0x00000000000000ff) |
+                  (((bytes[50] as i64) << 8) & 0x000000000000ff00) |
+                  (((bytes[51] as i64) << 16) & 0x0000000000ff0000) |
+                  (((bytes[52] as i64) << 24) & 0x00000000ff000000) |
+                  (((bytes[53] as i64) << 32) & 0x000000ff00000000) |
+                  (((bytes[54] as i64) << 40) & 0x0000ff0000000000) |
+                  (((bytes[55] as i64) << 48) & 0x00ff000000000000);
+    }
+
+    fn unpacked(bytes: &[u8]) -> Self {
+        let mut out = ZERO;
+        out.unpack(bytes);
+        out
+    }
+
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 56 {
+            return Err(Error::InvalidLength);
+        }
+
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        // Normalizing changes the value exactly when it is at least
+        // the modulus.
+        if !out.clone().normalize_self_eq(&out) {
+            return Err(Error::NonCanonical);
+        }
+
+        *self = out;
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
+    fn pack(&mut self, bytes: &mut [u8]) {
+        self.normalize();
+        self.pack_normalized(bytes)
+    }
+
+    fn pack_normalized(&self, bytes: &mut [u8]) {
+        bytes[0] = (self.0[0] & 0b11111111) as u8;
+        bytes[1] = ((self.0[0] >> 8) & 0b11111111) as u8;
+        bytes[2] = ((self.0[0] >> 16) & 0b11111111) as u8;
+        bytes[3] = ((self.0[0] >> 24) & 0b11111111) as u8;
+        bytes[4] = ((self.0[0] >> 32) & 0b11111111) as u8;
+        bytes[5] = ((self.0[0] >> 40) & 0b11111111) as u8;
+        bytes[6] = ((self.0[0] >> 48) & 0b11111111) as u8;
+        bytes[7] = (self.0[1] & 0b11111111) as u8;
+        bytes[8] = ((self.0[1] >> 8) & 0b11111111) as u8;
+        bytes[9] = ((self.0[1] >> 16) & 0b11111111) as u8;
+        bytes[10] = ((self.0[1] >> 24) & 0b11111111) as u8;
+        bytes[11] = ((self.0[1] >> 32) & 0b11111111) as u8;
+        bytes[12] = ((self.0[1] >> 40) & 0b11111111) as u8;
+        bytes[13] = ((self.0[1] >> 48) & 0b11111111) as u8;
+        bytes[14] = (self.0[2] & 0b11111111) as u8;
+        bytes[15] = ((self.0[2] >> 8) & 0b11111111) as u8;
+        bytes[16] = ((self.0[2] >> 16) & 0b11111111) as u8;
+        bytes[17] = ((self.0[2] >> 24) & 0b11111111) as u8;
+        bytes[18] = ((self.0[2] >> 32) & 0b11111111) as u8;
+        bytes[19] = ((self.0[2] >> 40) & 0b11111111) as u8;
+        bytes[20] = ((self.0[2] >> 48) & 0b11111111) as u8;
+        bytes[21] = (self.0[3] & 0b11111111) as u8;
+        bytes[22] = ((self.0[3] >> 8) & 0b11111111) as u8;
+        bytes[23] = ((self.0[3] >> 16) & 0b11111111) as u8;
+        bytes[24] = ((self.0[3] >> 24) & 0b11111111) as u8;
+        bytes[25] = ((self.0[3] >> 32) & 0b11111111) as u8;
+        bytes[26] = ((self.0[3] >> 40) & 0b11111111) as u8;
+        bytes[27] = ((self.0[3] >> 48) & 0b11111111) as u8;
+        bytes[28] = (self.0[4] & 0b11111111) as u8;
+        bytes[29] = ((self.0[4] >> 8) & 0b11111111) as u8;
+        bytes[30] = ((self.0[4] >> 16) & 0b11111111) as u8;
+        bytes[31] = ((self.0[4] >> 24) & 0b11111111) as u8;
+        bytes[32] = ((self.0[4] >> 32) & 0b11111111) as u8;
+        bytes[33] = ((self.0[4] >> 40) & 0b11111111) as u8;
+        bytes[34] = ((self.0[4] >> 48) & 0b11111111) as u8;
+        bytes[35] = (self.0[5] & 0b11111111) as u8;
+        bytes[36] = ((self.0[5] >> 8) & 0b11111111) as u8;
+        bytes[37] = ((self.0[5] >> 16) & 0b11111111) as u8;
+        bytes[38] = ((self.0[5] >> 24) & 0b11111111) as u8;
+        bytes[39] = ((self.0[5] >> 32) & 0b11111111) as u8;
+        bytes[40] = ((self.0[5] >> 40) & 0b11111111) as u8;
+        bytes[41] = ((self.0[5] >> 48) & 0b11111111) as u8;
+        bytes[42] = (self.0[6] & 0b11111111) as u8;
+        bytes[43] = ((self.0[6] >> 8) & 0b11111111) as u8;
+        bytes[44] = ((self.0[6] >> 16) & 0b11111111) as u8;
+        bytes[45] = ((self.0[6] >> 24) & 0b11111111) as u8;
+        bytes[46] = ((self.0[6] >> 32) & 0b11111111) as u8;
+        bytes[47] = ((self.0[6] >> 40) & 0b11111111) as u8;
+        bytes[48] = ((self.0[6] >> 48) & 0b11111111) as u8;
+        bytes[49] = (self.0[7] & 0b11111111) as u8;
+        bytes[50] = ((self.0[7] >> 8) & 0b11111111) as u8;
+        bytes[51] = ((self.0[7] >> 16) & 0b11111111) as u8;
+        bytes[52] = ((self.0[7] >> 24) & 0b11111111) as u8;
+        bytes[53] = ((self.0[7] >> 32) & 0b11111111) as u8;
+        bytes[54] = ((self.0[7] >> 40) & 0b11111111) as u8;
+        bytes[55] = ((self.0[7] >> 48) & 0b11111111) as u8;
+    }
+
+    fn nbytes() -> i32 {
+        56
+    }
+}
+
+impl Normalize for Mod_e448_Goldilocks {
+    fn normalize(&mut self) {
+        // After one fold, at most a single carry can remain, and
+        // folding that one leaves the value below 2^448.
+        self.fold_carry();
+        self.fold_carry();
+
+        // Subtract the modulus if adding 2^224 + 1 carries out of
+        // bit 448.
+        let t0 = self.0[0] + 1;
+        let k0 = t0 >> 56;
+        let t1 = self.0[1] + k0;
+        let k1 = t1 >> 56;
+        let t2 = self.0[2] + k1;
+        let k2 = t2 >> 56;
+        let t3 = self.0[3] + k2;
+        let k3 = t3 >> 56;
+        let t4 = self.0[4] + 1 + k3;
+        let k4 = t4 >> 56;
+        let t5 = self.0[5] + k4;
+        let k5 = t5 >> 56;
+        let t6 = self.0[6] + k5;
+        let k6 = t6 >> 56;
+        let t7 = self.0[7] + k6;
+        let m = -(t7 >> 56);
+
+        self.0[0] = (self.0[0] & !m) | (t0 & 0x00ffffffffffffff & m);
+        self.0[1] = (self.0[1] & !m) | (t1 & 0x00ffffffffffffff & m);
+        self.0[2] = (self.0[2] & !m) | (t2 & 0x00ffffffffffffff & m);
+        self.0[3] = (self.0[3] & !m) | (t3 & 0x00ffffffffffffff & m);
+        self.0[4] = (self.0[4] & !m) | (t4 & 0x00ffffffffffffff & m);
+        self.0[5] = (self.0[5] & !m) | (t5 & 0x00ffffffffffffff & m);
+        self.0[6] = (self.0[6] & !m) | (t6 & 0x00ffffffffffffff & m);
+        self.0[7] = (self.0[7] & !m) | (t7 & 0x00ffffffffffffff & m);
+    }
+}
+
+impl NormalizeEq for Mod_e448_Goldilocks {
+    fn normalize_self_eq(&mut self, other: &Self) -> bool {
+        let mut are_equal: bool = true;
+
+        self.normalize();
+
+        are_equal &= self.0[0] == other.0[0];
+        are_equal &= self.0[1] == other.0[1];
+        are_equal &= self.0[2] == other.0[2];
+        are_equal &= self.0[3] == other.0[3];
+        are_equal &= self.0[4] == other.0[4];
+        are_equal &= self.0[5] == other.0[5];
+        are_equal &= self.0[6] == other.0[6];
+        are_equal &= self.0[7] == other.0[7];
+
+        are_equal
+    }
+
+    fn normalize_eq(&mut self, other: &mut Self) -> bool {
+        other.normalize();
+        self.normalize_self_eq(other)
+    }
+}
+
+impl PrimeFieldMask for Mod_e448_Goldilocks_Mask {
+    fn fill(&mut self, bit: bool) {
+        let mut mask = bit as i64;
+
+        mask |= mask << 1;
+        mask |= mask << 2;
+        mask |= mask << 4;
+        mask |= mask << 8;
+        mask |= mask << 16;
+        mask |= mask << 32;
+
+        self.0[0] = mask;
+        self.0[1] = mask;
+        self.0[2] = mask;
+        self.0[3] = mask;
+        self.0[4] = mask;
+        self.0[5] = mask;
+        self.0[6] = mask;
+        self.0[7] = mask;
+    }
+
+    fn filled(bit: bool) -> Self {
+        let mut mask = bit as i64;
+
+        mask |= mask << 1;
+        mask |= mask << 2;
+        mask |= mask << 4;
+        mask |= mask << 8;
+        mask |= mask << 16;
+        mask |= mask << 32;
+
+        Mod_e448_Goldilocks_Mask([mask; 8])
+    }
+}
+
+impl ConditionallySelectable for Mod_e448_Goldilocks {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = Mod_e448_Goldilocks_Mask::filled(choice.into());
+
+        Mod_e448_Goldilocks([ a.0[0] ^ ((a.0[0] ^ b.0[0]) & mask.0[0]),
+                       a.0[1] ^ ((a.0[1] ^ b.0[1]) & mask.0[1]),
+                       a.0[2] ^ ((a.0[2] ^ b.0[2]) & mask.0[2]),
+                       a.0[3] ^ ((a.0[3] ^ b.0[3]) & mask.0[3]),
+                       a.0[4] ^ ((a.0[4] ^ b.0[4]) & mask.0[4]),
+                       a.0[5] ^ ((a.0[5] ^ b.0[5]) & mask.0[5]),
+                       a.0[6] ^ ((a.0[6] ^ b.0[6]) & mask.0[6]),
+                       a.0[7] ^ ((a.0[7] ^ b.0[7]) & mask.0[7]) ])
+    }
+}
+
+impl ConditionallyNegatable for Mod_e448_Goldilocks {
+    fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -*self;
+
+        self.conditional_assign(&neg, choice);
+    }
+}
+
+impl ConstantTimeEq for Mod_e448_Goldilocks {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.normalize();
+        b.normalize();
+
+        is_zero_i64((a.0[0] ^ b.0[0]) | (a.0[1] ^ b.0[1]) |
+                    (a.0[2] ^ b.0[2]) | (a.0[3] ^ b.0[3]) |
+                    (a.0[4] ^ b.0[4]) | (a.0[5] ^ b.0[5]) |
+                    (a.0[6] ^ b.0[6]) | (a.0[7] ^ b.0[7]))
+    }
+}
+
+impl PrimeField for Mod_e448_Goldilocks {
+    fn fill(&mut self, bit: bool) {
+        let mut mask = bit as i64;
+
+        mask |= mask << 1;
+        mask |= mask << 2;
+        mask |= mask << 4;
+        mask |= mask << 8;
+        mask |= mask << 16;
+        mask |= mask << 32;
+
+        self.0[0] = mask;
+        self.0[1] = mask;
+        self.0[2] = mask;
+        self.0[3] = mask;
+        self.0[4] = mask;
+        self.0[5] = mask;
+        self.0[6] = mask;
+        self.0[7] = mask;
+    }
+
+    fn filled(bit: bool) -> Self {
+        let mut mask = bit as i64;
+
+        mask |= mask << 1;
+        mask |= mask << 2;
+        mask |= mask << 4;
+        mask |= mask << 8;
+        mask |= mask << 16;
+        mask |= mask << 32;
+
+        Mod_e448_Goldilocks([mask; 8])
+    }
+
+    fn nbits() -> usize { 448 }
+
+    fn bit_normalized(&self, idx: usize) -> bool {
+        let byte = idx / 56;
+        let bit = idx % 56;
+
+        (self.0[byte] >> bit) & 0x1 == 0x1
+    }
+
+    fn bit(&mut self, idx: usize) -> bool {
+        self.normalize();
+        self.bit_normalized(idx)
+    }
+
+    fn normalize_bitand(&mut self, rhs: &mut Self) {
+        rhs.normalize();
+        self.normalize_self_bitand(rhs);
+    }
+
+    fn normalize_self_bitand(&mut self, rhs: &Self) {
+        self.normalize();
+        self.normalized_bitand(rhs);
+    }
+
+    fn normalized_bitand(&mut self, rhs: &Self) {
+        self.0[0] &= rhs.0[0];
+        self.0[1] &= rhs.0[1];
+        self.0[2] &= rhs.0[2];
+        self.0[3] &= rhs.0[3];
+        self.0[4] &= rhs.0[4];
+        self.0[5] &= rhs.0[5];
+        self.0[6] &= rhs.0[6];
+        self.0[7] &= rhs.0[7];
+    }
+
+    fn normalize_bitor(&mut self, rhs: &mut Self) {
+        rhs.normalize();
+        self.normalize_self_bitor(rhs);
+    }
+
+    fn normalize_self_bitor(&mut self, rhs: &Self) {
+        self.normalize();
+        self.normalized_bitor(rhs);
+    }
+
+    fn normalized_bitor(&mut self, rhs: &Self) {
+        self.0[0] |= rhs.0[0];
+        self.0[1] |= rhs.0[1];
+        self.0[2] |= rhs.0[2];
+        self.0[3] |= rhs.0[3];
+        self.0[4] |= rhs.0[4];
+        self.0[5] |= rhs.0[5];
+        self.0[6] |= rhs.0[6];
+        self.0[7] |= rhs.0[7];
+    }
+
+    fn zero() -> Mod_e448_Goldilocks {
+        return ZERO;
+    }
+
+    fn one() -> Mod_e448_Goldilocks {
+        return ONE;
+    }
+
+    fn m_one() -> Mod_e448_Goldilocks {
+        return M_ONE;
+    }
+
+    fn modulus() -> Mod_e448_Goldilocks {
+        return MODULUS;
+    }
+
+    fn square(&mut self) {
+        let cpy = self.clone();
+
+        *self *= &cpy;
+    }
+
+    fn squared(&self) -> Self {
+        let mut out = self.clone();
+
+        out.square();
+
+        out
+    }
+
+    fn invert(&mut self) {
+        // The exponent is p - 2 = 2^448 - 2^224 - 3.
+
+        // First digit is 1.
+        let mut sqval = self.clone();
+
+        // Second digit is 0.
+        sqval.square();
+
+        // Digits 2 through 223 are 1.
+        for _ in 2..224 {
+            sqval.square();
+            *self *= &sqval;
+        }
+
+        // Digit 224 is 0.
+        sqval.square();
+
+        // All the remaining digits are 1.
+        for _ in 225..448 {
+            sqval.square();
+            *self *= &sqval;
+        }
+    }
+
+    fn inverted(&self) -> Self {
+        let mut out = self.clone();
+
+        out.invert();
+
+        out
+    }
+
+    fn legendre(&self) -> Self {
+        // The exponent is (p - 1) / 2 = 2^447 - 2^223 - 1.
+
+        // Digits 0 through 222 are 1.
+        let mut out = self.clone();
+        let mut sqval = out.clone();
+
+        for _ in 1..223 {
+            sqval.square();
+            out *= &sqval;
+        }
+
+        // Digit 223 is 0.
+        sqval.square();
+
+        // All the remaining digits are 1.
+        for _ in 224..447 {
+            sqval.square();
+            out *= &sqval;
+        }
+
+        out
+    }
+
+    fn sqrt(&self) -> Self {
+        // Since p = 3 mod 4, the root is self^((p + 1) / 4), and the
+        // exponent is 2^446 - 2^222.
+
+        // Digits 0 through 221 are 0.
+        let mut sqval = self.clone();
+
+        for _ in 0..222 {
+            sqval.square();
+        }
+
+        // All the remaining digits are 1.
+        let mut out = sqval.clone();
+
+        for _ in 223..446 {
+            sqval.square();
+            out *= &sqval;
+        }
+
+        out
+    }
+
+    fn small_add_assign(&mut self, rhs: i32) {
+        let a0 = self.0[0];
+        let a1 = self.0[1];
+        let a2 = self.0[2];
+        let a3 = self.0[3];
+        let a4 = self.0[4];
+        let a5 = self.0[5];
+        let a6 = self.0[6];
+        let a7 = self.0[7] & 0x00ffffffffffffff;
+
+        let b = i64::from(rhs);
+
+        let cin = self.carry_out();
+        let s0 = a0 + b + cin;
+        let c0 = s0 >> 56;
+        let s1 = a1 + c0;
+        let c1 = s1 >> 56;
+        let s2 = a2 + c1;
+        let c2 = s2 >> 56;
+        let s3 = a3 + c2;
+        let c3 = s3 >> 56;
+        let s4 = a4 + cin + c3;
+        let c4 = s4 >> 56;
+        let s5 = a5 + c4;
+        let c5 = s5 >> 56;
+        let s6 = a6 + c5;
+        let c6 = s6 >> 56;
+        let s7 = a7 + c6;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6 & 0x00ffffffffffffff;
+        self.0[7] = s7;
+
+        self.fix_sign();
+    }
+
+    fn small_add(&self, rhs: i32) -> Mod_e448_Goldilocks {
+        let mut out = self.clone();
+
+        out.small_add_assign(rhs);
+
+        out
+    }
+
+    fn small_sub_assign(&mut self, rhs: i32) {
+        let a0 = self.0[0];
+        let a1 = self.0[1];
+        let a2 = self.0[2];
+        let a3 = self.0[3];
+        let a4 = self.0[4];
+        let a5 = self.0[5];
+        let a6 = self.0[6];
+        let a7 = self.0[7] & 0x00ffffffffffffff;
+
+        let b = i64::from(rhs);
+
+        let cin = self.carry_out();
+        let s0 = a0 - b + cin;
+        let c0 = s0 >> 56;
+        let s1 = a1 + c0;
+        let c1 = s1 >> 56;
+        let s2 = a2 + c1;
+        let c2 = s2 >> 56;
+        let s3 = a3 + c2;
+        let c3 = s3 >> 56;
+        let s4 = a4 + cin + c3;
+        let c4 = s4 >> 56;
+        let s5 = a5 + c4;
+        let c5 = s5 >> 56;
+        let s6 = a6 + c5;
+        let c6 = s6 >> 56;
+        let s7 = a7 + c6;
+
+        self.0[0] = s0 & 0x00ffffffffffffff;
+        self.0[1] = s1 & 0x00ffffffffffffff;
+        self.0[2] = s2 & 0x00ffffffffffffff;
+        self.0[3] = s3 & 0x00ffffffffffffff;
+        self.0[4] = s4 & 0x00ffffffffffffff;
+        self.0[5] = s5 & 0x00ffffffffffffff;
+        self.0[6] = s6 & 0x00ffffffffffffff;
+        self.0[7] = s7;
+
+        self.fix_sign();
+    }
+
+    fn small_sub(&self, rhs: i32) -> Mod_e448_Goldilocks {
+        let mut out = self.clone();
+
+        out.small_sub_assign(rhs);
+
+        out
+    }
+
+    fn small_mul_assign(&mut self, rhs: i32) {
+        let mut d = self.digits();
+        let b = i64::from(rhs);
+
+        for i in 0..16 {
+            d[i] *= b;
+        }
+
+        *self = Mod_e448_Goldilocks::from_digits(&d);
+    }
+
+    fn small_mul(&self, b: i32) -> Mod_e448_Goldilocks {
+        let mut out = self.clone();
+
+        out.small_mul_assign(b);
+
+        out
+    }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        let ndigits = (bytes.len() + 55) / 56;
+        let mut buf = [0u8; 56];
+        let mut out = ZERO;
+
+        // Read the input as digits in base 2^448, and evaluate it
+        // by Horner's rule, using 2^448 = phi + 1 mod p.
+        for j in 0..ndigits {
+            let base = (ndigits - 1 - j) * 56;
+
+            for i in 0..56 {
+                buf[i] = bytes.get(base + i).map_or(0, |b| *b);
+            }
+
+            out = (out * PHI) + out + Mod_e448_Goldilocks::unpacked(&buf);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use choice::*;
+    use error::Error;
+    use field::prime_field::*;
+    use field::mod_e448_goldilocks::*;
+    use rand::thread_rng;
+
+    const TWO: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x2, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_TWO: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00fffffffffffffd, 0x00ffffffffffffff,
+                              0x00ffffffffffffff, 0x00ffffffffffffff,
+                              0x00fffffffffffffe, 0x00ffffffffffffff,
+                              0x00ffffffffffffff, 0x00ffffffffffffff ]);
+
+    const THREE: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x3, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const FOUR: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x4, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_FOUR: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00fffffffffffffb, 0x00ffffffffffffff,
+                              0x00ffffffffffffff, 0x00ffffffffffffff,
+                              0x00fffffffffffffe, 0x00ffffffffffffff,
+                              0x00ffffffffffffff, 0x00ffffffffffffff ]);
+
+    const SIX: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x6, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_SIX: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00fffffffffffff9, 0x00ffffffffffffff,
+                              0x00ffffffffffffff, 0x00ffffffffffffff,
+                              0x00fffffffffffffe, 0x00ffffffffffffff,
+                              0x00ffffffffffffff, 0x00ffffffffffffff ]);
+
+    const NINE: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x9, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const A: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00c32fec47c6b78b, 0x0006871e33c6bd3f,
+                              0x00194b445712f284, 0x00007cbae22854c4,
+                              0x00699d7bce2c3243, 0x0098968cb9e84695,
+                              0x00406d736e23586d, 0x00aee356f2e7f309 ]);
+
+    const B: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00f9ce4c1e09e2d4, 0x0031d29ee1faf4d4,
+                              0x00a6c7fc64c13156, 0x00f6c522caee1a36,
+                              0x00e330b1821b4a5b, 0x0008de500b9ac958,
+                              0x00245ad29d36b71e, 0x00d035540156a420 ]);
+
+    const A_PLUS_B: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00bcfe3865d09a60, 0x003859bd15c1b214,
+                              0x00c01340bbd423da, 0x00f741ddad166efa,
+                              0x004cce2d50477c9f, 0x00a174dcc5830fee,
+                              0x0064c8460b5a0f8b, 0x007f18aaf43e9729 ]);
+
+    const A_MINUS_B: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00c961a029bcd4b6, 0x00d4b47f51cbc86a,
+                              0x00728347f251c12d, 0x0009b798173a3a8d,
+                              0x00866cca4c10e7e6, 0x008fb83cae4d7d3c,
+                              0x001c12a0d0eca14f, 0x00deae02f1914ee9 ]);
+
+    const A_TIMES_B: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00657a552c4cd20d, 0x001cf0e7e06d1c44,
+                              0x0060aea794f8e503, 0x009f0fdfd3d09e32,
+                              0x00bf0561edcf479a, 0x00e7596275973f78,
+                              0x00519a7b7ef3d80f, 0x00a6fb1c47c2f525 ]);
+
+    const A_INV: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x005bb03fab8403b1, 0x0099c4d17ee4d740,
+                              0x006c6d6a19ec0ca7, 0x00c59eac75c6e3ee,
+                              0x0035975e6321094b, 0x0053b54632d19d2e,
+                              0x00ca5149273fc704, 0x005cf53b91ba61b5 ]);
+
+    const A_SQUARED: Mod_e448_Goldilocks =
+        Mod_e448_Goldilocks([ 0x00979eafbeb39907, 0x0014202c8a1e0326,
+                              0x00d4c2685ab11ef7, 0x008e5ee8863d61cd,
+                              0x0072d3ea8a814470, 0x004eb54b6802a027,
+                              0x0068228e6e4133ed, 0x006507ed757de24a ]);
+
+    fn test_pack_unpack(expected: &[u8; 56]) {
+        let mut unpacked = Mod_e448_Goldilocks::unpacked(expected);
+        let actual = unpacked.packed();
+
+        for i in 0..56 {
+            assert!(expected[i] == actual[i]);
+        }
+    }
+
+    fn test_unpack_pack(expected: &mut Mod_e448_Goldilocks) {
+        let bytes = expected.packed();
+        let mut actual = Mod_e448_Goldilocks::unpacked(&bytes);
+
+        assert!(expected.normalize_eq(&mut actual));
+    }
+
+    #[test]
+    fn pack_unpack_test() {
+        let mut alt = [0u8; 56];
+        let mut ramp = [0u8; 56];
+
+        for i in 0..56 {
+            alt[i] = if i % 2 == 0 { 0xff } else { 0x00 };
+            ramp[i] = i as u8;
+        }
+
+        test_pack_unpack(&alt);
+        test_pack_unpack(&ramp);
+        test_pack_unpack(&[0u8; 56]);
+    }
+
+    #[test]
+    fn unpack_pack_test() {
+        test_unpack_pack(&mut ZERO.clone());
+        test_unpack_pack(&mut ONE.clone());
+        test_unpack_pack(&mut M_ONE.clone());
+        test_unpack_pack(&mut A.clone());
+        test_unpack_pack(&mut B.clone());
+    }
+
+    #[test]
+    fn test_add() {
+        let l1_zeros: [&mut Mod_e448_Goldilocks; 4] = [ &mut (&ZERO + &ZERO),
+                                        &mut (&M_ONE + &ONE),
+                                        &mut (&M_TWO + &TWO),
+                                        &mut (&MODULUS + &ZERO) ];
+
+        let l1_ones: [&mut Mod_e448_Goldilocks; 3] = [ &mut (&ZERO + &ONE),
+                                       &mut (&M_ONE + &TWO),
+                                       &mut (&MODULUS + &ONE) ];
+
+        let l1_mtwos: [&mut Mod_e448_Goldilocks; 2] = [ &mut (&M_ONE + &M_ONE),
+                                        &mut (&M_FOUR + &TWO) ];
+
+        for i in 0..4 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(ONE.normalize_eq(l1_ones[i]));
+        }
+
+        for i in 0..2 {
+            assert!(M_TWO.normalize_eq(l1_mtwos[i]));
+        }
+
+        assert!(A_PLUS_B.normalize_eq(&mut (&A + &B)));
+        assert!(A_PLUS_B.normalize_eq(&mut (&B + &A)));
+    }
+
+    #[test]
+    fn test_sub() {
+        let l1_zeros: [&mut Mod_e448_Goldilocks; 4] = [ &mut (&ZERO - &ZERO),
+                                        &mut (&ONE - &ONE),
+                                        &mut (&M_TWO - &M_TWO),
+                                        &mut (&A - &A) ];
+
+        let l1_mones: [&mut Mod_e448_Goldilocks; 3] = [ &mut (&ZERO - &ONE),
+                                        &mut (&ONE - &TWO),
+                                        &mut (&M_TWO - &M_ONE) ];
+
+        for i in 0..4 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(M_ONE.normalize_eq(l1_mones[i]));
+        }
+
+        assert!(A_MINUS_B.normalize_eq(&mut (&A - &B)));
+        assert!(A.normalize_eq(&mut (&A_PLUS_B - &B)));
+    }
+
+    #[test]
+    fn test_mul() {
+        let l1_sixes: [&mut Mod_e448_Goldilocks; 4] = [ &mut (&TWO * &THREE),
+                                        &mut (&M_TWO * &(&ZERO - &THREE)),
+                                        &mut (&M_ONE * &M_SIX),
+                                        &mut (&(&A * &SIX) * &A_INV) ];
+
+        for i in 0..4 {
+            assert!(SIX.normalize_eq(l1_sixes[i]));
+        }
+
+        assert!(ZERO.normalize_eq(&mut (&A * &ZERO)));
+        assert!(A.normalize_eq(&mut (&A * &ONE)));
+        assert!(A_TIMES_B.normalize_eq(&mut (&A * &B)));
+        assert!(A_TIMES_B.normalize_eq(&mut (&B * &A)));
+        assert!(A_SQUARED.normalize_eq(&mut A.squared()));
+        assert!(ONE.normalize_eq(&mut (&M_ONE * &M_ONE)));
+    }
+
+    #[test]
+    fn test_mul_lazy() {
+        let mut sum = ZERO;
+        let mut expected = A_TIMES_B.small_mul(7);
+
+        // Leave carries in the representation before multiplying.
+        for _ in 0..7 {
+            sum += &A;
+        }
+
+        assert!(expected.normalize_eq(&mut (&sum * &B)));
+        assert!(expected.normalize_eq(&mut (&B * &sum)));
+    }
+
+    #[test]
+    fn test_distributive() {
+        let mut rng = thread_rng();
+
+        for _ in 0..100 {
+            let a = Mod_e448_Goldilocks::rand(&mut rng);
+            let b = Mod_e448_Goldilocks::rand(&mut rng);
+            let c = Mod_e448_Goldilocks::rand(&mut rng);
+            let mut lhs = &(&a + &b) * &c;
+            let mut rhs = &(&a * &c) + &(&b * &c);
+
+            assert!(lhs.normalize_eq(&mut rhs));
+        }
+    }
+
+    #[test]
+    fn test_inv() {
+        assert!(A_INV.normalize_eq(&mut A.inverted()));
+        assert!(ONE.normalize_eq(&mut ONE.inverted()));
+        assert!(M_ONE.normalize_eq(&mut M_ONE.inverted()));
+        assert!(ONE.normalize_eq(&mut (&TWO * &TWO.inverted())));
+        assert!(ONE.normalize_eq(&mut (&B / &B)));
+        assert!(THREE.normalize_eq(&mut (&SIX / &TWO)));
+    }
+
+    #[test]
+    fn test_legendre() {
+        assert!(ONE.normalize_eq(&mut FOUR.legendre()));
+        assert!(ONE.normalize_eq(&mut NINE.legendre()));
+        assert!(ONE.normalize_eq(&mut A_SQUARED.legendre()));
+        assert!(ONE.normalize_eq(&mut TWO.legendre()));
+        assert!(M_ONE.normalize_eq(&mut M_ONE.legendre()));
+        assert!(M_ONE.normalize_eq(&mut M_FOUR.legendre()));
+        assert!(ZERO.normalize_eq(&mut ZERO.legendre()));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let mut four_root = FOUR.sqrt();
+        let mut nine_root = NINE.sqrt();
+        let mut a_root = A_SQUARED.sqrt();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(A_SQUARED.normalize_eq(&mut a_root.squared()));
+        assert!(four_root.normalize_eq(&mut TWO.clone()) ||
+                four_root.normalize_eq(&mut M_TWO.clone()));
+        assert!(nine_root.normalize_eq(&mut THREE.clone()) ||
+                nine_root.normalize_eq(&mut -THREE));
+        assert!(a_root.normalize_eq(&mut A.clone()) ||
+                a_root.normalize_eq(&mut -A));
+    }
+
+    #[test]
+    fn test_small_add() {
+        assert!(ZERO.normalize_eq(&mut M_ONE.small_add(1)));
+        assert!(ONE.normalize_eq(&mut M_ONE.small_add(2)));
+        assert!(M_TWO.normalize_eq(&mut ZERO.small_add(-2)));
+        assert!(SIX.normalize_eq(&mut M_TWO.small_add(8)));
+        assert!(A_PLUS_B.normalize_eq(&mut A_PLUS_B.small_add(9)
+                                                   .small_add(-9)));
+    }
+
+    #[test]
+    fn test_small_sub() {
+        assert!(M_ONE.normalize_eq(&mut ZERO.small_sub(1)));
+        assert!(ZERO.normalize_eq(&mut M_ONE.small_sub(-1)));
+        assert!(M_SIX.normalize_eq(&mut TWO.small_sub(8)));
+        assert!(NINE.normalize_eq(&mut THREE.small_sub(-6)));
+    }
+
+    #[test]
+    fn test_small_mul() {
+        assert!(ZERO.normalize_eq(&mut A.small_mul(0)));
+        assert!(M_SIX.normalize_eq(&mut TWO.small_mul(-3)));
+        assert!(SIX.normalize_eq(&mut M_TWO.small_mul(-3)));
+        assert!(M_TWO.normalize_eq(&mut M_ONE.small_mul(2)));
+        assert!(ZERO.small_add(39081)
+                    .normalize_eq(&mut (&A.small_mul(39081) * &A_INV)));
+        assert!((-A).normalize_eq(&mut A.small_mul(-1)));
+    }
+
+    #[test]
+    fn test_neg() {
+        let l1_zeros: [&mut Mod_e448_Goldilocks; 3] = [ &mut -ZERO,
+                                        &mut (&-ONE + &ONE),
+                                        &mut (&-A + &A) ];
+
+        for i in 0..3 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        assert!(M_ONE.normalize_eq(&mut -ONE));
+        assert!(SIX.normalize_eq(&mut -M_SIX));
+        assert!(A.normalize_eq(&mut -(-A)));
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+        let mut a = TWO;
+        let mut b = A;
+        let mut sel_f = Mod_e448_Goldilocks::conditional_select(&TWO, &THREE,
+                                                              f);
+        let mut sel_t = Mod_e448_Goldilocks::conditional_select(&TWO, &THREE,
+                                                              t);
+
+        assert!(TWO.normalize_eq(&mut sel_f));
+        assert!(THREE.normalize_eq(&mut sel_t));
+
+        Mod_e448_Goldilocks::conditional_swap(&mut a, &mut b, f);
+        assert!(TWO.normalize_eq(&mut a));
+        assert!(A.normalize_eq(&mut b));
+
+        Mod_e448_Goldilocks::conditional_swap(&mut a, &mut b, t);
+        assert!(A.normalize_eq(&mut a));
+        assert!(TWO.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_conditional_negate() {
+        let mut a = SIX;
+        let mut b = SIX;
+
+        a.conditional_negate(Choice::from(0));
+        b.conditional_negate(Choice::from(1));
+
+        assert!(SIX.normalize_eq(&mut a));
+        assert!(M_SIX.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(ZERO.ct_eq(&ZERO)));
+        assert!(bool::from(ZERO.ct_eq(&MODULUS)));
+        assert!(bool::from(ZERO.ct_eq(&(M_ONE + ONE))));
+        assert!(bool::from(SIX.ct_eq(&(TWO * THREE))));
+        assert!(bool::from(A_TIMES_B.ct_eq(&(A * B))));
+        assert!(!bool::from(ZERO.ct_eq(&ONE)));
+        assert!(!bool::from(SIX.ct_eq(&M_SIX)));
+        assert!(!bool::from(A.ct_eq(&B)));
+    }
+
+    #[test]
+    fn test_sqrt_checked() {
+        let four_root = FOUR.sqrt_checked().unwrap();
+        let nine_root = NINE.sqrt_checked().unwrap();
+        let mut zero_root = ZERO.sqrt_checked().unwrap();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(ZERO.normalize_eq(&mut zero_root));
+        assert!(bool::from(A_SQUARED.sqrt_checked().is_some()));
+        assert!(bool::from(TWO.sqrt_checked().is_some()));
+
+        // -1 is not a square when p = 3 mod 4.
+        assert!(bool::from(M_ONE.sqrt_checked().is_none()));
+        assert!(bool::from(M_FOUR.sqrt_checked().is_none()));
+    }
+
+    #[test]
+    fn test_invert_checked() {
+        let two_inv = TWO.invert_checked().unwrap();
+        let a_inv = A.invert_checked().unwrap();
+
+        assert!(ONE.normalize_eq(&mut (two_inv * TWO)));
+        assert!(A_INV.normalize_eq(&mut a_inv.clone()));
+        assert!(bool::from(ZERO.invert_checked().is_none()));
+        assert!(bool::from(MODULUS.invert_checked().is_none()));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+        let mut long = [0u8; 57];
+        let mut actual = ZERO;
+
+        let mut unpacked =
+            Mod_e448_Goldilocks::unpacked_checked(&bytes).unwrap();
+
+        assert!(M_ONE.normalize_eq(&mut unpacked));
+        assert!(actual.unpack_checked(&A.packed()).is_ok());
+        assert!(A.normalize_eq(&mut actual));
+        assert!(actual.unpack_checked(&ZERO.packed()).is_ok());
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        // The modulus itself.
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&bytes));
+
+        // Values between the modulus and 2^448.
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&[0xffu8; 56]));
+
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        long[0] = 1;
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long));
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long[0..55]));
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut rng = thread_rng();
+        let mut counts = [0u32; 448];
+
+        // Each bit of a uniform value is set about half the time.
+        for _ in 0..2000 {
+            let mut val = Mod_e448_Goldilocks::rand(&mut rng);
+            let bytes = val.packed();
+
+            assert!(Mod_e448_Goldilocks::unpacked_checked(&bytes).is_ok());
+
+            for i in 0..448 {
+                counts[i] += ((bytes[i / 8] >> (i % 8)) & 0x1) as u32;
+            }
+        }
+
+        for i in 0..448 {
+            assert!(counts[i] > 800 && counts[i] < 1200,
+                    "bit {} set {} times", i, counts[i]);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        const ONES: Mod_e448_Goldilocks =
+            Mod_e448_Goldilocks([ 0x0000000000000001, 0x0000000000000000,
+                                  0x0000000000000000, 0x0000000000000000,
+                                  0x0000000000000003, 0x0000000000000000,
+                                  0x0000000000000000, 0x0000000000000000 ]);
+
+        const SEQ: Mod_e448_Goldilocks =
+            Mod_e448_Goldilocks([ 0x00f98a1aab3bcc5e, 0x00029323b444d566,
+                                  0x000b9c2cbd4dde70, 0x0014a535c656e779,
+                                  0x00137eea55c12c9a, 0x001f8af661cd38a5,
+                                  0x002b97026dd944b1, 0x0037a30e79e550bd ]);
+
+        let mut bytes = [0u8; 112];
+        let mut out;
+
+        for i in 0..112 {
+            bytes[i] = ((i * 37) + 11) as u8;
+        }
+
+        out = Mod_e448_Goldilocks::from_bytes_wide(&bytes);
+        assert!(SEQ.normalize_eq(&mut out));
+        out = Mod_e448_Goldilocks::from_bytes_wide(&[0xff; 112]);
+        assert!(ONES.normalize_eq(&mut out));
+
+        // Values that fit in the field are unchanged.
+        bytes = [0u8; 112];
+        bytes[0..56].copy_from_slice(&M_ONE.packed());
+        out = Mod_e448_Goldilocks::from_bytes_wide(&bytes);
+        assert!(M_ONE.normalize_eq(&mut out));
+
+        bytes[0] += 1;
+        out = Mod_e448_Goldilocks::from_bytes_wide(&bytes);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e448_Goldilocks::from_bytes_wide(&[]);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e448_Goldilocks::from_bytes_wide(&[2]);
+        assert!(TWO.normalize_eq(&mut out));
+    }
+}
@@ -1,3 +1,9 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_i64;
+use error::Error;
 use field::prime_field::PrimeField;
 use field::prime_field::PrimeFieldMask;
 use normalize::Normalize;
@@ -90,13 +96,24 @@ pub const M511_BASE_Y: Mod_e511_187 =
                    0x002399ac1cf8f6e0, 0x002eb4d52ed2234c,
                    0x002d8530803d28fd, 0x0000000000bef702 ]);
 
-/// The prime subgroup order of the curve M-511, 2^508 +
-/// 10724754759635747624044531514068121842070756627434833028965540808827675062043.
-pub const M511_ORDER: Mod_e511_187 =
-    Mod_e511_187([ 0x000c15bab76baf1b, 0x00010a9aa842fe95,
-                   0x003d13779a2ac125, 0x0031fd59deacabba,
-                   0x00000017b5feff30, 0, 0, 0, 0,
-                   0x0000000000400000 ]);
+/// The A value 530438 in the M-511 curve equation y^2 = x^3 + Ax^2 + x.
+pub const M511_A: Mod_e511_187 =
+    Mod_e511_187([ 0x81806, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The value (A + 2) / 4 = 132610 for the M-511 curve equation
+/// y^2 = x^3 + Ax^2 + x, used by the Montgomery ladder.
+pub const M511_A24: Mod_e511_187 =
+    Mod_e511_187([ 0x20602, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The x-coordinate of the base point of the curve M-511 plus a
+/// point of order 8, which generates the whole group,
+/// 0x5959866465dea23e929e3c436bda4442e93804c493cd91f79cb4b988e41e8a67baa5a67fef8424ea4242ebeec23cec8d5d29b1adceb7360fb868359d92b2fb0.
+pub const M511_DIRTY_BASE_X: Mod_e511_187 =
+    Mod_e511_187([ 0x00068359d92b2fb0, 0x002c6b73adcd83ee,
+                   0x002ec23cec8d5d29, 0x003e1093a9090baf,
+                   0x0028a67baa5a67fe, 0x003de72d2e623907,
+                   0x00293804c493cd91, 0x0038f10daf69110b,
+                   0x0026465dea23e929, 0x0000000000165661 ]);
 
 impl Debug for Mod_e511_187 {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -144,6 +161,44 @@ impl Mod_e511_187 {
         self.0[9] >> 25
     }
 
+    /// Add 2p to a value whose representation has gone negative.
+    /// Multiplication assumes that every digit is non-negative, so
+    /// this must follow any operation that can borrow out of the
+    /// highest digit.
+    fn fix_sign(&mut self) {
+        let m = self.0[9] >> 63;
+        let s0 = self.0[0] + ((MODULUS.0[0] << 1) & m);
+        let c0 = s0 >> 54;
+        let s1 = self.0[1] + ((MODULUS.0[1] << 1) & m) + c0;
+        let c1 = s1 >> 54;
+        let s2 = self.0[2] + ((MODULUS.0[2] << 1) & m) + c1;
+        let c2 = s2 >> 54;
+        let s3 = self.0[3] + ((MODULUS.0[3] << 1) & m) + c2;
+        let c3 = s3 >> 54;
+        let s4 = self.0[4] + ((MODULUS.0[4] << 1) & m) + c3;
+        let c4 = s4 >> 54;
+        let s5 = self.0[5] + ((MODULUS.0[5] << 1) & m) + c4;
+        let c5 = s5 >> 54;
+        let s6 = self.0[6] + ((MODULUS.0[6] << 1) & m) + c5;
+        let c6 = s6 >> 54;
+        let s7 = self.0[7] + ((MODULUS.0[7] << 1) & m) + c6;
+        let c7 = s7 >> 54;
+        let s8 = self.0[8] + ((MODULUS.0[8] << 1) & m) + c7;
+        let c8 = s8 >> 54;
+        let s9 = self.0[9] + ((MODULUS.0[9] << 1) & m) + c8;
+
+        self.0[0] = s0 & 0x003fffffffffffff;
+        self.0[1] = s1 & 0x003fffffffffffff;
+        self.0[2] = s2 & 0x003fffffffffffff;
+        self.0[3] = s3 & 0x003fffffffffffff;
+        self.0[4] = s4 & 0x003fffffffffffff;
+        self.0[5] = s5 & 0x003fffffffffffff;
+        self.0[6] = s6 & 0x003fffffffffffff;
+        self.0[7] = s7 & 0x003fffffffffffff;
+        self.0[8] = s8 & 0x003fffffffffffff;
+        self.0[9] = s9;
+    }
+
     /// Serialize a value as a little-endian byte array.  This has the
     /// effect of normalizing the representation.
     pub fn packed(&mut self) -> [u8; 64] {
@@ -183,9 +238,9 @@ impl Neg for Mod_e511_187 {
     type Output = Mod_e511_187;
 
     fn neg(self) -> Mod_e511_187 {
-        let mut out = self;
+        let mut out = ZERO;
 
-        out += &MODULUS;
+        out -= &self;
         out
     }
 }
@@ -416,7 +471,7 @@ impl<'b> SubAssign<&'b Mod_e511_187> for Mod_e511_187 {
         let b8 = rhs.0[8];
         let b9 = rhs.0[9] & 0x0000000001ffffff;
 
-        let cin = self.carry_out() + rhs.carry_out();
+        let cin = self.carry_out() - rhs.carry_out();
         let s0 = a0 - b0 + (cin * C_VAL);
         let c0 = s0 >> 54;
         let s1 = a1 - b1 + c0;
@@ -447,6 +502,8 @@ impl<'b> SubAssign<&'b Mod_e511_187> for Mod_e511_187 {
         self.0[7] = s7 & 0x003fffffffffffff;
         self.0[8] = s8 & 0x003fffffffffffff;
         self.0[9] = s9;
+
+        self.fix_sign();
     }
 }
 
@@ -1366,13 +1423,20 @@ impl Mul<Mod_e511_187> for Mod_e511_187 {
 
 impl Rand for Mod_e511_187 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        let mut out = Mod_e511_187([0i64; 10]);
+        let mut bytes = [0u8; 64];
+        let mut out = ZERO;
 
-        for i in 0..10 {
-            out.0[i] = rng.gen_range(0, MODULUS.0[i]);
-        }
+        // Rejection sampling: draw 511-bit values until one is less
+        // than the modulus.  This is uniform, and almost never
+        // takes more than one try.
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[63] &= 0x7f;
 
-        out
+            if out.unpack_checked(&bytes).is_ok() {
+                return out;
+            }
+        }
     }
 }
 
@@ -1457,6 +1521,38 @@ impl Pack for Mod_e511_187 {
         out
     }
 
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 64 {
+            return Err(Error::InvalidLength);
+        }
+
+        if bytes[63] & 0x80 != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        // Adding C carries out of the top digit exactly when the
+        // value is at least the modulus.
+        if (&out + (C_VAL as i32)).carry_out() != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        *self = out;
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
     fn pack(&mut self, bytes: &mut [u8]) {
         self.normalize();
         self.pack_normalized(bytes)
@@ -1612,6 +1708,47 @@ impl PrimeFieldMask for Mod_e511_187_Mask {
     }
 }
 
+impl ConditionallySelectable for Mod_e511_187 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = Mod_e511_187_Mask::filled(choice.into());
+
+        Mod_e511_187([ a.0[0] ^ ((a.0[0] ^ b.0[0]) & mask.0[0]),
+                       a.0[1] ^ ((a.0[1] ^ b.0[1]) & mask.0[1]),
+                       a.0[2] ^ ((a.0[2] ^ b.0[2]) & mask.0[2]),
+                       a.0[3] ^ ((a.0[3] ^ b.0[3]) & mask.0[3]),
+                       a.0[4] ^ ((a.0[4] ^ b.0[4]) & mask.0[4]),
+                       a.0[5] ^ ((a.0[5] ^ b.0[5]) & mask.0[5]),
+                       a.0[6] ^ ((a.0[6] ^ b.0[6]) & mask.0[6]),
+                       a.0[7] ^ ((a.0[7] ^ b.0[7]) & mask.0[7]),
+                       a.0[8] ^ ((a.0[8] ^ b.0[8]) & mask.0[8]),
+                       a.0[9] ^ ((a.0[9] ^ b.0[9]) & mask.0[9]) ])
+    }
+}
+
+impl ConditionallyNegatable for Mod_e511_187 {
+    fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -*self;
+
+        self.conditional_assign(&neg, choice);
+    }
+}
+
+impl ConstantTimeEq for Mod_e511_187 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.normalize();
+        b.normalize();
+
+        is_zero_i64((a.0[0] ^ b.0[0]) | (a.0[1] ^ b.0[1]) |
+                    (a.0[2] ^ b.0[2]) | (a.0[3] ^ b.0[3]) |
+                    (a.0[4] ^ b.0[4]) | (a.0[5] ^ b.0[5]) |
+                    (a.0[6] ^ b.0[6]) | (a.0[7] ^ b.0[7]) |
+                    (a.0[8] ^ b.0[8]) | (a.0[9] ^ b.0[9]))
+    }
+}
+
 impl PrimeField for Mod_e511_187 {
     fn fill(&mut self, bit: bool) {
         let mut mask = bit as i64;
@@ -2561,6 +2698,8 @@ impl PrimeField for Mod_e511_187 {
         self.0[7] = s7 & 0x003fffffffffffff;
         self.0[8] = s8 & 0x003fffffffffffff;
         self.0[9] = s9;
+
+        self.fix_sign();
     }
 
     fn small_add(&self, rhs: i32) -> Mod_e511_187 {
@@ -2616,6 +2755,8 @@ impl PrimeField for Mod_e511_187 {
         self.0[7] = s7 & 0x003fffffffffffff;
         self.0[8] = s8 & 0x003fffffffffffff;
         self.0[9] = s9;
+
+        self.fix_sign();
     }
 
     fn small_sub(&self, rhs: i32) -> Mod_e511_187 {
@@ -2645,7 +2786,7 @@ impl PrimeField for Mod_e511_187 {
         let a15 = self.0[7] >> 27;
         let a16 = self.0[8] & 0x07ffffff;
         let a17 = self.0[8] >> 27;
-        let a18 = self.0[9];
+        let a18 = self.0[9] & 0x0000000001ffffff;
 
         let b = i64::from(rhs);
 
@@ -2670,7 +2811,7 @@ impl PrimeField for Mod_e511_187 {
         let m18 = a18 * b;
 
         let cin = self.carry_out();
-        let d0 = m0 + ((m1 & 0x07ffffff) << 27) + (cin * C_VAL);
+        let d0 = m0 + ((m1 & 0x07ffffff) << 27) + (cin * C_VAL * b);
         let c0 = d0 >> 54;
         let d1 = (m1 >> 27) + m2 + ((m3 & 0x07ffffff) << 27) + c0;
         let c1 = d1 >> 54;
@@ -2690,16 +2831,40 @@ impl PrimeField for Mod_e511_187 {
         let c8 = d8 >> 54;
         let d9 = (m17 >> 27) + m18 + c8;
 
-        self.0[0] = d0 & 0x003fffffffffffff;
-        self.0[1] = d1 & 0x003fffffffffffff;
-        self.0[2] = d2 & 0x003fffffffffffff;
-        self.0[3] = d3 & 0x003fffffffffffff;
-        self.0[4] = d4 & 0x003fffffffffffff;
-        self.0[5] = d5 & 0x003fffffffffffff;
-        self.0[6] = d6 & 0x003fffffffffffff;
-        self.0[7] = d7 & 0x003fffffffffffff;
-        self.0[8] = d8 & 0x003fffffffffffff;
-        self.0[9] = d9;
+        // Fold everything above bit 511 back in, multiplied by C.
+        let over = d9 >> 25;
+        let s0 = (d0 & 0x003fffffffffffff) + (over * C_VAL);
+        let k0 = s0 >> 54;
+        let s1 = (d1 & 0x003fffffffffffff) + k0;
+        let k1 = s1 >> 54;
+        let s2 = (d2 & 0x003fffffffffffff) + k1;
+        let k2 = s2 >> 54;
+        let s3 = (d3 & 0x003fffffffffffff) + k2;
+        let k3 = s3 >> 54;
+        let s4 = (d4 & 0x003fffffffffffff) + k3;
+        let k4 = s4 >> 54;
+        let s5 = (d5 & 0x003fffffffffffff) + k4;
+        let k5 = s5 >> 54;
+        let s6 = (d6 & 0x003fffffffffffff) + k5;
+        let k6 = s6 >> 54;
+        let s7 = (d7 & 0x003fffffffffffff) + k6;
+        let k7 = s7 >> 54;
+        let s8 = (d8 & 0x003fffffffffffff) + k7;
+        let k8 = s8 >> 54;
+        let s9 = (d9 & 0x0000000001ffffff) + k8;
+
+        self.0[0] = s0 & 0x003fffffffffffff;
+        self.0[1] = s1 & 0x003fffffffffffff;
+        self.0[2] = s2 & 0x003fffffffffffff;
+        self.0[3] = s3 & 0x003fffffffffffff;
+        self.0[4] = s4 & 0x003fffffffffffff;
+        self.0[5] = s5 & 0x003fffffffffffff;
+        self.0[6] = s6 & 0x003fffffffffffff;
+        self.0[7] = s7 & 0x003fffffffffffff;
+        self.0[8] = s8 & 0x003fffffffffffff;
+        self.0[9] = s9;
+
+        self.fix_sign();
     }
 
     fn small_mul(&self, b: i32) -> Mod_e511_187 {
@@ -2709,12 +2874,40 @@ impl PrimeField for Mod_e511_187 {
 
         out
     }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        let ndigits = (8 * bytes.len() + 510) / 511;
+        let mut buf = [0u8; 64];
+        let mut out = ZERO;
+
+        // Read the input as digits in base 2^511, and evaluate it
+        // by Horner's rule, using 2^511 = 187 mod p.
+        for j in 0..ndigits {
+            let base = (ndigits - 1 - j) * 511;
+
+            for i in 0..64 {
+                let off = base + (8 * i);
+                let lo = bytes.get(off / 8).map_or(0, |b| *b as u16);
+                let hi = bytes.get((off / 8) + 1).map_or(0, |b| *b as u16);
+
+                buf[i] = (((hi << 8) | lo) >> (off % 8)) as u8;
+            }
+
+            buf[63] &= 0x7f;
+            out = out.small_mul(C_VAL as i32) + Mod_e511_187::unpacked(&buf);
+        }
+
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use choice::*;
+    use error::Error;
     use field::prime_field::*;
     use field::mod_e511_187::*;
+    use rand::thread_rng;
 
     const TWO: Mod_e511_187 = Mod_e511_187([ 2, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
 
@@ -4837,4 +5030,201 @@ mod tests {
             assert!(M_FOUR.normalize_eq(&mut val));
         }
     }
+
+    #[test]
+    fn test_neg() {
+        let l1_zeros: [&mut Mod_e511_187; 3] = [ &mut -ZERO,
+                                          &mut (&-ONE + &ONE),
+                                          &mut (&-M_TWO + &M_TWO) ];
+
+        let l1_mones: [&mut Mod_e511_187; 3] = [ &mut -ONE,
+                                          &mut -(&TWO - &ONE),
+                                          &mut (&-TWO + &ONE) ];
+
+        let l1_sixes: [&mut Mod_e511_187; 4] = [ &mut -M_SIX,
+                                          &mut (&-TWO * &M_THREE),
+                                          &mut (&(&M_ONE - &ONE) * &-THREE),
+                                          &mut (&(&ZERO - &TWO) -
+                                                &(&(&ONE - &NINE) * &ONE)) ];
+
+        for i in 0..3 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(M_ONE.normalize_eq(l1_mones[i]));
+        }
+
+        for i in 0..4 {
+            assert!(SIX.normalize_eq(l1_sixes[i]));
+        }
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+        let mut a = TWO;
+        let mut b = M_THREE;
+        let mut sel_f = Mod_e511_187::conditional_select(&TWO, &THREE, f);
+        let mut sel_t = Mod_e511_187::conditional_select(&TWO, &THREE, t);
+
+        assert!(TWO.normalize_eq(&mut sel_f));
+        assert!(THREE.normalize_eq(&mut sel_t));
+
+        Mod_e511_187::conditional_swap(&mut a, &mut b, f);
+        assert!(TWO.normalize_eq(&mut a));
+        assert!(M_THREE.normalize_eq(&mut b));
+
+        Mod_e511_187::conditional_swap(&mut a, &mut b, t);
+        assert!(M_THREE.normalize_eq(&mut a));
+        assert!(TWO.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_conditional_negate() {
+        let mut a = SIX;
+        let mut b = SIX;
+
+        a.conditional_negate(Choice::from(0));
+        b.conditional_negate(Choice::from(1));
+
+        assert!(SIX.normalize_eq(&mut a));
+        assert!(M_SIX.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(ZERO.ct_eq(&ZERO)));
+        assert!(bool::from(ZERO.ct_eq(&MODULUS)));
+        assert!(bool::from(ZERO.ct_eq(&(M_ONE + ONE))));
+        assert!(bool::from(SIX.ct_eq(&(TWO * THREE))));
+        assert!(bool::from(M_SIX.ct_eq(&(M_TWO * THREE))));
+        assert!(!bool::from(ZERO.ct_eq(&ONE)));
+        assert!(!bool::from(SIX.ct_eq(&M_SIX)));
+        assert!(!bool::from(TWO.ct_eq(&THREE)));
+    }
+
+    #[test]
+    fn test_sqrt_checked() {
+        let four_root = FOUR.sqrt_checked().unwrap();
+        let nine_root = NINE.sqrt_checked().unwrap();
+        let mut zero_root = ZERO.sqrt_checked().unwrap();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(ZERO.normalize_eq(&mut zero_root));
+        assert!(bool::from(SIXTEEN.sqrt_checked().is_some()));
+        assert!(bool::from(TWO.sqrt_checked().is_none()));
+        assert!(bool::from(M_TWO.sqrt_checked().is_none()));
+
+        // -1 is a square when p = 5 mod 8.
+        let m_one_root = M_ONE.sqrt_checked().unwrap();
+
+        assert!(M_ONE.normalize_eq(&mut m_one_root.squared()));
+    }
+
+    #[test]
+    fn test_invert_checked() {
+        let two_inv = TWO.invert_checked().unwrap();
+        let m_six_inv = M_SIX.invert_checked().unwrap();
+
+        assert!(ONE.normalize_eq(&mut (two_inv * TWO)));
+        assert!(ONE.normalize_eq(&mut (m_six_inv * M_SIX)));
+        assert!(bool::from(ZERO.invert_checked().is_none()));
+        assert!(bool::from(MODULUS.invert_checked().is_none()));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+        let mut long = [0u8; 65];
+        let mut actual = ZERO;
+
+        assert!(M_ONE.normalize_eq(&mut Mod_e511_187::unpacked_checked(&bytes)
+                                            .unwrap()));
+        assert!(actual.unpack_checked(&TWO.packed()).is_ok());
+        assert!(TWO.normalize_eq(&mut actual));
+        assert!(actual.unpack_checked(&ZERO.packed()).is_ok());
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        // The modulus itself.
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&bytes));
+
+        // Bits above the modulus.
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&[0xffu8; 64]));
+
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        long[0] = 1;
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long));
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long[0..63]));
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut rng = thread_rng();
+        let mut counts = [0u32; 511];
+
+        // Each bit of a uniform value is set about half the time.
+        for _ in 0..2000 {
+            let mut val = Mod_e511_187::rand(&mut rng);
+            let bytes = val.packed();
+
+            assert!(Mod_e511_187::unpacked_checked(&bytes).is_ok());
+
+            for i in 0..511 {
+                counts[i] += ((bytes[i / 8] >> (i % 8)) & 0x1) as u32;
+            }
+        }
+
+        for i in 0..511 {
+            assert!(counts[i] > 800 && counts[i] < 1200,
+                    "bit {} set {} times", i, counts[i]);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        const ONES: Mod_e511_187 =
+            Mod_e511_187([ 0x22263, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+        const SEQ: Mod_e511_187 =
+            Mod_e511_187([ 0x0016e37a10a81806, 0x0001338de8429cfb,
+                           0x001b64ce37a10a84, 0x0007ed9338de846a,
+                           0x00091fb64ce37b13, 0x000a247ed93391f2,
+                           0x003f2891fb64de5f, 0x0016fca247edd3d6,
+                           0x00055bf28920b8c5, 0x00000000002488bb ]);
+
+        let mut bytes = [0u8; 128];
+        let mut out;
+
+        for i in 0..128 {
+            bytes[i] = ((i * 37) + 11) as u8;
+        }
+
+        out = Mod_e511_187::from_bytes_wide(&bytes);
+        assert!(SEQ.normalize_eq(&mut out));
+        out = Mod_e511_187::from_bytes_wide(&[0xff; 128]);
+        assert!(ONES.normalize_eq(&mut out));
+
+        // Values that fit in the field are unchanged.
+        bytes = [0u8; 128];
+        bytes[0..64].copy_from_slice(&M_ONE.packed());
+        out = Mod_e511_187::from_bytes_wide(&bytes);
+        assert!(M_ONE.normalize_eq(&mut out));
+
+        bytes[0] += 1;
+        out = Mod_e511_187::from_bytes_wide(&bytes);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e511_187::from_bytes_wide(&[]);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e511_187::from_bytes_wide(&[2]);
+        assert!(TWO.normalize_eq(&mut out));
+    }
 }
@@ -1,3 +1,9 @@
+use choice::Choice;
+use choice::ConditionallyNegatable;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
+use choice::is_zero_i64;
+use error::Error;
 use field::prime_field::PrimeField;
 use field::prime_field::PrimeFieldMask;
 use normalize::Normalize;
@@ -84,14 +90,23 @@ pub const E521_BASE_X: Mod_e521_1 =
 pub const E521_BASE_Y: Mod_e521_1 =
     Mod_e521_1([ 0xc, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
 
-/// The prime subgroup order of the curve E-521,
-/// 2^519 - 337554763258501705789107630418782636071904961214051226618635150085779108655765
-pub const E521_ORDER: Mod_e521_1 =
-    Mod_e521_1([ 0x002a2435f5180d6b, 0x00115a6a3c7d1503,
-                 0x0027ec53f04fbd8c, 0x003f217dcdae2bd7,
-                 0x003ffd15b6c64746, 0x003fffffffffffff,
-                 0x003fffffffffffff, 0x003fffffffffffff,
-                 0x003fffffffffffff, 0x00000001ffffffff ]);
+/// The non-negative square root of -d for E-521, used by Decaf,
+/// 0xd07656ee612ae602664f03a3b94a11539170515b2dab1a214d2aa70c5f8dac4db4b0312cb80cea27a5577f34829cd5d7c1584762281e21df700375d668ef98910e.
+pub const E521_SQRT_MINUS_D: Mod_e521_1 =
+    Mod_e521_1([ 0x0035d668ef98910e, 0x0008a078877dc00d,
+                 0x0029cd5d7c158476, 0x003a89e955dfcd20,
+                 0x000db4b0312cb80c, 0x0034aa9c317e36b1,
+                 0x000515b2dab1a214, 0x0028ee528454e45c,
+                 0x00212ae602664f03, 0x0000000341d95bb9 ]);
+
+/// The inverse of the square root of -d for E-521, used by Decaf,
+/// 0x9007d520ee49b39821c27b6edb1b1a1147bf1966ddebe03ccd49f579eae3a34aaf78111a3ba6d6cf2ea33f1fdc597eb583cc5437320b241fec80325f6073954339.
+pub const E521_INVSQRT_MINUS_D: Mod_e521_1 =
+    Mod_e521_1([ 0x00325f6073954339, 0x001cc82c907fb200,
+                 0x000597eb583cc543, 0x0035b3cba8cfc7f7,
+                 0x000aaf78111a3ba6, 0x003527d5e7ab8e8d,
+                 0x0031966ddebe03cc, 0x001bb6c6c68451ef,
+                 0x002e49b39821c27b, 0x00000002401f5483 ]);
 
 impl Debug for Mod_e521_1 {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -139,6 +154,44 @@ impl Mod_e521_1 {
         self.0[9] >> 35
     }
 
+    /// Add 2p to a value whose representation has gone negative.
+    /// Multiplication assumes that every digit is non-negative, so
+    /// this must follow any operation that can borrow out of the
+    /// highest digit.
+    fn fix_sign(&mut self) {
+        let m = self.0[9] >> 63;
+        let s0 = self.0[0] + ((MODULUS.0[0] << 1) & m);
+        let c0 = s0 >> 54;
+        let s1 = self.0[1] + ((MODULUS.0[1] << 1) & m) + c0;
+        let c1 = s1 >> 54;
+        let s2 = self.0[2] + ((MODULUS.0[2] << 1) & m) + c1;
+        let c2 = s2 >> 54;
+        let s3 = self.0[3] + ((MODULUS.0[3] << 1) & m) + c2;
+        let c3 = s3 >> 54;
+        let s4 = self.0[4] + ((MODULUS.0[4] << 1) & m) + c3;
+        let c4 = s4 >> 54;
+        let s5 = self.0[5] + ((MODULUS.0[5] << 1) & m) + c4;
+        let c5 = s5 >> 54;
+        let s6 = self.0[6] + ((MODULUS.0[6] << 1) & m) + c5;
+        let c6 = s6 >> 54;
+        let s7 = self.0[7] + ((MODULUS.0[7] << 1) & m) + c6;
+        let c7 = s7 >> 54;
+        let s8 = self.0[8] + ((MODULUS.0[8] << 1) & m) + c7;
+        let c8 = s8 >> 54;
+        let s9 = self.0[9] + ((MODULUS.0[9] << 1) & m) + c8;
+
+        self.0[0] = s0 & 0x003fffffffffffff;
+        self.0[1] = s1 & 0x003fffffffffffff;
+        self.0[2] = s2 & 0x003fffffffffffff;
+        self.0[3] = s3 & 0x003fffffffffffff;
+        self.0[4] = s4 & 0x003fffffffffffff;
+        self.0[5] = s5 & 0x003fffffffffffff;
+        self.0[6] = s6 & 0x003fffffffffffff;
+        self.0[7] = s7 & 0x003fffffffffffff;
+        self.0[8] = s8 & 0x003fffffffffffff;
+        self.0[9] = s9;
+    }
+
     /// Serialize a value as a little-endian byte array.  This has the
     /// effect of normalizing the representation.
     pub fn packed(&mut self) -> [u8; 66] {
@@ -152,9 +205,9 @@ impl Neg for Mod_e521_1 {
     type Output = Mod_e521_1;
 
     fn neg(self) -> Mod_e521_1 {
-        let mut out = self;
+        let mut out = ZERO;
 
-        out += &MODULUS;
+        out -= &self;
         out
     }
 }
@@ -385,7 +438,7 @@ impl<'b> SubAssign<&'b Mod_e521_1> for Mod_e521_1 {
         let b8 = rhs.0[8];
         let b9 = rhs.0[9] & 0x00000007ffffffff;
 
-        let cin = self.carry_out() + rhs.carry_out();
+        let cin = self.carry_out() - rhs.carry_out();
         let s0 = a0 - b0 + (cin * C_VAL);
         let c0 = s0 >> 54;
         let s1 = a1 - b1 + c0;
@@ -416,6 +469,8 @@ impl<'b> SubAssign<&'b Mod_e521_1> for Mod_e521_1 {
         self.0[7] = s7 & 0x003fffffffffffff;
         self.0[8] = s8 & 0x003fffffffffffff;
         self.0[9] = s9;
+
+        self.fix_sign();
     }
 }
 
@@ -1392,13 +1447,20 @@ impl Mul<Mod_e521_1> for Mod_e521_1 {
 
 impl Rand for Mod_e521_1 {
     fn rand<R: Rng>(rng: &mut R) -> Self {
-        let mut out = Mod_e521_1([0i64; 10]);
+        let mut bytes = [0u8; 66];
+        let mut out = ZERO;
 
-        for i in 0..10 {
-            out.0[i] = rng.gen_range(0, MODULUS.0[i]);
-        }
+        // Rejection sampling: draw 521-bit values until one is less
+        // than the modulus.  This is uniform, and almost never
+        // takes more than one try.
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[65] &= 0x01;
 
-        out
+            if out.unpack_checked(&bytes).is_ok() {
+                return out;
+            }
+        }
     }
 }
 
@@ -1485,6 +1547,38 @@ impl Pack for Mod_e521_1 {
         out
     }
 
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 66 {
+            return Err(Error::InvalidLength);
+        }
+
+        if bytes[65] & 0xfe != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        // Adding C carries out of the top digit exactly when the
+        // value is at least the modulus.
+        if (&out + (C_VAL as i32)).carry_out() != 0 {
+            return Err(Error::NonCanonical);
+        }
+
+        *self = out;
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
     fn pack(&mut self, bytes: &mut [u8]) {
         self.normalize();
         self.pack_normalized(bytes)
@@ -1642,6 +1736,47 @@ impl PrimeFieldMask for Mod_e521_1_Mask {
     }
 }
 
+impl ConditionallySelectable for Mod_e521_1 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = Mod_e521_1_Mask::filled(choice.into());
+
+        Mod_e521_1([ a.0[0] ^ ((a.0[0] ^ b.0[0]) & mask.0[0]),
+                     a.0[1] ^ ((a.0[1] ^ b.0[1]) & mask.0[1]),
+                     a.0[2] ^ ((a.0[2] ^ b.0[2]) & mask.0[2]),
+                     a.0[3] ^ ((a.0[3] ^ b.0[3]) & mask.0[3]),
+                     a.0[4] ^ ((a.0[4] ^ b.0[4]) & mask.0[4]),
+                     a.0[5] ^ ((a.0[5] ^ b.0[5]) & mask.0[5]),
+                     a.0[6] ^ ((a.0[6] ^ b.0[6]) & mask.0[6]),
+                     a.0[7] ^ ((a.0[7] ^ b.0[7]) & mask.0[7]),
+                     a.0[8] ^ ((a.0[8] ^ b.0[8]) & mask.0[8]),
+                     a.0[9] ^ ((a.0[9] ^ b.0[9]) & mask.0[9]) ])
+    }
+}
+
+impl ConditionallyNegatable for Mod_e521_1 {
+    fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -*self;
+
+        self.conditional_assign(&neg, choice);
+    }
+}
+
+impl ConstantTimeEq for Mod_e521_1 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let mut a = *self;
+        let mut b = *other;
+
+        a.normalize();
+        b.normalize();
+
+        is_zero_i64((a.0[0] ^ b.0[0]) | (a.0[1] ^ b.0[1]) |
+                    (a.0[2] ^ b.0[2]) | (a.0[3] ^ b.0[3]) |
+                    (a.0[4] ^ b.0[4]) | (a.0[5] ^ b.0[5]) |
+                    (a.0[6] ^ b.0[6]) | (a.0[7] ^ b.0[7]) |
+                    (a.0[8] ^ b.0[8]) | (a.0[9] ^ b.0[9]))
+    }
+}
+
 impl PrimeField for Mod_e521_1 {
     fn fill(&mut self, bit: bool) {
         let mut mask = bit as i64;
@@ -2595,6 +2730,8 @@ impl PrimeField for Mod_e521_1 {
         self.0[7] = s7 & 0x003fffffffffffff;
         self.0[8] = s8 & 0x003fffffffffffff;
         self.0[9] = s9;
+
+        self.fix_sign();
     }
 
     fn small_add(&self, rhs: i32) -> Mod_e521_1 {
@@ -2650,6 +2787,8 @@ impl PrimeField for Mod_e521_1 {
         self.0[7] = s7 & 0x003fffffffffffff;
         self.0[8] = s8 & 0x003fffffffffffff;
         self.0[9] = s9;
+
+        self.fix_sign();
     }
 
     fn small_sub(&self, rhs: i32) -> Mod_e521_1 {
@@ -2680,7 +2819,7 @@ impl PrimeField for Mod_e521_1 {
         let a16 = self.0[8] & 0x07ffffff;
         let a17 = self.0[8] >> 27;
         let a18 = self.0[9] & 0x07ffffff;
-        let a19 = self.0[9] >> 27;
+        let a19 = (self.0[9] & 0x00000007ffffffff) >> 27;
 
         let b = i64::from(rhs);
 
@@ -2706,7 +2845,7 @@ impl PrimeField for Mod_e521_1 {
         let m19 = a19 * b;
 
         let cin = self.carry_out();
-        let d0 = m0 + ((m1 & 0x07ffffff) << 27) + (cin * C_VAL);
+        let d0 = m0 + ((m1 & 0x07ffffff) << 27) + (cin * C_VAL * b);
         let c0 = d0 >> 54;
         let d1 = (m1 >> 27) + m2 + ((m3 & 0x07ffffff) << 27) + c0;
         let c1 = d1 >> 54;
@@ -2719,23 +2858,48 @@ impl PrimeField for Mod_e521_1 {
         let d5 = (m9 >> 27) + m10 + ((m11 & 0x07ffffff) << 27) + c4;
         let c5 = d5 >> 54;
         let d6 = (m11 >> 27) + m12 + ((m13 & 0x07ffffff) << 27) + c5;
-        let c6 = d5 >> 54;
+        let c6 = d6 >> 54;
         let d7 = (m13 >> 27) + m14 + ((m15 & 0x07ffffff) << 27) + c6;
-        let c7 = d6 >> 54;
+        let c7 = d7 >> 54;
         let d8 = (m15 >> 27) + m16 + ((m17 & 0x07ffffff) << 27) + c7;
-        let c8 = d7 >> 54;
-        let d9 = (m17 >> 27) + m18 + (m19 << 27) + c8;
-
-        self.0[0] = d0 & 0x003fffffffffffff;
-        self.0[1] = d1 & 0x003fffffffffffff;
-        self.0[2] = d2 & 0x003fffffffffffff;
-        self.0[3] = d3 & 0x003fffffffffffff;
-        self.0[4] = d4 & 0x003fffffffffffff;
-        self.0[5] = d5 & 0x003fffffffffffff;
-        self.0[6] = d6 & 0x003fffffffffffff;
-        self.0[7] = d7 & 0x003fffffffffffff;
-        self.0[8] = d8 & 0x003fffffffffffff;
-        self.0[9] = d9;
+        let c8 = d8 >> 54;
+        let d9 = (m17 >> 27) + m18 +
+                 ((m19 & 0x07ffffff) << 27) + c8;
+
+        // Fold everything above bit 521 back in, multiplied by C.
+        let over = (d9 >> 35) + ((m19 >> 27) << 19);
+        let s0 = (d0 & 0x003fffffffffffff) + (over * C_VAL);
+        let k0 = s0 >> 54;
+        let s1 = (d1 & 0x003fffffffffffff) + k0;
+        let k1 = s1 >> 54;
+        let s2 = (d2 & 0x003fffffffffffff) + k1;
+        let k2 = s2 >> 54;
+        let s3 = (d3 & 0x003fffffffffffff) + k2;
+        let k3 = s3 >> 54;
+        let s4 = (d4 & 0x003fffffffffffff) + k3;
+        let k4 = s4 >> 54;
+        let s5 = (d5 & 0x003fffffffffffff) + k4;
+        let k5 = s5 >> 54;
+        let s6 = (d6 & 0x003fffffffffffff) + k5;
+        let k6 = s6 >> 54;
+        let s7 = (d7 & 0x003fffffffffffff) + k6;
+        let k7 = s7 >> 54;
+        let s8 = (d8 & 0x003fffffffffffff) + k7;
+        let k8 = s8 >> 54;
+        let s9 = (d9 & 0x00000007ffffffff) + k8;
+
+        self.0[0] = s0 & 0x003fffffffffffff;
+        self.0[1] = s1 & 0x003fffffffffffff;
+        self.0[2] = s2 & 0x003fffffffffffff;
+        self.0[3] = s3 & 0x003fffffffffffff;
+        self.0[4] = s4 & 0x003fffffffffffff;
+        self.0[5] = s5 & 0x003fffffffffffff;
+        self.0[6] = s6 & 0x003fffffffffffff;
+        self.0[7] = s7 & 0x003fffffffffffff;
+        self.0[8] = s8 & 0x003fffffffffffff;
+        self.0[9] = s9;
+
+        self.fix_sign();
     }
 
     fn small_mul(&self, b: i32) -> Mod_e521_1 {
@@ -2745,12 +2909,40 @@ impl PrimeField for Mod_e521_1 {
 
         out
     }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        let ndigits = (8 * bytes.len() + 520) / 521;
+        let mut buf = [0u8; 66];
+        let mut out = ZERO;
+
+        // Read the input as digits in base 2^521, and evaluate it
+        // by Horner's rule, using 2^521 = 1 mod p.
+        for j in 0..ndigits {
+            let base = (ndigits - 1 - j) * 521;
+
+            for i in 0..66 {
+                let off = base + (8 * i);
+                let lo = bytes.get(off / 8).map_or(0, |b| *b as u16);
+                let hi = bytes.get((off / 8) + 1).map_or(0, |b| *b as u16);
+
+                buf[i] = (((hi << 8) | lo) >> (off % 8)) as u8;
+            }
+
+            buf[65] &= 0x01;
+            out = out.small_mul(C_VAL as i32) + Mod_e521_1::unpacked(&buf);
+        }
+
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use choice::*;
+    use error::Error;
     use field::prime_field::*;
     use field::mod_e521_1::*;
+    use rand::thread_rng;
 
     const TWO: Mod_e521_1 = Mod_e521_1([ 2, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
 
@@ -4779,4 +4971,196 @@ mod tests {
             assert!(M_FOUR.normalize_eq(&mut val));
         }
     }
+
+    #[test]
+    fn test_neg() {
+        let l1_zeros: [&mut Mod_e521_1; 3] = [ &mut -ZERO,
+                                          &mut (&-ONE + &ONE),
+                                          &mut (&-M_TWO + &M_TWO) ];
+
+        let l1_mones: [&mut Mod_e521_1; 3] = [ &mut -ONE,
+                                          &mut -(&TWO - &ONE),
+                                          &mut (&-TWO + &ONE) ];
+
+        let l1_sixes: [&mut Mod_e521_1; 4] = [ &mut -M_SIX,
+                                          &mut (&-TWO * &M_THREE),
+                                          &mut (&(&M_ONE - &ONE) * &-THREE),
+                                          &mut (&(&ZERO - &TWO) -
+                                                &(&(&ONE - &NINE) * &ONE)) ];
+
+        for i in 0..3 {
+            assert!(ZERO.normalize_eq(l1_zeros[i]));
+        }
+
+        for i in 0..3 {
+            assert!(M_ONE.normalize_eq(l1_mones[i]));
+        }
+
+        for i in 0..4 {
+            assert!(SIX.normalize_eq(l1_sixes[i]));
+        }
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        let t = Choice::from(1);
+        let f = Choice::from(0);
+        let mut a = TWO;
+        let mut b = M_THREE;
+        let mut sel_f = Mod_e521_1::conditional_select(&TWO, &THREE, f);
+        let mut sel_t = Mod_e521_1::conditional_select(&TWO, &THREE, t);
+
+        assert!(TWO.normalize_eq(&mut sel_f));
+        assert!(THREE.normalize_eq(&mut sel_t));
+
+        Mod_e521_1::conditional_swap(&mut a, &mut b, f);
+        assert!(TWO.normalize_eq(&mut a));
+        assert!(M_THREE.normalize_eq(&mut b));
+
+        Mod_e521_1::conditional_swap(&mut a, &mut b, t);
+        assert!(M_THREE.normalize_eq(&mut a));
+        assert!(TWO.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_conditional_negate() {
+        let mut a = SIX;
+        let mut b = SIX;
+
+        a.conditional_negate(Choice::from(0));
+        b.conditional_negate(Choice::from(1));
+
+        assert!(SIX.normalize_eq(&mut a));
+        assert!(M_SIX.normalize_eq(&mut b));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(ZERO.ct_eq(&ZERO)));
+        assert!(bool::from(ZERO.ct_eq(&MODULUS)));
+        assert!(bool::from(ZERO.ct_eq(&(M_ONE + ONE))));
+        assert!(bool::from(SIX.ct_eq(&(TWO * THREE))));
+        assert!(bool::from(M_SIX.ct_eq(&(M_TWO * THREE))));
+        assert!(!bool::from(ZERO.ct_eq(&ONE)));
+        assert!(!bool::from(SIX.ct_eq(&M_SIX)));
+        assert!(!bool::from(TWO.ct_eq(&THREE)));
+    }
+
+    #[test]
+    fn test_sqrt_checked() {
+        let four_root = FOUR.sqrt_checked().unwrap();
+        let nine_root = NINE.sqrt_checked().unwrap();
+        let mut zero_root = ZERO.sqrt_checked().unwrap();
+
+        assert!(FOUR.normalize_eq(&mut four_root.squared()));
+        assert!(NINE.normalize_eq(&mut nine_root.squared()));
+        assert!(ZERO.normalize_eq(&mut zero_root));
+        assert!(bool::from(SIXTEEN.sqrt_checked().is_some()));
+        assert!(bool::from(M_ONE.sqrt_checked().is_none()));
+        assert!(bool::from(M_FOUR.sqrt_checked().is_none()));
+    }
+
+    #[test]
+    fn test_invert_checked() {
+        let two_inv = TWO.invert_checked().unwrap();
+        let m_six_inv = M_SIX.invert_checked().unwrap();
+
+        assert!(ONE.normalize_eq(&mut (two_inv * TWO)));
+        assert!(ONE.normalize_eq(&mut (m_six_inv * M_SIX)));
+        assert!(bool::from(ZERO.invert_checked().is_none()));
+        assert!(bool::from(MODULUS.invert_checked().is_none()));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+        let mut long = [0u8; 67];
+        let mut actual = ZERO;
+
+        assert!(M_ONE.normalize_eq(&mut Mod_e521_1::unpacked_checked(&bytes)
+                                            .unwrap()));
+        assert!(actual.unpack_checked(&TWO.packed()).is_ok());
+        assert!(TWO.normalize_eq(&mut actual));
+        assert!(actual.unpack_checked(&ZERO.packed()).is_ok());
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        // The modulus itself.
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&bytes));
+
+        // Bits above the modulus.
+        assert_eq!(Err(Error::NonCanonical),
+                   actual.unpack_checked(&[0xffu8; 66]));
+
+        assert!(ZERO.normalize_eq(&mut actual));
+
+        long[0] = 1;
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long));
+        assert_eq!(Err(Error::InvalidLength),
+                   actual.unpack_checked(&long[0..65]));
+    }
+
+    #[test]
+    fn test_rand() {
+        let mut rng = thread_rng();
+        let mut counts = [0u32; 521];
+
+        // Each bit of a uniform value is set about half the time.
+        for _ in 0..2000 {
+            let mut val = Mod_e521_1::rand(&mut rng);
+            let bytes = val.packed();
+
+            assert!(Mod_e521_1::unpacked_checked(&bytes).is_ok());
+
+            for i in 0..521 {
+                counts[i] += ((bytes[i / 8] >> (i % 8)) & 0x1) as u32;
+            }
+        }
+
+        for i in 0..521 {
+            assert!(counts[i] > 800 && counts[i] < 1200,
+                    "bit {} set {} times", i, counts[i]);
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        const ONES: Mod_e521_1 =
+            Mod_e521_1([ 0x3fff, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+        const SEQ: Mod_e521_1 =
+            Mod_e521_1([ 0x0010d921ea32b978, 0x00157699badbfd22,
+                         0x001e22af33b83cd1, 0x0019abddf0021466,
+                         0x002b33fc448cd61e, 0x003ee20324456a8b,
+                         0x0003d054d95df276, 0x003062748698eafd,
+                         0x00060e569ee83078, 0x000000048daed3f5 ]);
+
+        let mut bytes = [0u8; 132];
+        let mut out;
+
+        for i in 0..132 {
+            bytes[i] = ((i * 37) + 11) as u8;
+        }
+
+        out = Mod_e521_1::from_bytes_wide(&bytes);
+        assert!(SEQ.normalize_eq(&mut out));
+        out = Mod_e521_1::from_bytes_wide(&[0xff; 132]);
+        assert!(ONES.normalize_eq(&mut out));
+
+        // Values that fit in the field are unchanged.
+        bytes = [0u8; 132];
+        bytes[0..66].copy_from_slice(&M_ONE.packed());
+        out = Mod_e521_1::from_bytes_wide(&bytes);
+        assert!(M_ONE.normalize_eq(&mut out));
+
+        bytes[0] += 1;
+        out = Mod_e521_1::from_bytes_wide(&bytes);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e521_1::from_bytes_wide(&[]);
+        assert!(ZERO.normalize_eq(&mut out));
+        out = Mod_e521_1::from_bytes_wide(&[2]);
+        assert!(TWO.normalize_eq(&mut out));
+    }
 }
@@ -1,3 +1,7 @@
+use choice::ConditionallyNegatable;
+use choice::CtOption;
+use choice::ConditionallySelectable;
+use choice::ConstantTimeEq;
 use normalize::*;
 use pack::Pack;
 use rand::Rand;
@@ -16,6 +20,7 @@ use std::ops::SubAssign;
 pub trait PrimeField : Add<i32, Output = Self> + Add<i16, Output = Self> +
     Add<i8, Output = Self> + Add<Self, Output = Self> +
     AddAssign<i32> + AddAssign<i16> + AddAssign<i8> + AddAssign<Self> +
+    ConditionallyNegatable + ConditionallySelectable + ConstantTimeEq +
     Div<Self, Output = Self> + DivAssign<Self> +
     MulAssign<i32> + MulAssign<i16> + MulAssign<i8> + MulAssign<Self> +
     Mul<i32, Output = Self> + Mul<i16, Output = Self> +
@@ -91,6 +96,26 @@ pub trait PrimeField : Add<i32, Output = Self> + Add<i16, Output = Self> +
     /// value.
     fn sqrt(&self) -> Self;
 
+    /// Compute the square root, along with a flag indicating whether
+    /// it exists.  The root is present exactly when self is a
+    /// quadratic residue or zero.
+    fn sqrt_checked(&self) -> CtOption<Self> {
+        let root = self.sqrt();
+        let exists = root.squared().ct_eq(self);
+
+        CtOption::new(root, exists)
+    }
+
+    /// Compute the multiplicative inverse, along with a flag
+    /// indicating whether it exists.  The inverse is absent exactly
+    /// when self is zero.
+    fn invert_checked(&self) -> CtOption<Self> {
+        let inverse = self.inverted();
+        let nonzero = !self.ct_eq(&Self::zero());
+
+        CtOption::new(inverse, nonzero)
+    }
+
     /// Add a single digit (represented as an i32) in-place.
     fn small_add_assign(&mut self, b: i32);
 
@@ -108,6 +133,12 @@ pub trait PrimeField : Add<i32, Output = Self> + Add<i16, Output = Self> +
 
     /// Multiply by a single digit (represented as an i32).
     fn small_mul(&self, b: i32) -> Self;
+
+    /// Reduce a little-endian byte array of any length, usually
+    /// twice the size of an element, modulo p.  This produces
+    /// uniformly distributed elements from uniform input with
+    /// negligible bias.
+    fn from_bytes_wide(bytes: &[u8]) -> Self;
 }
 
 /// Bitmasks corresponding to a PrimeField type.  These are used as
@@ -0,0 +1,213 @@
+use error::Error;
+use hash::hash_function::HashFunction;
+use hash::sha512::Sha512;
+use hash::shake256::Shake256;
+
+/// The prefix used to hash domain separation tags longer than 255
+/// bytes down to a usable size.
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// Get the domain separation tag with its length byte appended,
+/// hashing it first if it is too long, as in RFC 9380 section 5.3.3.
+fn dst_prime<H : HashFunction>(dst: &[u8], hash_len: usize) -> Vec<u8> {
+    let mut out = if dst.len() > 255 {
+        let mut hash = H::new();
+        let mut short = vec![0; hash_len];
+
+        hash.update(OVERSIZE_DST_PREFIX);
+        hash.update(dst);
+        hash.finish_into(&mut short);
+        short
+    } else {
+        dst.to_vec()
+    };
+    let len = out.len() as u8;
+
+    out.push(len);
+    out
+}
+
+/// The expand_message_xmd function from RFC 9380, using SHA-512.
+/// This produces len pseudorandom bytes from a message and a domain
+/// separation tag, and fails if len is more than 255 hash outputs.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize)
+                          -> Result<Vec<u8>, Error> {
+    let ell = len.div_ceil(64);
+
+    if ell > 255 {
+        return Err(Error::InvalidLength);
+    }
+
+    let dst = dst_prime::<Sha512>(dst, 64);
+    let mut hash = Sha512::new();
+
+    hash.update(&[0; 128]);
+    hash.update(msg);
+    hash.update(&[(len >> 8) as u8, len as u8, 0]);
+    hash.update(&dst);
+
+    let b0 = hash.finish();
+    let mut bi = [0u8; 64];
+    let mut out = Vec::with_capacity(ell * 64);
+
+    for i in 1..(ell + 1) {
+        let mut hash = Sha512::new();
+
+        // b_1 = H(b_0 || 1 || DST'), b_i = H((b_0 ^ b_(i-1)) || i || DST').
+        for (b, prev) in bi.iter_mut().zip(b0.iter()) {
+            *b ^= *prev;
+        }
+
+        hash.update(&bi);
+        hash.update(&[i as u8]);
+        hash.update(&dst);
+        bi = hash.finish();
+        out.extend_from_slice(&bi);
+    }
+
+    out.truncate(len);
+
+    Ok(out)
+}
+
+/// The expand_message_xof function from RFC 9380, using SHAKE256.
+/// The security level k, in bits, only matters for domain separation
+/// tags longer than 255 bytes, which are hashed to 2k bits.  This
+/// fails if len does not fit in two bytes.
+pub fn expand_message_xof(msg: &[u8], dst: &[u8], len: usize, k: usize)
+                          -> Result<Vec<u8>, Error> {
+    if len > 0xffff {
+        return Err(Error::InvalidLength);
+    }
+
+    let dst = dst_prime::<Shake256>(dst, (2 * k).div_ceil(8));
+    let mut hash = Shake256::new();
+    let mut out = vec![0; len];
+
+    hash.update(msg);
+    hash.update(&[(len >> 8) as u8, len as u8]);
+    hash.update(&dst);
+    hash.finish_into(&mut out);
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use hash::expand_message::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    const XMD_DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA512-256";
+
+    const XOF_DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHAKE256";
+
+    #[test]
+    fn test_xmd_rfc9380() {
+        let vectors: [(&[u8], usize, &str); 6] = [
+            (b"", 0x20,
+             "6b9a7312411d92f921c6f68ca0b63807\
+              30a1a4d982c507211a90964c394179ba"),
+            (b"abc", 0x20,
+             "0da749f12fbe5483eb066a5f59505567\
+              9b976e93abe9be6f0f6318bce7aca8dc"),
+            (b"abcdef0123456789", 0x20,
+             "087e45a86e2939ee8b91100af1583c49\
+              38e0f5fc6c9db4b107b83346bc967f58"),
+            (b"", 0x80,
+             "41b037d1734a5f8df225dd8c7de38f85\
+              1efdb45c372887be655212d07251b921\
+              b052b62eaed99b46f72f2ef4cc96bfaf\
+              254ebbbec091e1a3b9e4fb5e5b619d2e\
+              0c5414800a1d882b62bb5cd1778f098b\
+              8eb6cb399d5d9d18f5d5842cf5d13d7e\
+              b00a7cff859b605da678b318bd0e65eb\
+              ff70bec88c753b159a805d2c89c55961"),
+            (b"abc", 0x80,
+             "7f1dddd13c08b543f2e2037b14cefb25\
+              5b44c83cc397c1786d975653e36a6b11\
+              bdd7732d8b38adb4a0edc26a0cef4bb4\
+              5217135456e58fbca1703cd6032cb134\
+              7ee720b87972d63fbf232587043ed290\
+              1bce7f22610c0419751c065922b48843\
+              1851041310ad659e4b23520e1772ab29\
+              dcdeb2002222a363f0c2b1c972b3efe1"),
+            (b"abcdef0123456789", 0x80,
+             "3f721f208e6199fe903545abc26c837c\
+              e59ac6fa45733f1baaf0222f8b7acb04\
+              24814fcb5eecf6c1d38f06e9d0a6ccfb\
+              f85ae612ab8735dfdf9ce84c372a77c8\
+              f9e1c1e952c3a61b7567dd0693016af5\
+              1d2745822663d0c2367e3f4f0bed827f\
+              eecc2aaf98c949b5ed0d35c3f1023d64\
+              ad1407924288d366ea159f46287e61ac")
+        ];
+
+        for &(msg, len, out) in vectors.iter() {
+            assert_eq!(from_hex(out),
+                       expand_message_xmd(msg, XMD_DST, len).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_xof_rfc9380() {
+        let vectors: [(&[u8], usize, &str); 4] = [
+            (b"", 0x20,
+             "2ffc05c48ed32b95d72e807f6eab9f75\
+              30dd1c2f013914c8fed38c5ccc15ad76"),
+            (b"abc", 0x20,
+             "b39e493867e2767216792abce1f2676c\
+              197c0692aed061560ead251821808e07"),
+            (b"", 0x80,
+             "7a1361d2d7d82d79e035b8880c5a3c86\
+              c5afa719478c007d96e6c88737a3f631\
+              dd74a2c88df79a4cb5e5d9f7504957c7\
+              0d669ec6bfedc31e01e2bacc4ff3fdf9\
+              b6a00b17cc18d9d72ace7d6b81c2e481\
+              b4f73f34f9a7505dccbe8f5485f3d20c\
+              5409b0310093d5d6492dea4e18aa6979\
+              c23c8ea5de01582e9689612afbb353df"),
+            (b"abc", 0x80,
+             "a54303e6b172909783353ab05ef08dd4\
+              35a558c3197db0c132134649708e0b9b\
+              4e34fb99b92a9e9e28fc1f1d8860d858\
+              97a8e021e6382f3eea10577f968ff6df\
+              6c45fe624ce65ca25932f679a42a404b\
+              c3681efe03fcd45ef73bb3a8f79ba784\
+              f80f55ea8a3c367408f30381299617f5\
+              0c8cf8fbb21d0f1e1d70b0131a7b6fbe")
+        ];
+
+        for &(msg, len, out) in vectors.iter() {
+            assert_eq!(from_hex(out),
+                       expand_message_xof(msg, XOF_DST, len, 256).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_oversize_dst() {
+        let mut dst = b"QUUX-V01-CS02-with-expander-".to_vec();
+
+        dst.extend_from_slice(&[b'1'; 300]);
+        assert_eq!(from_hex("3ae157fc2a92c21f3af315e95a2ce9cd\
+                             8dfa3fc388f09af6bda943fccb70c5f3"),
+                   expand_message_xmd(b"abc", &dst, 0x20).unwrap());
+        assert_eq!(from_hex("7cfd53d2d28fa55ca88f61333bb2f107\
+                             e3482eea2661a4fa1da3ac266bef9acc"),
+                   expand_message_xof(b"abc", &dst, 0x20, 256).unwrap());
+    }
+
+    #[test]
+    fn test_too_long() {
+        assert_eq!(Some(Error::InvalidLength),
+                   expand_message_xmd(b"", XMD_DST, 255 * 64 + 1).err());
+        assert_eq!(Some(Error::InvalidLength),
+                   expand_message_xof(b"", XOF_DST, 0x10000, 256).err());
+        assert_eq!(255 * 64,
+                   expand_message_xmd(b"", XMD_DST, 255 * 64).unwrap().len());
+    }
+}
@@ -0,0 +1,14 @@
+/// Hash functions, as used by the signature schemes.  The output
+/// length is chosen by the caller, so that extendable-output
+/// functions can produce as much output as a curve needs.
+pub trait HashFunction {
+    /// Create a hash with no input.
+    fn new() -> Self;
+
+    /// Add input to the hash.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Finish the hash, filling out with its output.  A hash with a
+    /// fixed output size panics if out is longer than that.
+    fn finish_into(self, out: &mut [u8]);
+}
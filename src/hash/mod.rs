@@ -0,0 +1,4 @@
+pub mod expand_message;
+pub mod hash_function;
+pub mod sha512;
+pub mod shake256;
@@ -0,0 +1,253 @@
+use hash::hash_function::HashFunction;
+
+/// The SHA-512 hash function, as specified in FIPS 180-4.
+///
+/// This is used by the signature schemes, which need a hash with
+/// twice the output size of the scalars.  It is not constant-time
+/// with respect to the length of the input, but the lengths it is
+/// used on are never secret.
+#[derive(Clone)]
+pub struct Sha512 {
+    state: [u64; 8],
+    block: [u8; 128],
+    nblock: usize,
+    len: u64
+}
+
+/// Round constants: the first 64 bits of the fractional parts of the
+/// cube roots of the first 80 primes.
+const K: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+    0x3956c25bf348b538, 0x59f111f1b605d019,
+    0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe,
+    0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210,
+    0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2, 0xd5a79147930aa725,
+    0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001,
+    0xc24b8b70d0f89791, 0xc76c51a30654be30,
+    0xd192e819d6ef5218, 0xd69906245565a910,
+    0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53,
+    0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60,
+    0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+    0x06f067aa72176fba, 0x0a637dc5a2c898a6,
+    0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec, 0x6c44198c4a475817
+];
+
+/// Initial state: the first 64 bits of the fractional parts of the
+/// square roots of the first 8 primes.
+const INIT: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b, 0x5be0cd19137e2179
+];
+
+/// Process one 128-byte block.
+fn compress(state: &mut [u64; 8], block: &[u8]) {
+    let mut w = [0u64; 80];
+
+    for i in 0..16 {
+        let mut word = 0u64;
+
+        for j in 0..8 {
+            word = (word << 8) | (block[8 * i + j] as u64);
+        }
+
+        w[i] = word;
+    }
+
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^
+                 (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^
+                 (w[i - 2] >> 6);
+
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7])
+                        .wrapping_add(s1);
+    }
+
+    let mut v = *state;
+
+    for i in 0..80 {
+        let s1 = v[4].rotate_right(14) ^ v[4].rotate_right(18) ^
+                 v[4].rotate_right(41);
+        let ch = (v[4] & v[5]) ^ (!v[4] & v[6]);
+        let t1 = v[7].wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i])
+                     .wrapping_add(w[i]);
+        let s0 = v[0].rotate_right(28) ^ v[0].rotate_right(34) ^
+                 v[0].rotate_right(39);
+        let maj = (v[0] & v[1]) ^ (v[0] & v[2]) ^ (v[1] & v[2]);
+        let t2 = s0.wrapping_add(maj);
+
+        v[7] = v[6];
+        v[6] = v[5];
+        v[5] = v[4];
+        v[4] = v[3].wrapping_add(t1);
+        v[3] = v[2];
+        v[2] = v[1];
+        v[1] = v[0];
+        v[0] = t1.wrapping_add(t2);
+    }
+
+    for i in 0..8 {
+        state[i] = state[i].wrapping_add(v[i]);
+    }
+}
+
+impl Sha512 {
+    /// Finish the hash and get the 64-byte output.
+    pub fn finish(mut self) -> [u8; 64] {
+        let bits = self.len << 3;
+        let mut out = [0u8; 64];
+
+        // Pad with a one bit, then zeros up to the last 16 bytes,
+        // which hold the length in bits.
+        self.update(&[0x80]);
+
+        while self.nblock != 112 {
+            self.update(&[0]);
+        }
+
+        self.update(&[0u8; 8]);
+
+        for i in 0..8 {
+            self.update(&[(bits >> (56 - 8 * i)) as u8]);
+        }
+
+        for i in 0..8 {
+            for j in 0..8 {
+                out[8 * i + j] = (self.state[i] >> (56 - 8 * j)) as u8;
+            }
+        }
+
+        out
+    }
+}
+
+impl HashFunction for Sha512 {
+    fn new() -> Sha512 {
+        Sha512 { state: INIT, block: [0u8; 128], nblock: 0, len: 0 }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.len = self.len.wrapping_add(bytes.len() as u64);
+
+        for b in bytes {
+            self.block[self.nblock] = *b;
+            self.nblock += 1;
+
+            if self.nblock == 128 {
+                compress(&mut self.state, &self.block);
+                self.nblock = 0;
+            }
+        }
+    }
+
+    fn finish_into(self, out: &mut [u8]) {
+        let len = out.len();
+
+        out.copy_from_slice(&self.finish()[0..len]);
+    }
+}
+
+impl Default for Sha512 {
+    fn default() -> Sha512 {
+        Sha512::new()
+    }
+}
+
+/// Hash a byte array with SHA-512.
+pub fn sha512(bytes: &[u8]) -> [u8; 64] {
+    let mut hash = Sha512::new();
+
+    hash.update(bytes);
+    hash.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use hash::hash_function::HashFunction;
+    use hash::sha512::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    #[test]
+    fn test_empty() {
+        let expected = from_hex("cf83e1357eefb8bdf1542850d66d8007\
+                                 d620e4050b5715dc83f4a921d36ce9ce\
+                                 47d0d13c5d85f2b0ff8318d2877eec2f\
+                                 63b931bd47417a81a538327af927da3e");
+
+        assert_eq!(&expected[..], &sha512(b"")[..]);
+    }
+
+    #[test]
+    fn test_abc() {
+        let expected = from_hex("ddaf35a193617abacc417349ae204131\
+                                 12e6fa4e89a97ea20a9eeee64b55d39a\
+                                 2192992a274fc1a836ba3c23a3feebbd\
+                                 454d4423643ce80e2a9ac94fa54ca49f");
+
+        assert_eq!(&expected[..], &sha512(b"abc")[..]);
+    }
+
+    #[test]
+    fn test_two_blocks() {
+        let expected = from_hex("8e959b75dae313da8cf4f72814fc143f\
+                                 8f7779c6eb9f7fa17299aeadb6889018\
+                                 501d289e4900f7e4331b99dec4b5433a\
+                                 c7d329eeb6dd26545e96e55b874be909");
+        let input = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklm\
+                      ghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrs\
+                      mnopqrstnopqrstu";
+
+        assert_eq!(&expected[..], &sha512(input)[..]);
+    }
+
+    #[test]
+    fn test_million() {
+        let expected = from_hex("e718483d0ce769644e2e42c7bc15b463\
+                                 8e1f98b13b2044285632a803afa973eb\
+                                 de0ff244877ea60a4cb0432ce577c31b\
+                                 eb009c5c2c49aa2e4eadb217ad8cc09b");
+        let chunk = [b'a'; 1000];
+        let mut hash = Sha512::new();
+
+        // Feed the input in pieces that do not line up with blocks.
+        for _ in 0..1000 {
+            hash.update(&chunk);
+        }
+
+        assert_eq!(&expected[..], &hash.finish()[..]);
+    }
+}
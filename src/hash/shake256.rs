@@ -0,0 +1,189 @@
+use hash::hash_function::HashFunction;
+
+/// The SHAKE256 extendable-output function, as specified in FIPS
+/// 202.  This can produce output of any length, which lets a single
+/// hash serve curves with scalars of any size.
+#[derive(Clone)]
+pub struct Shake256 {
+    state: [u64; 25],
+    pos: usize
+}
+
+/// The number of bytes absorbed or squeezed per permutation.
+const RATE: usize = 136;
+
+/// Round constants for the iota step.
+const RC: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a,
+    0x8000000080008000, 0x000000000000808b, 0x0000000080000001,
+    0x8000000080008081, 0x8000000000008009, 0x000000000000008a,
+    0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089,
+    0x8000000000008003, 0x8000000000008002, 0x8000000000000080,
+    0x000000000000800a, 0x800000008000000a, 0x8000000080008081,
+    0x8000000000008080, 0x0000000080000001, 0x8000000080008008
+];
+
+/// Rotation offsets for the rho step, indexed by x + 5y.
+const ROT: [u32; 25] = [
+     0,  1, 62, 28, 27,
+    36, 44,  6, 55, 20,
+     3, 10, 43, 25, 39,
+    41, 45, 15, 21,  8,
+    18,  2, 61, 56, 14
+];
+
+/// The Keccak-f[1600] permutation.
+fn keccak_f(a: &mut [u64; 25]) {
+    for rc in RC.iter() {
+        let mut c = [0u64; 5];
+        let mut b = [0u64; 25];
+
+        // Theta: add the parity of two neighbouring columns.
+        for x in 0..5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+        }
+
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+
+            for y in 0..5 {
+                a[x + 5 * y] ^= d;
+            }
+        }
+
+        // Rho and pi: rotate each lane and move it.
+        for x in 0..5 {
+            for y in 0..5 {
+                let i = x + 5 * y;
+
+                b[y + 5 * ((2 * x + 3 * y) % 5)] = a[i].rotate_left(ROT[i]);
+            }
+        }
+
+        // Chi: the only non-linear step.
+        for x in 0..5 {
+            for y in 0..5 {
+                a[x + 5 * y] = b[x + 5 * y] ^
+                               (!b[(x + 1) % 5 + 5 * y] &
+                                b[(x + 2) % 5 + 5 * y]);
+            }
+        }
+
+        // Iota.
+        a[0] ^= rc;
+    }
+}
+
+impl Shake256 {
+    /// XOR a byte into the state at the current position.
+    fn xor_byte(&mut self, pos: usize, byte: u8) {
+        self.state[pos / 8] ^= (byte as u64) << (8 * (pos % 8));
+    }
+}
+
+impl HashFunction for Shake256 {
+    fn new() -> Shake256 {
+        Shake256 { state: [0u64; 25], pos: 0 }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            let pos = self.pos;
+
+            self.xor_byte(pos, *b);
+            self.pos += 1;
+
+            if self.pos == RATE {
+                keccak_f(&mut self.state);
+                self.pos = 0;
+            }
+        }
+    }
+
+    fn finish_into(mut self, out: &mut [u8]) {
+        let pos = self.pos;
+
+        // Pad with the SHAKE domain bits, then the final bit of the
+        // block.
+        self.xor_byte(pos, 0x1f);
+        self.xor_byte(RATE - 1, 0x80);
+        keccak_f(&mut self.state);
+
+        let mut pos = 0;
+
+        for b in out.iter_mut() {
+            if pos == RATE {
+                keccak_f(&mut self.state);
+                pos = 0;
+            }
+
+            *b = (self.state[pos / 8] >> (8 * (pos % 8))) as u8;
+            pos += 1;
+        }
+    }
+}
+
+impl Default for Shake256 {
+    fn default() -> Shake256 {
+        Shake256::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hash::hash_function::HashFunction;
+    use hash::shake256::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    fn shake256(bytes: &[u8], len: usize) -> Vec<u8> {
+        let mut hash = Shake256::new();
+        let mut out = vec![0u8; len];
+
+        hash.update(bytes);
+        hash.finish_into(&mut out);
+
+        out
+    }
+
+    #[test]
+    fn test_empty() {
+        let expected = from_hex("46b9dd2b0ba88d13233b3feb743eeb24\
+                                 3fcd52ea62b81b82b50c27646ed5762f\
+                                 d75dc4ddd8c0f200cb05019d67b592f6\
+                                 fc821c49479ab48640292eacb3b7c4be");
+
+        assert_eq!(expected, shake256(b"", 64));
+    }
+
+    #[test]
+    fn test_abc() {
+        let expected = from_hex("483366601360a8771c6863080cc4114d\
+                                 8db44530f8f1e1ee4f94ea37e78b5739");
+
+        assert_eq!(expected, shake256(b"abc", 32));
+    }
+
+    #[test]
+    fn test_long() {
+        // 200 bytes of input spans two blocks, and 512 bytes of output
+        // needs several squeezes.
+        let expected = from_hex("6a1a9d7846436e4dca5728b6f760eef0\
+                                 ca92bf0be5615e96959d767197a0beeb");
+        let mut hash = Shake256::new();
+        let mut out = [0u8; 512];
+
+        for _ in 0..200 {
+            hash.update(&[ 0xa3 ]);
+        }
+
+        hash.finish_into(&mut out);
+        assert_eq!(&expected[..], &out[480..512]);
+        assert_eq!(&shake256(&[ 0xa3; 200 ], 100)[..], &out[0..100]);
+    }
+}
@@ -2,7 +2,12 @@
 
 extern crate rand;
 
+pub mod choice;
 pub mod curve;
+pub mod error;
 pub mod field;
+pub mod hash;
 pub mod normalize;
 pub mod pack;
+pub mod scalar;
+pub mod sign;
@@ -1,3 +1,5 @@
+use error::Error;
+
 /// Trait of things that can be packed into byte arrays.
 pub trait Pack {
     /// Deserialize a little-endian byte array into a value.  The byte
@@ -8,6 +10,17 @@ pub trait Pack {
     /// array must contain a number less than the modulus.
     fn unpacked(bytes: &[u8]) -> Self;
 
+    /// Deserialize a little-endian byte array into a value, checking
+    /// that it has exactly the right length and is the canonical
+    /// encoding of a number less than the modulus.
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error>;
+
+    /// Deserialize a little-endian byte array into a value, checking
+    /// that it has exactly the right length and is the canonical
+    /// encoding of a number less than the modulus.
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error>
+        where Self: Sized;
+
     /// Serialize as a little-endian byte array.  This has the effect
     /// of normalizing the representation.
     fn pack(&mut self, &mut [u8]);
@@ -0,0 +1,457 @@
+use error::Error;
+use pack::Pack;
+use rand::Rand;
+use rand::Rng;
+use scalar::limbs;
+use scalar::scalar_field::ScalarField;
+use std::fmt::Debug;
+use std::fmt::LowerHex;
+use std::fmt::UpperHex;
+use std::ops::AddAssign;
+use std::ops::Add;
+use std::ops::MulAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::SubAssign;
+use std::ops::Sub;
+
+/// Integers mod the prime order of the Curve1174 base point,
+/// 0x1fffffffffffffffffffffffffffffff77965c4dfd307348944d45fd166c971.
+///
+/// This is represented using eight little-endian 32-bit limbs.  The
+/// group order has no special form, so multiplication uses Montgomery
+/// reduction.  Values are always kept fully reduced.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Scalar_Curve1174([u32; 8]);
+
+/// The representation of the value 0.
+pub const ZERO: Scalar_Curve1174 =
+    Scalar_Curve1174([ 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value 1.
+pub const ONE: Scalar_Curve1174 = Scalar_Curve1174([ 1, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value -1.
+pub const M_ONE: Scalar_Curve1174 =
+    Scalar_Curve1174([ 0xd166c970, 0x8944d45f, 0xdfd30734, 0xf77965c4,
+                       0xffffffff, 0xffffffff, 0xffffffff, 0x01ffffff ]);
+
+/// The group order of Curve1174.
+pub const MODULUS: Scalar_Curve1174 =
+    Scalar_Curve1174([ 0xd166c971, 0x8944d45f, 0xdfd30734, 0xf77965c4,
+                       0xffffffff, 0xffffffff, 0xffffffff, 0x01ffffff ]);
+
+/// 2^512 mod the group order, used to convert into and out of the
+/// Montgomery representation.
+const R2: [u32; 8] =
+    [ 0x0d0df74a, 0x32a1cb0b, 0xdfcfdaf8, 0x7fe44146, 0xe8acc494, 0xaf59bbb1,
+      0xebbfc821, 0x001a6134 ];
+
+/// -1 / (group order) mod 2^32.
+const M_INV: u32 = 0xb1c5286f;
+
+impl Debug for Scalar_Curve1174 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Scalar_Curve1174: [ {:x}", &self.0[0])?;
+
+        for i in 1..8 {
+            write!(f, ", {:x}", &self.0[i])?;
+        }
+
+        write!(f, " ]")
+    }
+}
+
+impl LowerHex for Scalar_Curve1174 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..32 {
+            write!(f, "{:02x}", bytes[31 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl UpperHex for Scalar_Curve1174 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..32 {
+            write!(f, "{:02X}", bytes[31 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Scalar_Curve1174 {
+    /// Serialize a value as a little-endian byte array.
+    pub fn packed(&self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        self.pack_normalized(&mut out);
+        out
+    }
+}
+
+impl Neg for Scalar_Curve1174 {
+    type Output = Scalar_Curve1174;
+
+    fn neg(self) -> Scalar_Curve1174 {
+        let mut out = ZERO;
+
+        out -= &self;
+        out
+    }
+}
+
+impl<'b> AddAssign<&'b Scalar_Curve1174> for Scalar_Curve1174 {
+    fn add_assign(&mut self, rhs: &'b Scalar_Curve1174) {
+        let a = self.0;
+
+        limbs::add(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl AddAssign<Scalar_Curve1174> for Scalar_Curve1174 {
+    fn add_assign(&mut self, rhs: Scalar_Curve1174) {
+        *self += &rhs;
+    }
+}
+
+impl<'b> Add<&'b Scalar_Curve1174> for &Scalar_Curve1174 {
+    type Output = Scalar_Curve1174;
+
+    fn add(self, rhs: &'b Scalar_Curve1174) -> Scalar_Curve1174 {
+        let mut out = *self;
+        out += rhs;
+        out
+    }
+}
+
+impl Add<Scalar_Curve1174> for Scalar_Curve1174 {
+    type Output = Scalar_Curve1174;
+
+    fn add(self, rhs: Scalar_Curve1174) -> Scalar_Curve1174 {
+        &self + &rhs
+    }
+}
+
+impl<'b> SubAssign<&'b Scalar_Curve1174> for Scalar_Curve1174 {
+    fn sub_assign(&mut self, rhs: &'b Scalar_Curve1174) {
+        let a = self.0;
+
+        limbs::sub(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl SubAssign<Scalar_Curve1174> for Scalar_Curve1174 {
+    fn sub_assign(&mut self, rhs: Scalar_Curve1174) {
+        *self -= &rhs;
+    }
+}
+
+impl<'b> Sub<&'b Scalar_Curve1174> for &Scalar_Curve1174 {
+    type Output = Scalar_Curve1174;
+
+    fn sub(self, rhs: &'b Scalar_Curve1174) -> Scalar_Curve1174 {
+        let mut out = *self;
+        out -= rhs;
+        out
+    }
+}
+
+impl Sub<Scalar_Curve1174> for Scalar_Curve1174 {
+    type Output = Scalar_Curve1174;
+
+    fn sub(self, rhs: Scalar_Curve1174) -> Scalar_Curve1174 {
+        &self - &rhs
+    }
+}
+
+impl<'b> MulAssign<&'b Scalar_Curve1174> for Scalar_Curve1174 {
+    fn mul_assign(&mut self, rhs: &'b Scalar_Curve1174) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &rhs.0, &MODULUS.0, &R2, M_INV);
+    }
+}
+
+impl MulAssign<Scalar_Curve1174> for Scalar_Curve1174 {
+    fn mul_assign(&mut self, rhs: Scalar_Curve1174) {
+        *self *= &rhs;
+    }
+}
+
+impl<'b> Mul<&'b Scalar_Curve1174> for &Scalar_Curve1174 {
+    type Output = Scalar_Curve1174;
+
+    fn mul(self, rhs: &'b Scalar_Curve1174) -> Scalar_Curve1174 {
+        let mut out = *self;
+        out *= rhs;
+        out
+    }
+}
+
+impl Mul<Scalar_Curve1174> for Scalar_Curve1174 {
+    type Output = Scalar_Curve1174;
+
+    fn mul(self, rhs: Scalar_Curve1174) -> Scalar_Curve1174 {
+        &self * &rhs
+    }
+}
+
+impl Rand for Scalar_Curve1174 {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        // Reduce a string twice the size of the order, which makes
+        // the bias negligible.
+        let mut bytes = [0u8; 64];
+
+        rng.fill_bytes(&mut bytes);
+
+        Self::from_bytes_wide(&bytes)
+    }
+}
+
+impl Pack for Scalar_Curve1174 {
+    /// Deserialize a little-endian byte array into a value.  Unlike
+    /// the field types, this reduces the input.
+    fn unpack(&mut self, bytes: &[u8]) {
+        limbs::reduce_bytes(&mut self.0, bytes, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn unpacked(bytes: &[u8]) -> Self {
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        out
+    }
+
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 32 {
+            return Err(Error::InvalidLength);
+        }
+
+        if !limbs::less_than(bytes, &MODULUS.0) {
+            return Err(Error::NonCanonical);
+        }
+
+        self.unpack(bytes);
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
+    fn pack(&mut self, bytes: &mut [u8]) {
+        self.pack_normalized(bytes);
+    }
+
+    fn pack_normalized(&self, bytes: &mut [u8]) {
+        limbs::pack(&mut bytes[0..32], &self.0);
+    }
+
+    fn nbytes() -> i32 { 32 }
+}
+
+impl ScalarField for Scalar_Curve1174 {
+    fn nbits() -> usize { 249 }
+
+    fn bit(&self, idx: usize) -> bool {
+        (self.0[idx / 32] >> (idx % 32)) & 0x1 == 0x1
+    }
+
+    fn zero() -> Self { ZERO }
+
+    fn one() -> Self { ONE }
+
+    fn modulus() -> Self { MODULUS }
+
+    fn square(&mut self) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn squared(&self) -> Self {
+        let mut out = *self;
+        out.square();
+        out
+    }
+
+    fn invert(&mut self) {
+        let a = self.0;
+
+        limbs::invert(&mut self.0, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn inverted(&self) -> Self {
+        let mut out = *self;
+        out.invert();
+        out
+    }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        Self::unpacked(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use pack::Pack;
+    use scalar::curve1174::*;
+
+    const TWO: Scalar_Curve1174 =
+        Scalar_Curve1174([ 2, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_TWO: Scalar_Curve1174 =
+        Scalar_Curve1174([ 0xd166c96f, 0x8944d45f, 0xdfd30734, 0xf77965c4,
+                           0xffffffff, 0xffffffff, 0xffffffff, 0x01ffffff ]);
+
+    const THREE: Scalar_Curve1174 =
+        Scalar_Curve1174([ 3, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_THREE: Scalar_Curve1174 =
+        Scalar_Curve1174([ 0xd166c96e, 0x8944d45f, 0xdfd30734, 0xf77965c4,
+                           0xffffffff, 0xffffffff, 0xffffffff, 0x01ffffff ]);
+
+    const SIX: Scalar_Curve1174 =
+        Scalar_Curve1174([ 6, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_SIX: Scalar_Curve1174 =
+        Scalar_Curve1174([ 0xd166c96b, 0x8944d45f, 0xdfd30734, 0xf77965c4,
+                           0xffffffff, 0xffffffff, 0xffffffff, 0x01ffffff ]);
+
+    const HALF: Scalar_Curve1174 =
+        Scalar_Curve1174([ 0xe8b364b9, 0x44a26a2f, 0x6fe9839a, 0xfbbcb2e2,
+                           0xffffffff, 0xffffffff, 0xffffffff, 0x00ffffff ]);
+
+    const WIDE: Scalar_Curve1174 =
+        Scalar_Curve1174([ 0x0d0df749, 0x32a1cb0b, 0xdfcfdaf8, 0x7fe44146,
+                           0xe8acc494, 0xaf59bbb1, 0xebbfc821, 0x001a6134 ]);
+
+    #[test]
+    fn test_add() {
+        let twos = [ ONE + ONE, THREE + M_ONE, M_ONE + THREE,
+                     M_TWO + (TWO + TWO), MODULUS + TWO ];
+
+        for two in twos.iter() {
+            assert_eq!(TWO, *two);
+        }
+
+        assert_eq!(ZERO, M_ONE + ONE);
+        assert_eq!(M_TWO, M_ONE + M_ONE);
+        assert_eq!(SIX, THREE + THREE);
+    }
+
+    #[test]
+    fn test_sub() {
+        let m_twos = [ ONE - THREE, M_ONE - ONE, ZERO - TWO,
+                       M_THREE - M_ONE, M_SIX - (M_TWO + M_TWO) ];
+
+        for m_two in m_twos.iter() {
+            assert_eq!(M_TWO, *m_two);
+        }
+
+        assert_eq!(ZERO, M_ONE - M_ONE);
+        assert_eq!(ONE, ZERO - M_ONE);
+        assert_eq!(M_SIX, ZERO - SIX);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(ZERO, -ZERO);
+        assert_eq!(M_ONE, -ONE);
+        assert_eq!(ONE, -M_ONE);
+        assert_eq!(M_SIX, -SIX);
+        assert_eq!(SIX, -(M_TWO * THREE));
+    }
+
+    #[test]
+    fn test_mul() {
+        let sixes = [ TWO * THREE, THREE * TWO, M_TWO * M_THREE,
+                      M_THREE * M_TWO, SIX * ONE, M_SIX * M_ONE ];
+        let m_sixes = [ M_TWO * THREE, THREE * M_TWO, TWO * M_THREE,
+                        M_THREE * TWO, SIX * M_ONE, M_SIX * ONE ];
+
+        for i in 0..6 {
+            assert_eq!(SIX, sixes[i]);
+            assert_eq!(M_SIX, m_sixes[i]);
+        }
+
+        assert_eq!(ONE, HALF * TWO);
+        assert_eq!(ONE, M_ONE * M_ONE);
+        assert_eq!(ZERO, M_SIX * ZERO);
+        assert_eq!(ONE, M_ONE.squared());
+        assert_eq!(ONE, ((M_ONE * M_ONE) * M_ONE) * M_ONE);
+    }
+
+    #[test]
+    fn test_inv() {
+        let vals = [ ONE, M_ONE, TWO, M_TWO, THREE, M_THREE, SIX, M_SIX,
+                     HALF, WIDE ];
+
+        for val in vals.iter() {
+            let inv = val.inverted();
+
+            assert_eq!(ONE, *val * inv);
+        }
+
+        assert_eq!(HALF, TWO.inverted());
+        assert_eq!(ZERO, ZERO.inverted());
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let vals = [ ZERO, ONE, M_ONE, TWO, M_TWO, HALF, WIDE ];
+
+        for val in vals.iter() {
+            let bytes = val.packed();
+
+            assert_eq!(*val, Scalar_Curve1174::unpacked(&bytes));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        let mut ones = [0xffu8; 64];
+
+        assert_eq!(WIDE, Scalar_Curve1174::from_bytes_wide(&ones));
+
+        MODULUS.pack_normalized(&mut ones);
+
+        for byte in ones[32..].iter_mut() {
+            *byte = 0;
+        }
+
+        assert_eq!(ZERO, Scalar_Curve1174::from_bytes_wide(&ones));
+        assert_eq!(ZERO, Scalar_Curve1174::from_bytes_wide(&[]));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+
+        assert_eq!(Ok(M_ONE), Scalar_Curve1174::unpacked_checked(&bytes));
+        assert_eq!(Ok(ZERO), Scalar_Curve1174::unpacked_checked(&ZERO.packed()));
+
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_Curve1174::unpacked_checked(&bytes));
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_Curve1174::unpacked_checked(&[0xffu8; 32]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_Curve1174::unpacked_checked(&bytes[0..31]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_Curve1174::unpacked_checked(&[0u8; 64]));
+    }
+}
@@ -0,0 +1,473 @@
+use error::Error;
+use pack::Pack;
+use rand::Rand;
+use rand::Rng;
+use scalar::limbs;
+use scalar::scalar_field::ScalarField;
+use std::fmt::Debug;
+use std::fmt::LowerHex;
+use std::fmt::UpperHex;
+use std::ops::AddAssign;
+use std::ops::Add;
+use std::ops::MulAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::SubAssign;
+use std::ops::Sub;
+
+/// Integers mod the prime order of the Curve41417 base point,
+/// 0x7ffffffffffffffffffffffffffffffffffffffffffffffffffeb3cc92414cf706022b36f1c0338ad63cf181b0e71a5e106af79.
+///
+/// This is represented using thirteen little-endian 32-bit limbs.  The
+/// group order has no special form, so multiplication uses Montgomery
+/// reduction.  Values are always kept fully reduced.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Scalar_Curve41417([u32; 13]);
+
+/// The representation of the value 0.
+pub const ZERO: Scalar_Curve41417 =
+    Scalar_Curve41417([ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value 1.
+pub const ONE: Scalar_Curve41417 =
+    Scalar_Curve41417([ 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value -1.
+pub const M_ONE: Scalar_Curve41417 =
+    Scalar_Curve41417([ 0xe106af78, 0x1b0e71a5, 0xad63cf18, 0x6f1c0338,
+                        0x706022b3, 0xc92414cf, 0xffffeb3c, 0xffffffff,
+                        0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                        0x07ffffff ]);
+
+/// The group order of Curve41417.
+pub const MODULUS: Scalar_Curve41417 =
+    Scalar_Curve41417([ 0xe106af79, 0x1b0e71a5, 0xad63cf18, 0x6f1c0338,
+                        0x706022b3, 0xc92414cf, 0xffffeb3c, 0xffffffff,
+                        0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                        0x07ffffff ]);
+
+/// 2^832 mod the group order, used to convert into and out of the
+/// Montgomery representation.
+const R2: [u32; 13] =
+    [ 0xcdff6561, 0x85f0f33f, 0x13075c9b, 0xe0ad92e9, 0x11a8cd22, 0xaa8142b8,
+      0x76dc84dd, 0x8d0f4062, 0x52b7f9b6, 0x0f743fad, 0x79dc3bd7, 0x259a4207,
+      0x0455bbee ];
+
+/// -1 / (group order) mod 2^32.
+const M_INV: u32 = 0x16ce7537;
+
+impl Debug for Scalar_Curve41417 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Scalar_Curve41417: [ {:x}", &self.0[0])?;
+
+        for i in 1..13 {
+            write!(f, ", {:x}", &self.0[i])?;
+        }
+
+        write!(f, " ]")
+    }
+}
+
+impl LowerHex for Scalar_Curve41417 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..52 {
+            write!(f, "{:02x}", bytes[51 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl UpperHex for Scalar_Curve41417 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..52 {
+            write!(f, "{:02X}", bytes[51 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Scalar_Curve41417 {
+    /// Serialize a value as a little-endian byte array.
+    pub fn packed(&self) -> [u8; 52] {
+        let mut out = [0u8; 52];
+        self.pack_normalized(&mut out);
+        out
+    }
+}
+
+impl Neg for Scalar_Curve41417 {
+    type Output = Scalar_Curve41417;
+
+    fn neg(self) -> Scalar_Curve41417 {
+        let mut out = ZERO;
+
+        out -= &self;
+        out
+    }
+}
+
+impl<'b> AddAssign<&'b Scalar_Curve41417> for Scalar_Curve41417 {
+    fn add_assign(&mut self, rhs: &'b Scalar_Curve41417) {
+        let a = self.0;
+
+        limbs::add(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl AddAssign<Scalar_Curve41417> for Scalar_Curve41417 {
+    fn add_assign(&mut self, rhs: Scalar_Curve41417) {
+        *self += &rhs;
+    }
+}
+
+impl<'b> Add<&'b Scalar_Curve41417> for &Scalar_Curve41417 {
+    type Output = Scalar_Curve41417;
+
+    fn add(self, rhs: &'b Scalar_Curve41417) -> Scalar_Curve41417 {
+        let mut out = *self;
+        out += rhs;
+        out
+    }
+}
+
+impl Add<Scalar_Curve41417> for Scalar_Curve41417 {
+    type Output = Scalar_Curve41417;
+
+    fn add(self, rhs: Scalar_Curve41417) -> Scalar_Curve41417 {
+        &self + &rhs
+    }
+}
+
+impl<'b> SubAssign<&'b Scalar_Curve41417> for Scalar_Curve41417 {
+    fn sub_assign(&mut self, rhs: &'b Scalar_Curve41417) {
+        let a = self.0;
+
+        limbs::sub(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl SubAssign<Scalar_Curve41417> for Scalar_Curve41417 {
+    fn sub_assign(&mut self, rhs: Scalar_Curve41417) {
+        *self -= &rhs;
+    }
+}
+
+impl<'b> Sub<&'b Scalar_Curve41417> for &Scalar_Curve41417 {
+    type Output = Scalar_Curve41417;
+
+    fn sub(self, rhs: &'b Scalar_Curve41417) -> Scalar_Curve41417 {
+        let mut out = *self;
+        out -= rhs;
+        out
+    }
+}
+
+impl Sub<Scalar_Curve41417> for Scalar_Curve41417 {
+    type Output = Scalar_Curve41417;
+
+    fn sub(self, rhs: Scalar_Curve41417) -> Scalar_Curve41417 {
+        &self - &rhs
+    }
+}
+
+impl<'b> MulAssign<&'b Scalar_Curve41417> for Scalar_Curve41417 {
+    fn mul_assign(&mut self, rhs: &'b Scalar_Curve41417) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &rhs.0, &MODULUS.0, &R2, M_INV);
+    }
+}
+
+impl MulAssign<Scalar_Curve41417> for Scalar_Curve41417 {
+    fn mul_assign(&mut self, rhs: Scalar_Curve41417) {
+        *self *= &rhs;
+    }
+}
+
+impl<'b> Mul<&'b Scalar_Curve41417> for &Scalar_Curve41417 {
+    type Output = Scalar_Curve41417;
+
+    fn mul(self, rhs: &'b Scalar_Curve41417) -> Scalar_Curve41417 {
+        let mut out = *self;
+        out *= rhs;
+        out
+    }
+}
+
+impl Mul<Scalar_Curve41417> for Scalar_Curve41417 {
+    type Output = Scalar_Curve41417;
+
+    fn mul(self, rhs: Scalar_Curve41417) -> Scalar_Curve41417 {
+        &self * &rhs
+    }
+}
+
+impl Rand for Scalar_Curve41417 {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        // Reduce a string twice the size of the order, which makes
+        // the bias negligible.
+        let mut bytes = [0u8; 104];
+
+        rng.fill_bytes(&mut bytes);
+
+        Self::from_bytes_wide(&bytes)
+    }
+}
+
+impl Pack for Scalar_Curve41417 {
+    /// Deserialize a little-endian byte array into a value.  Unlike
+    /// the field types, this reduces the input.
+    fn unpack(&mut self, bytes: &[u8]) {
+        limbs::reduce_bytes(&mut self.0, bytes, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn unpacked(bytes: &[u8]) -> Self {
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        out
+    }
+
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 52 {
+            return Err(Error::InvalidLength);
+        }
+
+        if !limbs::less_than(bytes, &MODULUS.0) {
+            return Err(Error::NonCanonical);
+        }
+
+        self.unpack(bytes);
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
+    fn pack(&mut self, bytes: &mut [u8]) {
+        self.pack_normalized(bytes);
+    }
+
+    fn pack_normalized(&self, bytes: &mut [u8]) {
+        limbs::pack(&mut bytes[0..52], &self.0);
+    }
+
+    fn nbytes() -> i32 { 52 }
+}
+
+impl ScalarField for Scalar_Curve41417 {
+    fn nbits() -> usize { 411 }
+
+    fn bit(&self, idx: usize) -> bool {
+        (self.0[idx / 32] >> (idx % 32)) & 0x1 == 0x1
+    }
+
+    fn zero() -> Self { ZERO }
+
+    fn one() -> Self { ONE }
+
+    fn modulus() -> Self { MODULUS }
+
+    fn square(&mut self) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn squared(&self) -> Self {
+        let mut out = *self;
+        out.square();
+        out
+    }
+
+    fn invert(&mut self) {
+        let a = self.0;
+
+        limbs::invert(&mut self.0, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn inverted(&self) -> Self {
+        let mut out = *self;
+        out.invert();
+        out
+    }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        Self::unpacked(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use pack::Pack;
+    use scalar::curve41417::*;
+
+    const TWO: Scalar_Curve41417 =
+        Scalar_Curve41417([ 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_TWO: Scalar_Curve41417 =
+        Scalar_Curve41417([ 0xe106af77, 0x1b0e71a5, 0xad63cf18, 0x6f1c0338,
+                            0x706022b3, 0xc92414cf, 0xffffeb3c, 0xffffffff,
+                            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                            0x07ffffff ]);
+
+    const THREE: Scalar_Curve41417 =
+        Scalar_Curve41417([ 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_THREE: Scalar_Curve41417 =
+        Scalar_Curve41417([ 0xe106af76, 0x1b0e71a5, 0xad63cf18, 0x6f1c0338,
+                            0x706022b3, 0xc92414cf, 0xffffeb3c, 0xffffffff,
+                            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                            0x07ffffff ]);
+
+    const SIX: Scalar_Curve41417 =
+        Scalar_Curve41417([ 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_SIX: Scalar_Curve41417 =
+        Scalar_Curve41417([ 0xe106af73, 0x1b0e71a5, 0xad63cf18, 0x6f1c0338,
+                            0x706022b3, 0xc92414cf, 0xffffeb3c, 0xffffffff,
+                            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                            0x07ffffff ]);
+
+    const HALF: Scalar_Curve41417 =
+        Scalar_Curve41417([ 0xf08357bd, 0x0d8738d2, 0x56b1e78c, 0xb78e019c,
+                            0xb8301159, 0x64920a67, 0xfffff59e, 0xffffffff,
+                            0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                            0x03ffffff ]);
+
+    const WIDE: Scalar_Curve41417 =
+        Scalar_Curve41417([ 0xcdff6560, 0x85f0f33f, 0x13075c9b, 0xe0ad92e9,
+                            0x11a8cd22, 0xaa8142b8, 0x76dc84dd, 0x8d0f4062,
+                            0x52b7f9b6, 0x0f743fad, 0x79dc3bd7, 0x259a4207,
+                            0x0455bbee ]);
+
+    #[test]
+    fn test_add() {
+        let twos = [ ONE + ONE, THREE + M_ONE, M_ONE + THREE,
+                     M_TWO + (TWO + TWO), MODULUS + TWO ];
+
+        for two in twos.iter() {
+            assert_eq!(TWO, *two);
+        }
+
+        assert_eq!(ZERO, M_ONE + ONE);
+        assert_eq!(M_TWO, M_ONE + M_ONE);
+        assert_eq!(SIX, THREE + THREE);
+    }
+
+    #[test]
+    fn test_sub() {
+        let m_twos = [ ONE - THREE, M_ONE - ONE, ZERO - TWO,
+                       M_THREE - M_ONE, M_SIX - (M_TWO + M_TWO) ];
+
+        for m_two in m_twos.iter() {
+            assert_eq!(M_TWO, *m_two);
+        }
+
+        assert_eq!(ZERO, M_ONE - M_ONE);
+        assert_eq!(ONE, ZERO - M_ONE);
+        assert_eq!(M_SIX, ZERO - SIX);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(ZERO, -ZERO);
+        assert_eq!(M_ONE, -ONE);
+        assert_eq!(ONE, -M_ONE);
+        assert_eq!(M_SIX, -SIX);
+        assert_eq!(SIX, -(M_TWO * THREE));
+    }
+
+    #[test]
+    fn test_mul() {
+        let sixes = [ TWO * THREE, THREE * TWO, M_TWO * M_THREE,
+                      M_THREE * M_TWO, SIX * ONE, M_SIX * M_ONE ];
+        let m_sixes = [ M_TWO * THREE, THREE * M_TWO, TWO * M_THREE,
+                        M_THREE * TWO, SIX * M_ONE, M_SIX * ONE ];
+
+        for i in 0..6 {
+            assert_eq!(SIX, sixes[i]);
+            assert_eq!(M_SIX, m_sixes[i]);
+        }
+
+        assert_eq!(ONE, HALF * TWO);
+        assert_eq!(ONE, M_ONE * M_ONE);
+        assert_eq!(ZERO, M_SIX * ZERO);
+        assert_eq!(ONE, M_ONE.squared());
+        assert_eq!(ONE, ((M_ONE * M_ONE) * M_ONE) * M_ONE);
+    }
+
+    #[test]
+    fn test_inv() {
+        let vals = [ ONE, M_ONE, TWO, M_TWO, THREE, M_THREE, SIX, M_SIX,
+                     HALF, WIDE ];
+
+        for val in vals.iter() {
+            let inv = val.inverted();
+
+            assert_eq!(ONE, *val * inv);
+        }
+
+        assert_eq!(HALF, TWO.inverted());
+        assert_eq!(ZERO, ZERO.inverted());
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let vals = [ ZERO, ONE, M_ONE, TWO, M_TWO, HALF, WIDE ];
+
+        for val in vals.iter() {
+            let bytes = val.packed();
+
+            assert_eq!(*val, Scalar_Curve41417::unpacked(&bytes));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        let mut ones = [0xffu8; 104];
+
+        assert_eq!(WIDE, Scalar_Curve41417::from_bytes_wide(&ones));
+
+        MODULUS.pack_normalized(&mut ones);
+
+        for byte in ones[52..].iter_mut() {
+            *byte = 0;
+        }
+
+        assert_eq!(ZERO, Scalar_Curve41417::from_bytes_wide(&ones));
+        assert_eq!(ZERO, Scalar_Curve41417::from_bytes_wide(&[]));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+
+        assert_eq!(Ok(M_ONE), Scalar_Curve41417::unpacked_checked(&bytes));
+        assert_eq!(Ok(ZERO), Scalar_Curve41417::unpacked_checked(&ZERO.packed()));
+
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_Curve41417::unpacked_checked(&bytes));
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_Curve41417::unpacked_checked(&[0xffu8; 52]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_Curve41417::unpacked_checked(&bytes[0..51]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_Curve41417::unpacked_checked(&[0u8; 104]));
+    }
+}
@@ -0,0 +1,465 @@
+use error::Error;
+use pack::Pack;
+use rand::Rand;
+use rand::Rng;
+use scalar::limbs;
+use scalar::scalar_field::ScalarField;
+use std::fmt::Debug;
+use std::fmt::LowerHex;
+use std::fmt::UpperHex;
+use std::ops::AddAssign;
+use std::ops::Add;
+use std::ops::MulAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::SubAssign;
+use std::ops::Sub;
+
+/// Integers mod the prime order of the E-382 base point,
+/// 0xfffffffffffffffffffffffffffffffffffffffffffffffd5fb21f21e95eee17c5e69281b102d2773e27e13fd3c9719.
+///
+/// This is represented using twelve little-endian 32-bit limbs.  The
+/// group order has no special form, so multiplication uses Montgomery
+/// reduction.  Values are always kept fully reduced.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Scalar_E382([u32; 12]);
+
+/// The representation of the value 0.
+pub const ZERO: Scalar_E382 =
+    Scalar_E382([ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value 1.
+pub const ONE: Scalar_E382 =
+    Scalar_E382([ 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value -1.
+pub const M_ONE: Scalar_E382 =
+    Scalar_E382([ 0xfd3c9718, 0x73e27e13, 0x1b102d27, 0x7c5e6928, 0x1e95eee1,
+                  0xd5fb21f2, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                  0xffffffff, 0x0fffffff ]);
+
+/// The group order of E-382.
+pub const MODULUS: Scalar_E382 =
+    Scalar_E382([ 0xfd3c9719, 0x73e27e13, 0x1b102d27, 0x7c5e6928, 0x1e95eee1,
+                  0xd5fb21f2, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                  0xffffffff, 0x0fffffff ]);
+
+/// 2^768 mod the group order, used to convert into and out of the
+/// Montgomery representation.
+const R2: [u32; 12] =
+    [ 0x16778442, 0x94888800, 0xfb1dc980, 0x824eda31, 0xf547e13f, 0xf5630778,
+      0x2fed43c3, 0x97012bb5, 0xb1b9c1d5, 0x1769faff, 0x01eca32d, 0x0598f43f ];
+
+/// -1 / (group order) mod 2^32.
+const M_INV: u32 = 0xa54b2ad7;
+
+impl Debug for Scalar_E382 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Scalar_E382: [ {:x}", &self.0[0])?;
+
+        for i in 1..12 {
+            write!(f, ", {:x}", &self.0[i])?;
+        }
+
+        write!(f, " ]")
+    }
+}
+
+impl LowerHex for Scalar_E382 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..48 {
+            write!(f, "{:02x}", bytes[47 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl UpperHex for Scalar_E382 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..48 {
+            write!(f, "{:02X}", bytes[47 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Scalar_E382 {
+    /// Serialize a value as a little-endian byte array.
+    pub fn packed(&self) -> [u8; 48] {
+        let mut out = [0u8; 48];
+        self.pack_normalized(&mut out);
+        out
+    }
+}
+
+impl Neg for Scalar_E382 {
+    type Output = Scalar_E382;
+
+    fn neg(self) -> Scalar_E382 {
+        let mut out = ZERO;
+
+        out -= &self;
+        out
+    }
+}
+
+impl<'b> AddAssign<&'b Scalar_E382> for Scalar_E382 {
+    fn add_assign(&mut self, rhs: &'b Scalar_E382) {
+        let a = self.0;
+
+        limbs::add(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl AddAssign<Scalar_E382> for Scalar_E382 {
+    fn add_assign(&mut self, rhs: Scalar_E382) {
+        *self += &rhs;
+    }
+}
+
+impl<'b> Add<&'b Scalar_E382> for &Scalar_E382 {
+    type Output = Scalar_E382;
+
+    fn add(self, rhs: &'b Scalar_E382) -> Scalar_E382 {
+        let mut out = *self;
+        out += rhs;
+        out
+    }
+}
+
+impl Add<Scalar_E382> for Scalar_E382 {
+    type Output = Scalar_E382;
+
+    fn add(self, rhs: Scalar_E382) -> Scalar_E382 {
+        &self + &rhs
+    }
+}
+
+impl<'b> SubAssign<&'b Scalar_E382> for Scalar_E382 {
+    fn sub_assign(&mut self, rhs: &'b Scalar_E382) {
+        let a = self.0;
+
+        limbs::sub(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl SubAssign<Scalar_E382> for Scalar_E382 {
+    fn sub_assign(&mut self, rhs: Scalar_E382) {
+        *self -= &rhs;
+    }
+}
+
+impl<'b> Sub<&'b Scalar_E382> for &Scalar_E382 {
+    type Output = Scalar_E382;
+
+    fn sub(self, rhs: &'b Scalar_E382) -> Scalar_E382 {
+        let mut out = *self;
+        out -= rhs;
+        out
+    }
+}
+
+impl Sub<Scalar_E382> for Scalar_E382 {
+    type Output = Scalar_E382;
+
+    fn sub(self, rhs: Scalar_E382) -> Scalar_E382 {
+        &self - &rhs
+    }
+}
+
+impl<'b> MulAssign<&'b Scalar_E382> for Scalar_E382 {
+    fn mul_assign(&mut self, rhs: &'b Scalar_E382) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &rhs.0, &MODULUS.0, &R2, M_INV);
+    }
+}
+
+impl MulAssign<Scalar_E382> for Scalar_E382 {
+    fn mul_assign(&mut self, rhs: Scalar_E382) {
+        *self *= &rhs;
+    }
+}
+
+impl<'b> Mul<&'b Scalar_E382> for &Scalar_E382 {
+    type Output = Scalar_E382;
+
+    fn mul(self, rhs: &'b Scalar_E382) -> Scalar_E382 {
+        let mut out = *self;
+        out *= rhs;
+        out
+    }
+}
+
+impl Mul<Scalar_E382> for Scalar_E382 {
+    type Output = Scalar_E382;
+
+    fn mul(self, rhs: Scalar_E382) -> Scalar_E382 {
+        &self * &rhs
+    }
+}
+
+impl Rand for Scalar_E382 {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        // Reduce a string twice the size of the order, which makes
+        // the bias negligible.
+        let mut bytes = [0u8; 96];
+
+        rng.fill_bytes(&mut bytes);
+
+        Self::from_bytes_wide(&bytes)
+    }
+}
+
+impl Pack for Scalar_E382 {
+    /// Deserialize a little-endian byte array into a value.  Unlike
+    /// the field types, this reduces the input.
+    fn unpack(&mut self, bytes: &[u8]) {
+        limbs::reduce_bytes(&mut self.0, bytes, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn unpacked(bytes: &[u8]) -> Self {
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        out
+    }
+
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 48 {
+            return Err(Error::InvalidLength);
+        }
+
+        if !limbs::less_than(bytes, &MODULUS.0) {
+            return Err(Error::NonCanonical);
+        }
+
+        self.unpack(bytes);
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
+    fn pack(&mut self, bytes: &mut [u8]) {
+        self.pack_normalized(bytes);
+    }
+
+    fn pack_normalized(&self, bytes: &mut [u8]) {
+        limbs::pack(&mut bytes[0..48], &self.0);
+    }
+
+    fn nbytes() -> i32 { 48 }
+}
+
+impl ScalarField for Scalar_E382 {
+    fn nbits() -> usize { 380 }
+
+    fn bit(&self, idx: usize) -> bool {
+        (self.0[idx / 32] >> (idx % 32)) & 0x1 == 0x1
+    }
+
+    fn zero() -> Self { ZERO }
+
+    fn one() -> Self { ONE }
+
+    fn modulus() -> Self { MODULUS }
+
+    fn square(&mut self) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn squared(&self) -> Self {
+        let mut out = *self;
+        out.square();
+        out
+    }
+
+    fn invert(&mut self) {
+        let a = self.0;
+
+        limbs::invert(&mut self.0, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn inverted(&self) -> Self {
+        let mut out = *self;
+        out.invert();
+        out
+    }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        Self::unpacked(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use pack::Pack;
+    use scalar::e_382::*;
+
+    const TWO: Scalar_E382 =
+        Scalar_E382([ 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_TWO: Scalar_E382 =
+        Scalar_E382([ 0xfd3c9717, 0x73e27e13, 0x1b102d27, 0x7c5e6928,
+                      0x1e95eee1, 0xd5fb21f2, 0xffffffff, 0xffffffff,
+                      0xffffffff, 0xffffffff, 0xffffffff, 0x0fffffff ]);
+
+    const THREE: Scalar_E382 =
+        Scalar_E382([ 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_THREE: Scalar_E382 =
+        Scalar_E382([ 0xfd3c9716, 0x73e27e13, 0x1b102d27, 0x7c5e6928,
+                      0x1e95eee1, 0xd5fb21f2, 0xffffffff, 0xffffffff,
+                      0xffffffff, 0xffffffff, 0xffffffff, 0x0fffffff ]);
+
+    const SIX: Scalar_E382 =
+        Scalar_E382([ 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_SIX: Scalar_E382 =
+        Scalar_E382([ 0xfd3c9713, 0x73e27e13, 0x1b102d27, 0x7c5e6928,
+                      0x1e95eee1, 0xd5fb21f2, 0xffffffff, 0xffffffff,
+                      0xffffffff, 0xffffffff, 0xffffffff, 0x0fffffff ]);
+
+    const HALF: Scalar_E382 =
+        Scalar_E382([ 0xfe9e4b8d, 0xb9f13f09, 0x0d881693, 0xbe2f3494,
+                      0x0f4af770, 0xeafd90f9, 0xffffffff, 0xffffffff,
+                      0xffffffff, 0xffffffff, 0xffffffff, 0x07ffffff ]);
+
+    const WIDE: Scalar_E382 =
+        Scalar_E382([ 0x16778441, 0x94888800, 0xfb1dc980, 0x824eda31,
+                      0xf547e13f, 0xf5630778, 0x2fed43c3, 0x97012bb5,
+                      0xb1b9c1d5, 0x1769faff, 0x01eca32d, 0x0598f43f ]);
+
+    #[test]
+    fn test_add() {
+        let twos = [ ONE + ONE, THREE + M_ONE, M_ONE + THREE,
+                     M_TWO + (TWO + TWO), MODULUS + TWO ];
+
+        for two in twos.iter() {
+            assert_eq!(TWO, *two);
+        }
+
+        assert_eq!(ZERO, M_ONE + ONE);
+        assert_eq!(M_TWO, M_ONE + M_ONE);
+        assert_eq!(SIX, THREE + THREE);
+    }
+
+    #[test]
+    fn test_sub() {
+        let m_twos = [ ONE - THREE, M_ONE - ONE, ZERO - TWO,
+                       M_THREE - M_ONE, M_SIX - (M_TWO + M_TWO) ];
+
+        for m_two in m_twos.iter() {
+            assert_eq!(M_TWO, *m_two);
+        }
+
+        assert_eq!(ZERO, M_ONE - M_ONE);
+        assert_eq!(ONE, ZERO - M_ONE);
+        assert_eq!(M_SIX, ZERO - SIX);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(ZERO, -ZERO);
+        assert_eq!(M_ONE, -ONE);
+        assert_eq!(ONE, -M_ONE);
+        assert_eq!(M_SIX, -SIX);
+        assert_eq!(SIX, -(M_TWO * THREE));
+    }
+
+    #[test]
+    fn test_mul() {
+        let sixes = [ TWO * THREE, THREE * TWO, M_TWO * M_THREE,
+                      M_THREE * M_TWO, SIX * ONE, M_SIX * M_ONE ];
+        let m_sixes = [ M_TWO * THREE, THREE * M_TWO, TWO * M_THREE,
+                        M_THREE * TWO, SIX * M_ONE, M_SIX * ONE ];
+
+        for i in 0..6 {
+            assert_eq!(SIX, sixes[i]);
+            assert_eq!(M_SIX, m_sixes[i]);
+        }
+
+        assert_eq!(ONE, HALF * TWO);
+        assert_eq!(ONE, M_ONE * M_ONE);
+        assert_eq!(ZERO, M_SIX * ZERO);
+        assert_eq!(ONE, M_ONE.squared());
+        assert_eq!(ONE, ((M_ONE * M_ONE) * M_ONE) * M_ONE);
+    }
+
+    #[test]
+    fn test_inv() {
+        let vals = [ ONE, M_ONE, TWO, M_TWO, THREE, M_THREE, SIX, M_SIX,
+                     HALF, WIDE ];
+
+        for val in vals.iter() {
+            let inv = val.inverted();
+
+            assert_eq!(ONE, *val * inv);
+        }
+
+        assert_eq!(HALF, TWO.inverted());
+        assert_eq!(ZERO, ZERO.inverted());
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let vals = [ ZERO, ONE, M_ONE, TWO, M_TWO, HALF, WIDE ];
+
+        for val in vals.iter() {
+            let bytes = val.packed();
+
+            assert_eq!(*val, Scalar_E382::unpacked(&bytes));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        let mut ones = [0xffu8; 96];
+
+        assert_eq!(WIDE, Scalar_E382::from_bytes_wide(&ones));
+
+        MODULUS.pack_normalized(&mut ones);
+
+        for byte in ones[48..].iter_mut() {
+            *byte = 0;
+        }
+
+        assert_eq!(ZERO, Scalar_E382::from_bytes_wide(&ones));
+        assert_eq!(ZERO, Scalar_E382::from_bytes_wide(&[]));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+
+        assert_eq!(Ok(M_ONE), Scalar_E382::unpacked_checked(&bytes));
+        assert_eq!(Ok(ZERO), Scalar_E382::unpacked_checked(&ZERO.packed()));
+
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_E382::unpacked_checked(&bytes));
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_E382::unpacked_checked(&[0xffu8; 48]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_E382::unpacked_checked(&bytes[0..47]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_E382::unpacked_checked(&[0u8; 96]));
+    }
+}
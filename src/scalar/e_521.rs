@@ -0,0 +1,478 @@
+use error::Error;
+use pack::Pack;
+use rand::Rand;
+use rand::Rng;
+use scalar::limbs;
+use scalar::scalar_field::ScalarField;
+use std::fmt::Debug;
+use std::fmt::LowerHex;
+use std::fmt::UpperHex;
+use std::ops::AddAssign;
+use std::ops::Add;
+use std::ops::MulAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::SubAssign;
+use std::ops::Sub;
+
+/// Integers mod the prime order of the E-521 base point,
+/// 0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffd15b6c64746fc85f736b8af5e7ec53f04fbd8c4569a8f1f4540ea2435f5180d6b.
+///
+/// This is represented using seventeen little-endian 32-bit limbs.  The
+/// group order has no special form, so multiplication uses Montgomery
+/// reduction.  Values are always kept fully reduced.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Scalar_E521([u32; 17]);
+
+/// The representation of the value 0.
+pub const ZERO: Scalar_E521 =
+    Scalar_E521([ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value 1.
+pub const ONE: Scalar_E521 =
+    Scalar_E521([ 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value -1.
+pub const M_ONE: Scalar_E521 =
+    Scalar_E521([ 0xf5180d6a, 0x40ea2435, 0x9a8f1f45, 0xfbd8c456, 0x7ec53f04,
+                  0x36b8af5e, 0x46fc85f7, 0x15b6c647, 0xfffffffd, 0xffffffff,
+                  0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                  0xffffffff, 0x0000007f ]);
+
+/// The group order of E-521.
+pub const MODULUS: Scalar_E521 =
+    Scalar_E521([ 0xf5180d6b, 0x40ea2435, 0x9a8f1f45, 0xfbd8c456, 0x7ec53f04,
+                  0x36b8af5e, 0x46fc85f7, 0x15b6c647, 0xfffffffd, 0xffffffff,
+                  0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                  0xffffffff, 0x0000007f ]);
+
+/// 2^1088 mod the group order, used to convert into and out of the
+/// Montgomery representation.
+const R2: [u32; 17] =
+    [ 0x28774219, 0x992812e1, 0xd927bcf3, 0xc4597ee5, 0x7b4cf72c, 0x294eb6d3,
+      0x6ee898f4, 0xaa0fca19, 0xa7385a54, 0xa65e8b93, 0x55c0ecbd, 0x3f9107ac,
+      0xcb4640c1, 0x7925b788, 0x25248bf0, 0x3b93548f, 0x00000016 ];
+
+/// -1 / (group order) mod 2^32.
+const M_INV: u32 = 0x026848bd;
+
+impl Debug for Scalar_E521 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Scalar_E521: [ {:x}", &self.0[0])?;
+
+        for i in 1..17 {
+            write!(f, ", {:x}", &self.0[i])?;
+        }
+
+        write!(f, " ]")
+    }
+}
+
+impl LowerHex for Scalar_E521 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..65 {
+            write!(f, "{:02x}", bytes[64 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl UpperHex for Scalar_E521 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..65 {
+            write!(f, "{:02X}", bytes[64 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Scalar_E521 {
+    /// Serialize a value as a little-endian byte array.
+    pub fn packed(&self) -> [u8; 65] {
+        let mut out = [0u8; 65];
+        self.pack_normalized(&mut out);
+        out
+    }
+}
+
+impl Neg for Scalar_E521 {
+    type Output = Scalar_E521;
+
+    fn neg(self) -> Scalar_E521 {
+        let mut out = ZERO;
+
+        out -= &self;
+        out
+    }
+}
+
+impl<'b> AddAssign<&'b Scalar_E521> for Scalar_E521 {
+    fn add_assign(&mut self, rhs: &'b Scalar_E521) {
+        let a = self.0;
+
+        limbs::add(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl AddAssign<Scalar_E521> for Scalar_E521 {
+    fn add_assign(&mut self, rhs: Scalar_E521) {
+        *self += &rhs;
+    }
+}
+
+impl<'b> Add<&'b Scalar_E521> for &Scalar_E521 {
+    type Output = Scalar_E521;
+
+    fn add(self, rhs: &'b Scalar_E521) -> Scalar_E521 {
+        let mut out = *self;
+        out += rhs;
+        out
+    }
+}
+
+impl Add<Scalar_E521> for Scalar_E521 {
+    type Output = Scalar_E521;
+
+    fn add(self, rhs: Scalar_E521) -> Scalar_E521 {
+        &self + &rhs
+    }
+}
+
+impl<'b> SubAssign<&'b Scalar_E521> for Scalar_E521 {
+    fn sub_assign(&mut self, rhs: &'b Scalar_E521) {
+        let a = self.0;
+
+        limbs::sub(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl SubAssign<Scalar_E521> for Scalar_E521 {
+    fn sub_assign(&mut self, rhs: Scalar_E521) {
+        *self -= &rhs;
+    }
+}
+
+impl<'b> Sub<&'b Scalar_E521> for &Scalar_E521 {
+    type Output = Scalar_E521;
+
+    fn sub(self, rhs: &'b Scalar_E521) -> Scalar_E521 {
+        let mut out = *self;
+        out -= rhs;
+        out
+    }
+}
+
+impl Sub<Scalar_E521> for Scalar_E521 {
+    type Output = Scalar_E521;
+
+    fn sub(self, rhs: Scalar_E521) -> Scalar_E521 {
+        &self - &rhs
+    }
+}
+
+impl<'b> MulAssign<&'b Scalar_E521> for Scalar_E521 {
+    fn mul_assign(&mut self, rhs: &'b Scalar_E521) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &rhs.0, &MODULUS.0, &R2, M_INV);
+    }
+}
+
+impl MulAssign<Scalar_E521> for Scalar_E521 {
+    fn mul_assign(&mut self, rhs: Scalar_E521) {
+        *self *= &rhs;
+    }
+}
+
+impl<'b> Mul<&'b Scalar_E521> for &Scalar_E521 {
+    type Output = Scalar_E521;
+
+    fn mul(self, rhs: &'b Scalar_E521) -> Scalar_E521 {
+        let mut out = *self;
+        out *= rhs;
+        out
+    }
+}
+
+impl Mul<Scalar_E521> for Scalar_E521 {
+    type Output = Scalar_E521;
+
+    fn mul(self, rhs: Scalar_E521) -> Scalar_E521 {
+        &self * &rhs
+    }
+}
+
+impl Rand for Scalar_E521 {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        // Reduce a string twice the size of the order, which makes
+        // the bias negligible.
+        let mut bytes = [0u8; 130];
+
+        rng.fill_bytes(&mut bytes);
+
+        Self::from_bytes_wide(&bytes)
+    }
+}
+
+impl Pack for Scalar_E521 {
+    /// Deserialize a little-endian byte array into a value.  Unlike
+    /// the field types, this reduces the input.
+    fn unpack(&mut self, bytes: &[u8]) {
+        limbs::reduce_bytes(&mut self.0, bytes, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn unpacked(bytes: &[u8]) -> Self {
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        out
+    }
+
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 65 {
+            return Err(Error::InvalidLength);
+        }
+
+        if !limbs::less_than(bytes, &MODULUS.0) {
+            return Err(Error::NonCanonical);
+        }
+
+        self.unpack(bytes);
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
+    fn pack(&mut self, bytes: &mut [u8]) {
+        self.pack_normalized(bytes);
+    }
+
+    fn pack_normalized(&self, bytes: &mut [u8]) {
+        limbs::pack(&mut bytes[0..65], &self.0);
+    }
+
+    fn nbytes() -> i32 { 65 }
+}
+
+impl ScalarField for Scalar_E521 {
+    fn nbits() -> usize { 519 }
+
+    fn bit(&self, idx: usize) -> bool {
+        (self.0[idx / 32] >> (idx % 32)) & 0x1 == 0x1
+    }
+
+    fn zero() -> Self { ZERO }
+
+    fn one() -> Self { ONE }
+
+    fn modulus() -> Self { MODULUS }
+
+    fn square(&mut self) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn squared(&self) -> Self {
+        let mut out = *self;
+        out.square();
+        out
+    }
+
+    fn invert(&mut self) {
+        let a = self.0;
+
+        limbs::invert(&mut self.0, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn inverted(&self) -> Self {
+        let mut out = *self;
+        out.invert();
+        out
+    }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        Self::unpacked(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use pack::Pack;
+    use scalar::e_521::*;
+
+    const TWO: Scalar_E521 =
+        Scalar_E521([ 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_TWO: Scalar_E521 =
+        Scalar_E521([ 0xf5180d69, 0x40ea2435, 0x9a8f1f45, 0xfbd8c456,
+                      0x7ec53f04, 0x36b8af5e, 0x46fc85f7, 0x15b6c647,
+                      0xfffffffd, 0xffffffff, 0xffffffff, 0xffffffff,
+                      0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                      0x0000007f ]);
+
+    const THREE: Scalar_E521 =
+        Scalar_E521([ 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_THREE: Scalar_E521 =
+        Scalar_E521([ 0xf5180d68, 0x40ea2435, 0x9a8f1f45, 0xfbd8c456,
+                      0x7ec53f04, 0x36b8af5e, 0x46fc85f7, 0x15b6c647,
+                      0xfffffffd, 0xffffffff, 0xffffffff, 0xffffffff,
+                      0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                      0x0000007f ]);
+
+    const SIX: Scalar_E521 =
+        Scalar_E521([ 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_SIX: Scalar_E521 =
+        Scalar_E521([ 0xf5180d65, 0x40ea2435, 0x9a8f1f45, 0xfbd8c456,
+                      0x7ec53f04, 0x36b8af5e, 0x46fc85f7, 0x15b6c647,
+                      0xfffffffd, 0xffffffff, 0xffffffff, 0xffffffff,
+                      0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                      0x0000007f ]);
+
+    const HALF: Scalar_E521 =
+        Scalar_E521([ 0xfa8c06b6, 0xa075121a, 0x4d478fa2, 0x7dec622b,
+                      0x3f629f82, 0x9b5c57af, 0xa37e42fb, 0x8adb6323,
+                      0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff,
+                      0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                      0x0000003f ]);
+
+    const WIDE: Scalar_E521 =
+        Scalar_E521([ 0x4b102ae3, 0xc7fac8fd, 0x7841d2a8, 0x67806e6b,
+                      0x37037598, 0x6b23db3f, 0x26563965, 0xc56282bb,
+                      0xecbda65d, 0x07ac55c0, 0x40c13f91, 0xb788cb46,
+                      0x8bf07925, 0x548f2524, 0x3a963b93, 0xfe3b656a,
+                      0x00000021 ]);
+
+    #[test]
+    fn test_add() {
+        let twos = [ ONE + ONE, THREE + M_ONE, M_ONE + THREE,
+                     M_TWO + (TWO + TWO), MODULUS + TWO ];
+
+        for two in twos.iter() {
+            assert_eq!(TWO, *two);
+        }
+
+        assert_eq!(ZERO, M_ONE + ONE);
+        assert_eq!(M_TWO, M_ONE + M_ONE);
+        assert_eq!(SIX, THREE + THREE);
+    }
+
+    #[test]
+    fn test_sub() {
+        let m_twos = [ ONE - THREE, M_ONE - ONE, ZERO - TWO,
+                       M_THREE - M_ONE, M_SIX - (M_TWO + M_TWO) ];
+
+        for m_two in m_twos.iter() {
+            assert_eq!(M_TWO, *m_two);
+        }
+
+        assert_eq!(ZERO, M_ONE - M_ONE);
+        assert_eq!(ONE, ZERO - M_ONE);
+        assert_eq!(M_SIX, ZERO - SIX);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(ZERO, -ZERO);
+        assert_eq!(M_ONE, -ONE);
+        assert_eq!(ONE, -M_ONE);
+        assert_eq!(M_SIX, -SIX);
+        assert_eq!(SIX, -(M_TWO * THREE));
+    }
+
+    #[test]
+    fn test_mul() {
+        let sixes = [ TWO * THREE, THREE * TWO, M_TWO * M_THREE,
+                      M_THREE * M_TWO, SIX * ONE, M_SIX * M_ONE ];
+        let m_sixes = [ M_TWO * THREE, THREE * M_TWO, TWO * M_THREE,
+                        M_THREE * TWO, SIX * M_ONE, M_SIX * ONE ];
+
+        for i in 0..6 {
+            assert_eq!(SIX, sixes[i]);
+            assert_eq!(M_SIX, m_sixes[i]);
+        }
+
+        assert_eq!(ONE, HALF * TWO);
+        assert_eq!(ONE, M_ONE * M_ONE);
+        assert_eq!(ZERO, M_SIX * ZERO);
+        assert_eq!(ONE, M_ONE.squared());
+        assert_eq!(ONE, ((M_ONE * M_ONE) * M_ONE) * M_ONE);
+    }
+
+    #[test]
+    fn test_inv() {
+        let vals = [ ONE, M_ONE, TWO, M_TWO, THREE, M_THREE, SIX, M_SIX,
+                     HALF, WIDE ];
+
+        for val in vals.iter() {
+            let inv = val.inverted();
+
+            assert_eq!(ONE, *val * inv);
+        }
+
+        assert_eq!(HALF, TWO.inverted());
+        assert_eq!(ZERO, ZERO.inverted());
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let vals = [ ZERO, ONE, M_ONE, TWO, M_TWO, HALF, WIDE ];
+
+        for val in vals.iter() {
+            let bytes = val.packed();
+
+            assert_eq!(*val, Scalar_E521::unpacked(&bytes));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        let mut ones = [0xffu8; 130];
+
+        assert_eq!(WIDE, Scalar_E521::from_bytes_wide(&ones));
+
+        MODULUS.pack_normalized(&mut ones);
+
+        for byte in ones[65..].iter_mut() {
+            *byte = 0;
+        }
+
+        assert_eq!(ZERO, Scalar_E521::from_bytes_wide(&ones));
+        assert_eq!(ZERO, Scalar_E521::from_bytes_wide(&[]));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+
+        assert_eq!(Ok(M_ONE), Scalar_E521::unpacked_checked(&bytes));
+        assert_eq!(Ok(ZERO), Scalar_E521::unpacked_checked(&ZERO.packed()));
+
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_E521::unpacked_checked(&bytes));
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_E521::unpacked_checked(&[0xffu8; 65]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_E521::unpacked_checked(&bytes[0..64]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_E521::unpacked_checked(&[0u8; 130]));
+    }
+}
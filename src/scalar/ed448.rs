@@ -0,0 +1,471 @@
+use error::Error;
+use pack::Pack;
+use rand::Rand;
+use rand::Rng;
+use scalar::limbs;
+use scalar::scalar_field::ScalarField;
+use std::fmt::Debug;
+use std::fmt::LowerHex;
+use std::fmt::UpperHex;
+use std::ops::AddAssign;
+use std::ops::Add;
+use std::ops::MulAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::SubAssign;
+use std::ops::Sub;
+
+/// Integers mod the prime order of the Ed448-Goldilocks base point,
+/// 0x3fffffffffffffffffffffffffffffffffffffffffffffffffffffff7cca23e9c44edb49aed63690216cc2728dc58f552378c292ab5844f3.
+///
+/// This is represented using fourteen little-endian 32-bit limbs.  The
+/// group order has no special form, so multiplication uses Montgomery
+/// reduction.  Values are always kept fully reduced.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Scalar_Ed448([u32; 14]);
+
+/// The representation of the value 0.
+pub const ZERO: Scalar_Ed448 =
+    Scalar_Ed448([ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value 1.
+pub const ONE: Scalar_Ed448 =
+    Scalar_Ed448([ 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value -1.
+pub const M_ONE: Scalar_Ed448 =
+    Scalar_Ed448([ 0xab5844f2, 0x2378c292, 0x8dc58f55, 0x216cc272, 0xaed63690,
+                   0xc44edb49, 0x7cca23e9, 0xffffffff, 0xffffffff, 0xffffffff,
+                   0xffffffff, 0xffffffff, 0xffffffff, 0x3fffffff ]);
+
+/// The group order of Ed448-Goldilocks.
+pub const MODULUS: Scalar_Ed448 =
+    Scalar_Ed448([ 0xab5844f3, 0x2378c292, 0x8dc58f55, 0x216cc272, 0xaed63690,
+                   0xc44edb49, 0x7cca23e9, 0xffffffff, 0xffffffff, 0xffffffff,
+                   0xffffffff, 0xffffffff, 0xffffffff, 0x3fffffff ]);
+
+/// 2^896 mod the group order, used to convert into and out of the
+/// Montgomery representation.
+const R2: [u32; 14] =
+    [ 0x049b9b60, 0xe3539257, 0xc1b195d9, 0x7af32c4b, 0x88ea1859, 0x0d66de23,
+      0x5ee4d838, 0xae17cf72, 0xa3c47c44, 0x1a9cc14b, 0xe4d070af, 0x2052bcb7,
+      0xf823b729, 0x3402a939 ];
+
+/// -1 / (group order) mod 2^32.
+const M_INV: u32 = 0xae918bc5;
+
+impl Debug for Scalar_Ed448 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Scalar_Ed448: [ {:x}", &self.0[0])?;
+
+        for i in 1..14 {
+            write!(f, ", {:x}", &self.0[i])?;
+        }
+
+        write!(f, " ]")
+    }
+}
+
+impl LowerHex for Scalar_Ed448 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..56 {
+            write!(f, "{:02x}", bytes[55 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl UpperHex for Scalar_Ed448 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..56 {
+            write!(f, "{:02X}", bytes[55 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Scalar_Ed448 {
+    /// Serialize a value as a little-endian byte array.
+    pub fn packed(&self) -> [u8; 56] {
+        let mut out = [0u8; 56];
+        self.pack_normalized(&mut out);
+        out
+    }
+}
+
+impl Neg for Scalar_Ed448 {
+    type Output = Scalar_Ed448;
+
+    fn neg(self) -> Scalar_Ed448 {
+        let mut out = ZERO;
+
+        out -= &self;
+        out
+    }
+}
+
+impl<'b> AddAssign<&'b Scalar_Ed448> for Scalar_Ed448 {
+    fn add_assign(&mut self, rhs: &'b Scalar_Ed448) {
+        let a = self.0;
+
+        limbs::add(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl AddAssign<Scalar_Ed448> for Scalar_Ed448 {
+    fn add_assign(&mut self, rhs: Scalar_Ed448) {
+        *self += &rhs;
+    }
+}
+
+impl<'b> Add<&'b Scalar_Ed448> for &Scalar_Ed448 {
+    type Output = Scalar_Ed448;
+
+    fn add(self, rhs: &'b Scalar_Ed448) -> Scalar_Ed448 {
+        let mut out = *self;
+        out += rhs;
+        out
+    }
+}
+
+impl Add<Scalar_Ed448> for Scalar_Ed448 {
+    type Output = Scalar_Ed448;
+
+    fn add(self, rhs: Scalar_Ed448) -> Scalar_Ed448 {
+        &self + &rhs
+    }
+}
+
+impl<'b> SubAssign<&'b Scalar_Ed448> for Scalar_Ed448 {
+    fn sub_assign(&mut self, rhs: &'b Scalar_Ed448) {
+        let a = self.0;
+
+        limbs::sub(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl SubAssign<Scalar_Ed448> for Scalar_Ed448 {
+    fn sub_assign(&mut self, rhs: Scalar_Ed448) {
+        *self -= &rhs;
+    }
+}
+
+impl<'b> Sub<&'b Scalar_Ed448> for &Scalar_Ed448 {
+    type Output = Scalar_Ed448;
+
+    fn sub(self, rhs: &'b Scalar_Ed448) -> Scalar_Ed448 {
+        let mut out = *self;
+        out -= rhs;
+        out
+    }
+}
+
+impl Sub<Scalar_Ed448> for Scalar_Ed448 {
+    type Output = Scalar_Ed448;
+
+    fn sub(self, rhs: Scalar_Ed448) -> Scalar_Ed448 {
+        &self - &rhs
+    }
+}
+
+impl<'b> MulAssign<&'b Scalar_Ed448> for Scalar_Ed448 {
+    fn mul_assign(&mut self, rhs: &'b Scalar_Ed448) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &rhs.0, &MODULUS.0, &R2, M_INV);
+    }
+}
+
+impl MulAssign<Scalar_Ed448> for Scalar_Ed448 {
+    fn mul_assign(&mut self, rhs: Scalar_Ed448) {
+        *self *= &rhs;
+    }
+}
+
+impl<'b> Mul<&'b Scalar_Ed448> for &Scalar_Ed448 {
+    type Output = Scalar_Ed448;
+
+    fn mul(self, rhs: &'b Scalar_Ed448) -> Scalar_Ed448 {
+        let mut out = *self;
+        out *= rhs;
+        out
+    }
+}
+
+impl Mul<Scalar_Ed448> for Scalar_Ed448 {
+    type Output = Scalar_Ed448;
+
+    fn mul(self, rhs: Scalar_Ed448) -> Scalar_Ed448 {
+        &self * &rhs
+    }
+}
+
+impl Rand for Scalar_Ed448 {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        // Reduce a string twice the size of the order, which makes
+        // the bias negligible.
+        let mut bytes = [0u8; 112];
+
+        rng.fill_bytes(&mut bytes);
+
+        Self::from_bytes_wide(&bytes)
+    }
+}
+
+impl Pack for Scalar_Ed448 {
+    /// Deserialize a little-endian byte array into a value.  Unlike
+    /// the field types, this reduces the input.
+    fn unpack(&mut self, bytes: &[u8]) {
+        limbs::reduce_bytes(&mut self.0, bytes, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn unpacked(bytes: &[u8]) -> Self {
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        out
+    }
+
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 56 {
+            return Err(Error::InvalidLength);
+        }
+
+        if !limbs::less_than(bytes, &MODULUS.0) {
+            return Err(Error::NonCanonical);
+        }
+
+        self.unpack(bytes);
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
+    fn pack(&mut self, bytes: &mut [u8]) {
+        self.pack_normalized(bytes);
+    }
+
+    fn pack_normalized(&self, bytes: &mut [u8]) {
+        limbs::pack(&mut bytes[0..56], &self.0);
+    }
+
+    fn nbytes() -> i32 { 56 }
+}
+
+impl ScalarField for Scalar_Ed448 {
+    fn nbits() -> usize { 446 }
+
+    fn bit(&self, idx: usize) -> bool {
+        (self.0[idx / 32] >> (idx % 32)) & 0x1 == 0x1
+    }
+
+    fn zero() -> Self { ZERO }
+
+    fn one() -> Self { ONE }
+
+    fn modulus() -> Self { MODULUS }
+
+    fn square(&mut self) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn squared(&self) -> Self {
+        let mut out = *self;
+        out.square();
+        out
+    }
+
+    fn invert(&mut self) {
+        let a = self.0;
+
+        limbs::invert(&mut self.0, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn inverted(&self) -> Self {
+        let mut out = *self;
+        out.invert();
+        out
+    }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        Self::unpacked(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use pack::Pack;
+    use scalar::ed448::*;
+
+    const TWO: Scalar_Ed448 =
+        Scalar_Ed448([ 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_TWO: Scalar_Ed448 =
+        Scalar_Ed448([ 0xab5844f1, 0x2378c292, 0x8dc58f55, 0x216cc272,
+                       0xaed63690, 0xc44edb49, 0x7cca23e9, 0xffffffff,
+                       0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                       0xffffffff, 0x3fffffff ]);
+
+    const THREE: Scalar_Ed448 =
+        Scalar_Ed448([ 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_THREE: Scalar_Ed448 =
+        Scalar_Ed448([ 0xab5844f0, 0x2378c292, 0x8dc58f55, 0x216cc272,
+                       0xaed63690, 0xc44edb49, 0x7cca23e9, 0xffffffff,
+                       0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                       0xffffffff, 0x3fffffff ]);
+
+    const SIX: Scalar_Ed448 =
+        Scalar_Ed448([ 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_SIX: Scalar_Ed448 =
+        Scalar_Ed448([ 0xab5844ed, 0x2378c292, 0x8dc58f55, 0x216cc272,
+                       0xaed63690, 0xc44edb49, 0x7cca23e9, 0xffffffff,
+                       0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                       0xffffffff, 0x3fffffff ]);
+
+    const HALF: Scalar_Ed448 =
+        Scalar_Ed448([ 0x55ac227a, 0x91bc6149, 0x46e2c7aa, 0x10b66139,
+                       0xd76b1b48, 0xe2276da4, 0xbe6511f4, 0xffffffff,
+                       0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+                       0xffffffff, 0x1fffffff ]);
+
+    const WIDE: Scalar_Ed448 =
+        Scalar_Ed448([ 0x049b9b5f, 0xe3539257, 0xc1b195d9, 0x7af32c4b,
+                       0x88ea1859, 0x0d66de23, 0x5ee4d838, 0xae17cf72,
+                       0xa3c47c44, 0x1a9cc14b, 0xe4d070af, 0x2052bcb7,
+                       0xf823b729, 0x3402a939 ]);
+
+    #[test]
+    fn test_add() {
+        let twos = [ ONE + ONE, THREE + M_ONE, M_ONE + THREE,
+                     M_TWO + (TWO + TWO), MODULUS + TWO ];
+
+        for two in twos.iter() {
+            assert_eq!(TWO, *two);
+        }
+
+        assert_eq!(ZERO, M_ONE + ONE);
+        assert_eq!(M_TWO, M_ONE + M_ONE);
+        assert_eq!(SIX, THREE + THREE);
+    }
+
+    #[test]
+    fn test_sub() {
+        let m_twos = [ ONE - THREE, M_ONE - ONE, ZERO - TWO,
+                       M_THREE - M_ONE, M_SIX - (M_TWO + M_TWO) ];
+
+        for m_two in m_twos.iter() {
+            assert_eq!(M_TWO, *m_two);
+        }
+
+        assert_eq!(ZERO, M_ONE - M_ONE);
+        assert_eq!(ONE, ZERO - M_ONE);
+        assert_eq!(M_SIX, ZERO - SIX);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(ZERO, -ZERO);
+        assert_eq!(M_ONE, -ONE);
+        assert_eq!(ONE, -M_ONE);
+        assert_eq!(M_SIX, -SIX);
+        assert_eq!(SIX, -(M_TWO * THREE));
+    }
+
+    #[test]
+    fn test_mul() {
+        let sixes = [ TWO * THREE, THREE * TWO, M_TWO * M_THREE,
+                      M_THREE * M_TWO, SIX * ONE, M_SIX * M_ONE ];
+        let m_sixes = [ M_TWO * THREE, THREE * M_TWO, TWO * M_THREE,
+                        M_THREE * TWO, SIX * M_ONE, M_SIX * ONE ];
+
+        for i in 0..6 {
+            assert_eq!(SIX, sixes[i]);
+            assert_eq!(M_SIX, m_sixes[i]);
+        }
+
+        assert_eq!(ONE, HALF * TWO);
+        assert_eq!(ONE, M_ONE * M_ONE);
+        assert_eq!(ZERO, M_SIX * ZERO);
+        assert_eq!(ONE, M_ONE.squared());
+        assert_eq!(ONE, ((M_ONE * M_ONE) * M_ONE) * M_ONE);
+    }
+
+    #[test]
+    fn test_inv() {
+        let vals = [ ONE, M_ONE, TWO, M_TWO, THREE, M_THREE, SIX, M_SIX,
+                     HALF, WIDE ];
+
+        for val in vals.iter() {
+            let inv = val.inverted();
+
+            assert_eq!(ONE, *val * inv);
+        }
+
+        assert_eq!(HALF, TWO.inverted());
+        assert_eq!(ZERO, ZERO.inverted());
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let vals = [ ZERO, ONE, M_ONE, TWO, M_TWO, HALF, WIDE ];
+
+        for val in vals.iter() {
+            let bytes = val.packed();
+
+            assert_eq!(*val, Scalar_Ed448::unpacked(&bytes));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        let mut ones = [0xffu8; 112];
+
+        assert_eq!(WIDE, Scalar_Ed448::from_bytes_wide(&ones));
+
+        MODULUS.pack_normalized(&mut ones);
+
+        for byte in ones[56..].iter_mut() {
+            *byte = 0;
+        }
+
+        assert_eq!(ZERO, Scalar_Ed448::from_bytes_wide(&ones));
+        assert_eq!(ZERO, Scalar_Ed448::from_bytes_wide(&[]));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+
+        assert_eq!(Ok(M_ONE), Scalar_Ed448::unpacked_checked(&bytes));
+        assert_eq!(Ok(ZERO), Scalar_Ed448::unpacked_checked(&ZERO.packed()));
+
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_Ed448::unpacked_checked(&bytes));
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_Ed448::unpacked_checked(&[0xffu8; 56]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_Ed448::unpacked_checked(&bytes[0..55]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_Ed448::unpacked_checked(&[0u8; 112]));
+    }
+}
@@ -0,0 +1,252 @@
+// Multi-precision arithmetic on little-endian arrays of 32-bit
+// limbs, shared by the scalar field types.
+//
+// Group orders do not have the special form of the field moduli, so
+// the scalar fields use Montgomery multiplication instead of
+// pseudo-Mersenne reduction.  All routines here operate on slices of
+// the same length as the modulus, take inputs less than the modulus
+// (unless otherwise stated), and run in time that depends only on
+// the length of the modulus.
+
+/// The largest number of limbs used by any scalar field.
+pub const MAX_LIMBS: usize = 17;
+
+/// Select a if mask is all zeros, or b if mask is all ones.
+fn select(out: &mut [u32], a: &[u32], b: &[u32], mask: u32) {
+    for i in 0..out.len() {
+        out[i] = (a[i] & !mask) | (b[i] & mask);
+    }
+}
+
+/// Subtract the modulus from the n + 1 limb value t if the result is
+/// non-negative, and store the low n limbs in out.
+fn reduce_once(out: &mut [u32], t: &[u32], m: &[u32]) {
+    let n = m.len();
+    let mut d = [0u32; MAX_LIMBS];
+    let mut borrow: u64 = 0;
+
+    for i in 0..n {
+        let s = (t[i] as u64).wrapping_sub(m[i] as u64).wrapping_sub(borrow);
+
+        d[i] = s as u32;
+        borrow = (s >> 63) & 0x1;
+    }
+
+    // t < m exactly when the subtraction borrows out of the top limb.
+    let under = ((t[n] as u64).wrapping_sub(borrow) >> 63) as u32;
+    let mask = 0u32.wrapping_sub(under);
+
+    select(out, &d[0..n], &t[0..n], mask);
+}
+
+/// Modular addition.
+pub fn add(out: &mut [u32], a: &[u32], b: &[u32], m: &[u32]) {
+    let n = m.len();
+    let mut t = [0u32; MAX_LIMBS + 1];
+    let mut carry: u64 = 0;
+
+    for i in 0..n {
+        let s = (a[i] as u64) + (b[i] as u64) + carry;
+
+        t[i] = s as u32;
+        carry = s >> 32;
+    }
+
+    t[n] = carry as u32;
+    reduce_once(out, &t[0..n + 1], m);
+}
+
+/// Modular subtraction.
+pub fn sub(out: &mut [u32], a: &[u32], b: &[u32], m: &[u32]) {
+    let n = m.len();
+    let mut d = [0u32; MAX_LIMBS];
+    let mut borrow: u64 = 0;
+
+    for i in 0..n {
+        let s = (a[i] as u64).wrapping_sub(b[i] as u64).wrapping_sub(borrow);
+
+        d[i] = s as u32;
+        borrow = (s >> 63) & 0x1;
+    }
+
+    // Add the modulus back if we borrowed.
+    let mask = 0u32.wrapping_sub(borrow as u32);
+    let mut carry: u64 = 0;
+
+    for i in 0..n {
+        let s = (d[i] as u64) + ((m[i] & mask) as u64) + carry;
+
+        out[i] = s as u32;
+        carry = s >> 32;
+    }
+}
+
+/// Montgomery multiplication: out = a * b / 2^(32n) mod m.  The
+/// value a may be any n-limb number; b must be less than m.  minv is
+/// -1/m mod 2^32.
+pub fn mont_mul(out: &mut [u32], a: &[u32], b: &[u32], m: &[u32], minv: u32) {
+    let n = m.len();
+    let mut t = [0u32; MAX_LIMBS + 2];
+
+    for bi in b.iter().take(n) {
+        let mut carry: u64 = 0;
+
+        for j in 0..n {
+            let s = (t[j] as u64) + (a[j] as u64) * (*bi as u64) + carry;
+
+            t[j] = s as u32;
+            carry = s >> 32;
+        }
+
+        let s = (t[n] as u64) + carry;
+
+        t[n] = s as u32;
+        t[n + 1] = (s >> 32) as u32;
+
+        let u = t[0].wrapping_mul(minv);
+        let s = (t[0] as u64) + (u as u64) * (m[0] as u64);
+        let mut carry = s >> 32;
+
+        for j in 1..n {
+            let s = (t[j] as u64) + (u as u64) * (m[j] as u64) + carry;
+
+            t[j - 1] = s as u32;
+            carry = s >> 32;
+        }
+
+        let s = (t[n] as u64) + carry;
+
+        t[n - 1] = s as u32;
+        t[n] = t[n + 1] + ((s >> 32) as u32);
+    }
+
+    reduce_once(out, &t[0..n + 1], m);
+}
+
+/// Modular multiplication of values in the ordinary representation,
+/// using r2 = 2^(64n) mod m to cancel the Montgomery factor.
+pub fn mul(out: &mut [u32], a: &[u32], b: &[u32], m: &[u32], r2: &[u32],
+           minv: u32) {
+    let n = m.len();
+    let mut t = [0u32; MAX_LIMBS];
+
+    mont_mul(&mut t[0..n], a, b, m, minv);
+    mont_mul(out, &t[0..n], r2, m, minv);
+}
+
+/// Modular exponentiation by a public exponent, given as
+/// little-endian limbs.
+pub fn pow(out: &mut [u32], a: &[u32], e: &[u32], m: &[u32], r2: &[u32],
+           minv: u32) {
+    let n = m.len();
+    let mut one = [0u32; MAX_LIMBS];
+    let mut base = [0u32; MAX_LIMBS];
+    let mut acc = [0u32; MAX_LIMBS];
+    let mut tmp = [0u32; MAX_LIMBS];
+
+    one[0] = 1;
+    // Move into the Montgomery domain.
+    mont_mul(&mut base[0..n], a, r2, m, minv);
+    mont_mul(&mut acc[0..n], &one[0..n], r2, m, minv);
+
+    for i in 0..(32 * e.len()) {
+        let idx = 32 * e.len() - 1 - i;
+
+        mont_mul(&mut tmp[0..n], &acc[0..n], &acc[0..n], m, minv);
+        acc[0..n].copy_from_slice(&tmp[0..n]);
+
+        if (e[idx / 32] >> (idx % 32)) & 0x1 == 0x1 {
+            mont_mul(&mut tmp[0..n], &acc[0..n], &base[0..n], m, minv);
+            acc[0..n].copy_from_slice(&tmp[0..n]);
+        }
+    }
+
+    // Move back out of the Montgomery domain.
+    mont_mul(out, &acc[0..n], &one[0..n], m, minv);
+}
+
+/// Modular inverse by Fermat's little theorem.  Zero maps to zero.
+pub fn invert(out: &mut [u32], a: &[u32], m: &[u32], r2: &[u32], minv: u32) {
+    let n = m.len();
+    let mut e = [0u32; MAX_LIMBS];
+    let mut borrow: u64 = 2;
+
+    for i in 0..n {
+        let s = (m[i] as u64).wrapping_sub(borrow);
+
+        e[i] = s as u32;
+        borrow = (s >> 63) & 0x1;
+    }
+
+    pow(out, a, &e[0..n], m, r2, minv);
+}
+
+/// Reduce a little-endian byte string of any length mod m.
+pub fn reduce_bytes(out: &mut [u32], bytes: &[u8], m: &[u32], r2: &[u32],
+                    minv: u32) {
+    let n = m.len();
+    let chunk = 4 * n;
+    let nchunks = bytes.len().div_ceil(chunk);
+    let mut one = [0u32; MAX_LIMBS];
+    let mut acc = [0u32; MAX_LIMBS];
+    let mut tmp = [0u32; MAX_LIMBS];
+    let mut digit = [0u32; MAX_LIMBS];
+
+    one[0] = 1;
+
+    // Horner's rule in base 2^(32n), starting from the most
+    // significant chunk.
+    for i in 0..nchunks {
+        let base = (nchunks - 1 - i) * chunk;
+
+        for limb in digit.iter_mut().take(n) {
+            *limb = 0;
+        }
+
+        for j in 0..chunk {
+            if base + j < bytes.len() {
+                digit[j / 4] |= (bytes[base + j] as u32) << (8 * (j % 4));
+            }
+        }
+
+        // acc * 2^(32n) mod m
+        mont_mul(&mut tmp[0..n], &acc[0..n], r2, m, minv);
+        acc[0..n].copy_from_slice(&tmp[0..n]);
+
+        // digit mod m
+        mont_mul(&mut tmp[0..n], &digit[0..n], r2, m, minv);
+        mont_mul(&mut digit[0..n], &tmp[0..n], &one[0..n], m, minv);
+
+        add(&mut tmp[0..n], &acc[0..n], &digit[0..n], m);
+        acc[0..n].copy_from_slice(&tmp[0..n]);
+    }
+
+    out.copy_from_slice(&acc[0..n]);
+}
+
+/// Determine whether a little-endian byte string, of at most 4n
+/// bytes, encodes a number less than m.
+pub fn less_than(bytes: &[u8], m: &[u32]) -> bool {
+    let n = m.len();
+    let mut a = [0u32; MAX_LIMBS];
+    let mut borrow: u64 = 0;
+
+    for i in 0..bytes.len() {
+        a[i / 4] |= (bytes[i] as u32) << (8 * (i % 4));
+    }
+
+    for i in 0..n {
+        let s = (a[i] as u64).wrapping_sub(m[i] as u64).wrapping_sub(borrow);
+
+        borrow = (s >> 63) & 0x1;
+    }
+
+    borrow == 1
+}
+
+/// Serialize as a little-endian byte array of the given length.
+pub fn pack(bytes: &mut [u8], a: &[u32]) {
+    for i in 0..bytes.len() {
+        bytes[i] = (a[i / 4] >> (8 * (i % 4))) as u8;
+    }
+}
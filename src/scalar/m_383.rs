@@ -0,0 +1,465 @@
+use error::Error;
+use pack::Pack;
+use rand::Rand;
+use rand::Rng;
+use scalar::limbs;
+use scalar::scalar_field::ScalarField;
+use std::fmt::Debug;
+use std::fmt::LowerHex;
+use std::fmt::UpperHex;
+use std::ops::AddAssign;
+use std::ops::Add;
+use std::ops::MulAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::SubAssign;
+use std::ops::Sub;
+
+/// Integers mod the prime order of the M-383 base point,
+/// 0x10000000000000000000000000000000000000000000000006c79673ac36ba6e7a32576f7b1b249e46bbc225be9071d7.
+///
+/// This is represented using twelve little-endian 32-bit limbs.  The
+/// group order has no special form, so multiplication uses Montgomery
+/// reduction.  Values are always kept fully reduced.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Scalar_M383([u32; 12]);
+
+/// The representation of the value 0.
+pub const ZERO: Scalar_M383 =
+    Scalar_M383([ 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value 1.
+pub const ONE: Scalar_M383 =
+    Scalar_M383([ 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+/// The representation of the value -1.
+pub const M_ONE: Scalar_M383 =
+    Scalar_M383([ 0xbe9071d6, 0x46bbc225, 0x7b1b249e, 0x7a32576f, 0xac36ba6e,
+                  0x06c79673, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                  0x00000000, 0x10000000 ]);
+
+/// The group order of M-383.
+pub const MODULUS: Scalar_M383 =
+    Scalar_M383([ 0xbe9071d7, 0x46bbc225, 0x7b1b249e, 0x7a32576f, 0xac36ba6e,
+                  0x06c79673, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                  0x00000000, 0x10000000 ]);
+
+/// 2^768 mod the group order, used to convert into and out of the
+/// Montgomery representation.
+const R2: [u32; 12] =
+    [ 0x0261ad52, 0xb2071d1d, 0x4d66c637, 0xa8f1b173, 0xaa326960, 0xb9df094c,
+      0xec39c163, 0xc8b6af14, 0x5bc84fd7, 0xfae30993, 0x540fe754, 0x0df6a8ac ];
+
+/// -1 / (group order) mod 2^32.
+const M_INV: u32 = 0x8289ee19;
+
+impl Debug for Scalar_M383 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Scalar_M383: [ {:x}", &self.0[0])?;
+
+        for i in 1..12 {
+            write!(f, ", {:x}", &self.0[i])?;
+        }
+
+        write!(f, " ]")
+    }
+}
+
+impl LowerHex for Scalar_M383 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..48 {
+            write!(f, "{:02x}", bytes[47 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl UpperHex for Scalar_M383 {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.packed();
+
+        for i in 0..48 {
+            write!(f, "{:02X}", bytes[47 - i])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Scalar_M383 {
+    /// Serialize a value as a little-endian byte array.
+    pub fn packed(&self) -> [u8; 48] {
+        let mut out = [0u8; 48];
+        self.pack_normalized(&mut out);
+        out
+    }
+}
+
+impl Neg for Scalar_M383 {
+    type Output = Scalar_M383;
+
+    fn neg(self) -> Scalar_M383 {
+        let mut out = ZERO;
+
+        out -= &self;
+        out
+    }
+}
+
+impl<'b> AddAssign<&'b Scalar_M383> for Scalar_M383 {
+    fn add_assign(&mut self, rhs: &'b Scalar_M383) {
+        let a = self.0;
+
+        limbs::add(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl AddAssign<Scalar_M383> for Scalar_M383 {
+    fn add_assign(&mut self, rhs: Scalar_M383) {
+        *self += &rhs;
+    }
+}
+
+impl<'b> Add<&'b Scalar_M383> for &Scalar_M383 {
+    type Output = Scalar_M383;
+
+    fn add(self, rhs: &'b Scalar_M383) -> Scalar_M383 {
+        let mut out = *self;
+        out += rhs;
+        out
+    }
+}
+
+impl Add<Scalar_M383> for Scalar_M383 {
+    type Output = Scalar_M383;
+
+    fn add(self, rhs: Scalar_M383) -> Scalar_M383 {
+        &self + &rhs
+    }
+}
+
+impl<'b> SubAssign<&'b Scalar_M383> for Scalar_M383 {
+    fn sub_assign(&mut self, rhs: &'b Scalar_M383) {
+        let a = self.0;
+
+        limbs::sub(&mut self.0, &a, &rhs.0, &MODULUS.0);
+    }
+}
+
+impl SubAssign<Scalar_M383> for Scalar_M383 {
+    fn sub_assign(&mut self, rhs: Scalar_M383) {
+        *self -= &rhs;
+    }
+}
+
+impl<'b> Sub<&'b Scalar_M383> for &Scalar_M383 {
+    type Output = Scalar_M383;
+
+    fn sub(self, rhs: &'b Scalar_M383) -> Scalar_M383 {
+        let mut out = *self;
+        out -= rhs;
+        out
+    }
+}
+
+impl Sub<Scalar_M383> for Scalar_M383 {
+    type Output = Scalar_M383;
+
+    fn sub(self, rhs: Scalar_M383) -> Scalar_M383 {
+        &self - &rhs
+    }
+}
+
+impl<'b> MulAssign<&'b Scalar_M383> for Scalar_M383 {
+    fn mul_assign(&mut self, rhs: &'b Scalar_M383) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &rhs.0, &MODULUS.0, &R2, M_INV);
+    }
+}
+
+impl MulAssign<Scalar_M383> for Scalar_M383 {
+    fn mul_assign(&mut self, rhs: Scalar_M383) {
+        *self *= &rhs;
+    }
+}
+
+impl<'b> Mul<&'b Scalar_M383> for &Scalar_M383 {
+    type Output = Scalar_M383;
+
+    fn mul(self, rhs: &'b Scalar_M383) -> Scalar_M383 {
+        let mut out = *self;
+        out *= rhs;
+        out
+    }
+}
+
+impl Mul<Scalar_M383> for Scalar_M383 {
+    type Output = Scalar_M383;
+
+    fn mul(self, rhs: Scalar_M383) -> Scalar_M383 {
+        &self * &rhs
+    }
+}
+
+impl Rand for Scalar_M383 {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        // Reduce a string twice the size of the order, which makes
+        // the bias negligible.
+        let mut bytes = [0u8; 96];
+
+        rng.fill_bytes(&mut bytes);
+
+        Self::from_bytes_wide(&bytes)
+    }
+}
+
+impl Pack for Scalar_M383 {
+    /// Deserialize a little-endian byte array into a value.  Unlike
+    /// the field types, this reduces the input.
+    fn unpack(&mut self, bytes: &[u8]) {
+        limbs::reduce_bytes(&mut self.0, bytes, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn unpacked(bytes: &[u8]) -> Self {
+        let mut out = ZERO;
+
+        out.unpack(bytes);
+
+        out
+    }
+
+    fn unpack_checked(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() != 48 {
+            return Err(Error::InvalidLength);
+        }
+
+        if !limbs::less_than(bytes, &MODULUS.0) {
+            return Err(Error::NonCanonical);
+        }
+
+        self.unpack(bytes);
+
+        Ok(())
+    }
+
+    fn unpacked_checked(bytes: &[u8]) -> Result<Self, Error> {
+        let mut out = ZERO;
+
+        out.unpack_checked(bytes)?;
+
+        Ok(out)
+    }
+
+    fn pack(&mut self, bytes: &mut [u8]) {
+        self.pack_normalized(bytes);
+    }
+
+    fn pack_normalized(&self, bytes: &mut [u8]) {
+        limbs::pack(&mut bytes[0..48], &self.0);
+    }
+
+    fn nbytes() -> i32 { 48 }
+}
+
+impl ScalarField for Scalar_M383 {
+    fn nbits() -> usize { 381 }
+
+    fn bit(&self, idx: usize) -> bool {
+        (self.0[idx / 32] >> (idx % 32)) & 0x1 == 0x1
+    }
+
+    fn zero() -> Self { ZERO }
+
+    fn one() -> Self { ONE }
+
+    fn modulus() -> Self { MODULUS }
+
+    fn square(&mut self) {
+        let a = self.0;
+
+        limbs::mul(&mut self.0, &a, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn squared(&self) -> Self {
+        let mut out = *self;
+        out.square();
+        out
+    }
+
+    fn invert(&mut self) {
+        let a = self.0;
+
+        limbs::invert(&mut self.0, &a, &MODULUS.0, &R2, M_INV);
+    }
+
+    fn inverted(&self) -> Self {
+        let mut out = *self;
+        out.invert();
+        out
+    }
+
+    fn from_bytes_wide(bytes: &[u8]) -> Self {
+        Self::unpacked(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use pack::Pack;
+    use scalar::m_383::*;
+
+    const TWO: Scalar_M383 =
+        Scalar_M383([ 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_TWO: Scalar_M383 =
+        Scalar_M383([ 0xbe9071d5, 0x46bbc225, 0x7b1b249e, 0x7a32576f,
+                      0xac36ba6e, 0x06c79673, 0x00000000, 0x00000000,
+                      0x00000000, 0x00000000, 0x00000000, 0x10000000 ]);
+
+    const THREE: Scalar_M383 =
+        Scalar_M383([ 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_THREE: Scalar_M383 =
+        Scalar_M383([ 0xbe9071d4, 0x46bbc225, 0x7b1b249e, 0x7a32576f,
+                      0xac36ba6e, 0x06c79673, 0x00000000, 0x00000000,
+                      0x00000000, 0x00000000, 0x00000000, 0x10000000 ]);
+
+    const SIX: Scalar_M383 =
+        Scalar_M383([ 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0 ]);
+
+    const M_SIX: Scalar_M383 =
+        Scalar_M383([ 0xbe9071d1, 0x46bbc225, 0x7b1b249e, 0x7a32576f,
+                      0xac36ba6e, 0x06c79673, 0x00000000, 0x00000000,
+                      0x00000000, 0x00000000, 0x00000000, 0x10000000 ]);
+
+    const HALF: Scalar_M383 =
+        Scalar_M383([ 0xdf4838ec, 0x235de112, 0xbd8d924f, 0x3d192bb7,
+                      0xd61b5d37, 0x0363cb39, 0x00000000, 0x00000000,
+                      0x00000000, 0x00000000, 0x00000000, 0x08000000 ]);
+
+    const WIDE: Scalar_M383 =
+        Scalar_M383([ 0x0261ad51, 0xb2071d1d, 0x4d66c637, 0xa8f1b173,
+                      0xaa326960, 0xb9df094c, 0xec39c163, 0xc8b6af14,
+                      0x5bc84fd7, 0xfae30993, 0x540fe754, 0x0df6a8ac ]);
+
+    #[test]
+    fn test_add() {
+        let twos = [ ONE + ONE, THREE + M_ONE, M_ONE + THREE,
+                     M_TWO + (TWO + TWO), MODULUS + TWO ];
+
+        for two in twos.iter() {
+            assert_eq!(TWO, *two);
+        }
+
+        assert_eq!(ZERO, M_ONE + ONE);
+        assert_eq!(M_TWO, M_ONE + M_ONE);
+        assert_eq!(SIX, THREE + THREE);
+    }
+
+    #[test]
+    fn test_sub() {
+        let m_twos = [ ONE - THREE, M_ONE - ONE, ZERO - TWO,
+                       M_THREE - M_ONE, M_SIX - (M_TWO + M_TWO) ];
+
+        for m_two in m_twos.iter() {
+            assert_eq!(M_TWO, *m_two);
+        }
+
+        assert_eq!(ZERO, M_ONE - M_ONE);
+        assert_eq!(ONE, ZERO - M_ONE);
+        assert_eq!(M_SIX, ZERO - SIX);
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(ZERO, -ZERO);
+        assert_eq!(M_ONE, -ONE);
+        assert_eq!(ONE, -M_ONE);
+        assert_eq!(M_SIX, -SIX);
+        assert_eq!(SIX, -(M_TWO * THREE));
+    }
+
+    #[test]
+    fn test_mul() {
+        let sixes = [ TWO * THREE, THREE * TWO, M_TWO * M_THREE,
+                      M_THREE * M_TWO, SIX * ONE, M_SIX * M_ONE ];
+        let m_sixes = [ M_TWO * THREE, THREE * M_TWO, TWO * M_THREE,
+                        M_THREE * TWO, SIX * M_ONE, M_SIX * ONE ];
+
+        for i in 0..6 {
+            assert_eq!(SIX, sixes[i]);
+            assert_eq!(M_SIX, m_sixes[i]);
+        }
+
+        assert_eq!(ONE, HALF * TWO);
+        assert_eq!(ONE, M_ONE * M_ONE);
+        assert_eq!(ZERO, M_SIX * ZERO);
+        assert_eq!(ONE, M_ONE.squared());
+        assert_eq!(ONE, ((M_ONE * M_ONE) * M_ONE) * M_ONE);
+    }
+
+    #[test]
+    fn test_inv() {
+        let vals = [ ONE, M_ONE, TWO, M_TWO, THREE, M_THREE, SIX, M_SIX,
+                     HALF, WIDE ];
+
+        for val in vals.iter() {
+            let inv = val.inverted();
+
+            assert_eq!(ONE, *val * inv);
+        }
+
+        assert_eq!(HALF, TWO.inverted());
+        assert_eq!(ZERO, ZERO.inverted());
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let vals = [ ZERO, ONE, M_ONE, TWO, M_TWO, HALF, WIDE ];
+
+        for val in vals.iter() {
+            let bytes = val.packed();
+
+            assert_eq!(*val, Scalar_M383::unpacked(&bytes));
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_wide() {
+        let mut ones = [0xffu8; 96];
+
+        assert_eq!(WIDE, Scalar_M383::from_bytes_wide(&ones));
+
+        MODULUS.pack_normalized(&mut ones);
+
+        for byte in ones[48..].iter_mut() {
+            *byte = 0;
+        }
+
+        assert_eq!(ZERO, Scalar_M383::from_bytes_wide(&ones));
+        assert_eq!(ZERO, Scalar_M383::from_bytes_wide(&[]));
+    }
+
+    #[test]
+    fn test_unpack_checked() {
+        let mut bytes = M_ONE.packed();
+
+        assert_eq!(Ok(M_ONE), Scalar_M383::unpacked_checked(&bytes));
+        assert_eq!(Ok(ZERO), Scalar_M383::unpacked_checked(&ZERO.packed()));
+
+        bytes[0] += 1;
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_M383::unpacked_checked(&bytes));
+        assert_eq!(Err(Error::NonCanonical),
+                   Scalar_M383::unpacked_checked(&[0xffu8; 48]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_M383::unpacked_checked(&bytes[0..47]));
+        assert_eq!(Err(Error::InvalidLength),
+                   Scalar_M383::unpacked_checked(&[0u8; 96]));
+    }
+}
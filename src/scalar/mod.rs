@@ -0,0 +1,13 @@
+mod limbs;
+pub mod scalar_field;
+pub mod e_222;
+pub mod curve1174;
+pub mod e_382;
+pub mod curve41417;
+pub mod e_521;
+pub mod m_221;
+pub mod curve25519;
+pub mod m_383;
+pub mod curve383187;
+pub mod m_511;
+pub mod ed448;
@@ -0,0 +1,55 @@
+use pack::Pack;
+use rand::Rand;
+use std::marker::Sized;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+/// Operations on the integers mod the prime order of a curve's base
+/// point.  These are the scalars by which curve points are
+/// multiplied.
+///
+/// Unlike the PrimeField types, scalars are always kept fully
+/// reduced, so there is no need for normalization.
+pub trait ScalarField : Add<Self, Output = Self> + AddAssign<Self> +
+    Mul<Self, Output = Self> + MulAssign<Self> + Neg<Output = Self> +
+    Pack + Rand + Sized + Sub<Self, Output = Self> + SubAssign<Self> +
+    Copy + Eq {
+    /// Get the number of bits in the group order.
+    fn nbits() -> usize;
+
+    /// Get the bit given by idx.
+    fn bit(&self, idx: usize) -> bool;
+
+    /// Get the representation of the value 0.
+    fn zero() -> Self;
+
+    /// Get the representation of the value 1.
+    fn one() -> Self;
+
+    /// Get the group order itself.  This is the only value that is
+    /// not reduced; it is useful mainly as a multiplier to check that
+    /// a point lies in the prime-order subgroup.
+    fn modulus() -> Self;
+
+    /// In-place square.
+    fn square(&mut self);
+
+    /// Functional square.
+    fn squared(&self) -> Self;
+
+    /// In-place multiplicative inverse.  Zero maps to zero.
+    fn invert(&mut self);
+
+    /// Functional multiplicative inverse.  Zero maps to zero.
+    fn inverted(&self) -> Self;
+
+    /// Reduce a little-endian byte array of any length (typically a
+    /// hash output twice the size of the scalar) modulo the group
+    /// order.
+    fn from_bytes_wide(bytes: &[u8]) -> Self;
+}
@@ -0,0 +1,471 @@
+use curve::ed25519::Ed25519;
+use error::Error;
+use hash::sha512::Sha512;
+use hash::sha512::sha512;
+use rand::Rng;
+use sign::schnorr::Schnorr;
+
+/// Ed25519 as a Schnorr signature scheme.
+type Scheme = Schnorr<Ed25519, Sha512>;
+
+/// Copy a slice into a fixed-size array.
+fn to_array(bytes: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+
+    out.copy_from_slice(bytes);
+
+    out
+}
+
+/// Copy a signature into a fixed-size array.
+fn to_signature(bytes: &[u8]) -> [u8; 64] {
+    let mut out = [0u8; 64];
+
+    out.copy_from_slice(bytes);
+
+    out
+}
+
+/// Build the dom2 prefix from RFC 8032, which holds the prehash flag
+/// and the context.  The context can be at most 255 bytes.
+fn dom2(phflag: u8, context: &[u8]) -> Result<Vec<u8>, Error> {
+    if context.len() > 255 {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut out = b"SigEd25519 no Ed25519 collisions".to_vec();
+
+    out.push(phflag);
+    out.push(context.len() as u8);
+    out.extend_from_slice(context);
+
+    Ok(out)
+}
+
+/// Build the dom2 prefix for Ed25519ctx, which needs a context of 1
+/// to 255 bytes.  The empty context is left to plain Ed25519.
+fn dom2_ctx(context: &[u8]) -> Result<Vec<u8>, Error> {
+    if context.is_empty() {
+        return Err(Error::InvalidLength);
+    }
+
+    dom2(0, context)
+}
+
+/// Generate a random secret key, and return it along with the
+/// corresponding public key.
+pub fn generate<R: Rng>(rng: &mut R) -> ([u8; 32], [u8; 32]) {
+    let mut secret = [0u8; 32];
+
+    rng.fill_bytes(&mut secret);
+
+    (secret, public_key(&secret))
+}
+
+/// Get the public key corresponding to a 32-byte secret key.
+pub fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    to_array(&Scheme::public_key(secret))
+}
+
+/// Sign a message with the PureEdDSA variant of Ed25519 from RFC
+/// 8032.  The signature is deterministic.
+pub fn sign(secret: &[u8; 32], msg: &[u8]) -> [u8; 64] {
+    to_signature(&Scheme::sign(secret, msg))
+}
+
+/// Verify a signature on a message made with sign.
+///
+/// This rejects encodings of S that are not less than the group
+/// order, and non-canonical encodings of the points R and A.  It
+/// checks the cofactored equation [8][S]B = [8]R + [8][k]A, so any
+/// signature accepted by the cofactorless check is also accepted.
+pub fn verify(public: &[u8; 32], msg: &[u8], sig: &[u8; 64])
+              -> Result<(), Error> {
+    Scheme::verify(public, msg, sig)
+}
+
+/// Sign a message with Ed25519ctx, under a context of 1 to 255
+/// bytes.
+pub fn sign_ctx(secret: &[u8; 32], context: &[u8], msg: &[u8])
+                -> Result<[u8; 64], Error> {
+    let dom = dom2_ctx(context)?;
+
+    Ok(to_signature(&Scheme::sign_with_prefix(&dom, secret, msg)))
+}
+
+/// Verify a signature on a message made with sign_ctx, under the
+/// same context.
+pub fn verify_ctx(public: &[u8; 32], context: &[u8], msg: &[u8],
+                  sig: &[u8; 64]) -> Result<(), Error> {
+    let dom = dom2_ctx(context)?;
+
+    Scheme::verify_with_prefix(&dom, public, msg, sig)
+}
+
+/// Sign the SHA-512 hash of a message with Ed25519ph, under a context
+/// of at most 255 bytes.  Use this to sign a message that is fed to
+/// a Sha512 a piece at a time, and so is never held in memory.
+pub fn sign_prehashed(secret: &[u8; 32], context: &[u8], hash: &[u8; 64])
+                      -> Result<[u8; 64], Error> {
+    let dom = dom2(1, context)?;
+
+    Ok(to_signature(&Scheme::sign_with_prefix(&dom, secret, hash)))
+}
+
+/// Verify a signature on the SHA-512 hash of a message made with
+/// sign_prehashed or sign_ph, under the same context.
+pub fn verify_prehashed(public: &[u8; 32], context: &[u8],
+                        hash: &[u8; 64], sig: &[u8; 64])
+                        -> Result<(), Error> {
+    let dom = dom2(1, context)?;
+
+    Scheme::verify_with_prefix(&dom, public, hash, sig)
+}
+
+/// Sign a message with Ed25519ph, under a context of at most 255
+/// bytes.
+pub fn sign_ph(secret: &[u8; 32], context: &[u8], msg: &[u8])
+               -> Result<[u8; 64], Error> {
+    sign_prehashed(secret, context, &sha512(msg))
+}
+
+/// Verify a signature on a message made with sign_ph, under the same
+/// context.
+pub fn verify_ph(public: &[u8; 32], context: &[u8], msg: &[u8],
+                 sig: &[u8; 64]) -> Result<(), Error> {
+    verify_prehashed(public, context, &sha512(msg), sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use hash::hash_function::HashFunction;
+    use hash::sha512::Sha512;
+    use hash::sha512::sha512;
+    use rand::thread_rng;
+    use sign::ed25519::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    fn check_vector(secret: &str, public: &str, msg: &[u8], sig: &str) {
+        let mut sk = [0u8; 32];
+        let mut pk = [0u8; 32];
+        let mut expected = [0u8; 64];
+
+        sk.copy_from_slice(&from_hex(secret));
+        pk.copy_from_slice(&from_hex(public));
+        expected.copy_from_slice(&from_hex(sig));
+
+        assert_eq!(pk, public_key(&sk));
+        assert_eq!(&expected[..], &sign(&sk, msg)[..]);
+        assert_eq!(Ok(()), verify(&pk, msg, &expected));
+    }
+
+    #[test]
+    fn test_rfc8032_1() {
+        check_vector("9d61b19deffd5a60ba844af492ec2cc4\
+                      4449c5697b326919703bac031cae7f60",
+                     "d75a980182b10ab7d54bfed3c964073a\
+                      0ee172f3daa62325af021a68f707511a",
+                     b"",
+                     "e5564300c360ac729086e2cc806e828a\
+                      84877f1eb8e5d974d873e06522490155\
+                      5fb8821590a33bacc61e39701cf9b46b\
+                      d25bf5f0595bbe24655141438e7a100b");
+    }
+
+    #[test]
+    fn test_rfc8032_2() {
+        check_vector("4ccd089b28ff96da9db6c346ec114e0f\
+                      5b8a319f35aba624da8cf6ed4fb8a6fb",
+                     "3d4017c3e843895a92b70aa74d1b7ebc\
+                      9c982ccf2ec4968cc0cd55f12af4660c",
+                     &[ 0x72 ],
+                     "92a009a9f0d4cab8720e820b5f642540\
+                      a2b27b5416503f8fb3762223ebdb69da\
+                      085ac1e43e15996e458f3613d0f11d8c\
+                      387b2eaeb4302aeeb00d291612bb0c00");
+    }
+
+    #[test]
+    fn test_rfc8032_3() {
+        check_vector("c5aa8df43f9f837bedb7442f31dcb7b1\
+                      66d38535076f094b85ce3a2e0b4458f7",
+                     "fc51cd8e6218a1a38da47ed00230f058\
+                      0816ed13ba3303ac5deb911548908025",
+                     &[ 0xaf, 0x82 ],
+                     "6291d657deec24024827e69c3abe01a3\
+                      0ce548a284743a445e3680d7db5ac3ac\
+                      18ff9b538d16f290ae67f760984dc659\
+                      4a7c15e9716ed28dc027beceea1ec40a");
+    }
+
+    #[test]
+    fn test_rfc8032_1024() {
+        let msg = from_hex("08b8b2b733424243760fe426a4b54908\
+                            632110a66c2f6591eabd3345e3e4eb98\
+                            fa6e264bf09efe12ee50f8f54e9f77b1\
+                            e355f6c50544e23fb1433ddf73be84d8\
+                            79de7c0046dc4996d9e773f4bc9efe57\
+                            38829adb26c81b37c93a1b270b20329d\
+                            658675fc6ea534e0810a4432826bf58c\
+                            941efb65d57a338bbd2e26640f89ffbc\
+                            1a858efcb8550ee3a5e1998bd177e93a\
+                            7363c344fe6b199ee5d02e82d522c4fe\
+                            ba15452f80288a821a579116ec6dad2b\
+                            3b310da903401aa62100ab5d1a36553e\
+                            06203b33890cc9b832f79ef80560ccb9\
+                            a39ce767967ed628c6ad573cb116dbef\
+                            efd75499da96bd68a8a97b928a8bbc10\
+                            3b6621fcde2beca1231d206be6cd9ec7\
+                            aff6f6c94fcd7204ed3455c68c83f4a4\
+                            1da4af2b74ef5c53f1d8ac70bdcb7ed1\
+                            85ce81bd84359d44254d95629e9855a9\
+                            4a7c1958d1f8ada5d0532ed8a5aa3fb2\
+                            d17ba70eb6248e594e1a2297acbbb39d\
+                            502f1a8c6eb6f1ce22b3de1a1f40cc24\
+                            554119a831a9aad6079cad88425de6bd\
+                            e1a9187ebb6092cf67bf2b13fd65f270\
+                            88d78b7e883c8759d2c4f5c65adb7553\
+                            878ad575f9fad878e80a0c9ba63bcbcc\
+                            2732e69485bbc9c90bfbd62481d9089b\
+                            eccf80cfe2df16a2cf65bd92dd597b07\
+                            07e0917af48bbb75fed413d238f5555a\
+                            7a569d80c3414a8d0859dc65a46128ba\
+                            b27af87a71314f318c782b23ebfe808b\
+                            82b0ce26401d2e22f04d83d1255dc51a\
+                            ddd3b75a2b1ae0784504df543af8969b\
+                            e3ea7082ff7fc9888c144da2af58429e\
+                            c96031dbcad3dad9af0dcbaaaf268cb8\
+                            fcffead94f3c7ca495e056a9b47acdb7\
+                            51fb73e666c6c655ade8297297d07ad1\
+                            ba5e43f1bca32301651339e22904cc8c\
+                            42f58c30c04aafdb038dda0847dd988d\
+                            cda6f3bfd15c4b4c4525004aa06eeff8\
+                            ca61783aacec57fb3d1f92b0fe2fd1a8\
+                            5f6724517b65e614ad6808d6f6ee34df\
+                            f7310fdc82aebfd904b01e1dc54b2927\
+                            094b2db68d6f903b68401adebf5a7e08\
+                            d78ff4ef5d63653a65040cf9bfd4aca7\
+                            984a74d37145986780fc0b16ac451649\
+                            de6188a7dbdf191f64b5fc5e2ab47b57\
+                            f7f7276cd419c17a3ca8e1b939ae49e4\
+                            88acba6b965610b5480109c8b17b80e1\
+                            b7b750dfc7598d5d5011fd2dcc5600a3\
+                            2ef5b52a1ecc820e308aa342721aac09\
+                            43bf6686b64b2579376504ccc493d97e\
+                            6aed3fb0f9cd71a43dd497f01f17c0e2\
+                            cb3797aa2a2f256656168e6c496afc5f\
+                            b93246f6b1116398a346f1a641f3b041\
+                            e989f7914f90cc2c7fff357876e506b5\
+                            0d334ba77c225bc307ba537152f3f161\
+                            0e4eafe595f6d9d90d11faa933a15ef1\
+                            369546868a7f3a45a96768d40fd9d034\
+                            12c091c6315cf4fde7cb68606937380d\
+                            b2eaaa707b4c4185c32eddcdd306705e\
+                            4dc1ffc872eeee475a64dfac86aba41c\
+                            0618983f8741c5ef68d3a101e8a3b8ca\
+                            c60c905c15fc910840b94c00a0b9d0");
+
+        check_vector("f5e5767cf153319517630f226876b86c\
+                      8160cc583bc013744c6bf255f5cc0ee5",
+                     "278117fc144c72340f67d0f2316e8386\
+                      ceffbf2b2428c9c51fef7c597f1d426e",
+                     &msg,
+                     "0aab4c900501b3e24d7cdf4663326a3a\
+                      87df5e4843b2cbdb67cbf6e460fec350\
+                      aa5371b1508f9f4528ecea23c436d94b\
+                      5e8fcd4f681e30a6ac00a9704a188a03");
+    }
+
+    #[test]
+    fn test_rfc8032_sha_abc() {
+        check_vector("833fe62409237b9d62ec77587520911e\
+                      9a759cec1d19755b7da901b96dca3d42",
+                     "ec172b93ad5e563bf4932c70e1245034\
+                      c35467ef2efd4d64ebf819683467e2bf",
+                     &sha512(b"abc"),
+                     "dc2a4459e7369633a52b1bf277839a00\
+                      201009a3efbf3ecb69bea2186c26b589\
+                      09351fc9ac90b3ecfdfbc7c66431e030\
+                      3dca179c138ac17ad9bef1177331a704");
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let (sk, pk) = generate(&mut thread_rng());
+        let (_, other) = generate(&mut thread_rng());
+        let sig = sign(&sk, b"message");
+
+        assert_eq!(Ok(()), verify(&pk, b"message", &sig));
+        assert_eq!(Err(Error::InvalidSignature),
+                   verify(&pk, b"massage", &sig));
+        assert_eq!(Err(Error::InvalidSignature),
+                   verify(&other, b"message", &sig));
+
+        let mut bad = sig;
+
+        bad[5] ^= 0x10;
+        assert!(verify(&pk, b"message", &bad).is_err());
+    }
+
+    #[test]
+    fn test_non_canonical_s() {
+        // Adding the group order to S gives the same value mod the
+        // order, but must be rejected.
+        let order = from_hex("edd3f55c1a631258d69cf7a2def9de14\
+                              00000000000000000000000000000010");
+        let mut sk = [0u8; 32];
+
+        sk.copy_from_slice(&from_hex("9d61b19deffd5a60ba844af492ec2cc4\
+                                      4449c5697b326919703bac031cae7f60"));
+
+        let pk = public_key(&sk);
+        let mut sig = sign(&sk, b"");
+        let mut carry = 0u16;
+
+        for i in 0..32 {
+            let sum = (sig[32 + i] as u16) + (order[i] as u16) + carry;
+
+            sig[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+
+        assert_eq!(0, carry);
+        assert_eq!(Err(Error::NonCanonical), verify(&pk, b"", &sig));
+    }
+
+    fn keys(secret: &str, public: &str) -> ([u8; 32], [u8; 32]) {
+        let mut sk = [0u8; 32];
+        let mut pk = [0u8; 32];
+
+        sk.copy_from_slice(&from_hex(secret));
+        pk.copy_from_slice(&from_hex(public));
+        assert_eq!(pk, public_key(&sk));
+
+        (sk, pk)
+    }
+
+    fn check_vector_ctx(secret: &str, public: &str, context: &[u8],
+                        msg: &str, sig: &str) {
+        let (sk, pk) = keys(secret, public);
+        let msg = from_hex(msg);
+        let mut expected = [0u8; 64];
+
+        expected.copy_from_slice(&from_hex(sig));
+
+        assert_eq!(&expected[..], &sign_ctx(&sk, context, &msg).unwrap()[..]);
+        assert_eq!(Ok(()), verify_ctx(&pk, context, &msg, &expected));
+        assert_eq!(Err(Error::InvalidSignature),
+                   verify(&pk, &msg, &expected));
+        assert_eq!(Err(Error::InvalidSignature),
+                   verify_ph(&pk, context, &msg, &expected));
+    }
+
+    #[test]
+    fn test_rfc8032_ctx_foo() {
+        check_vector_ctx("0305334e381af78f141cb666f6199f57\
+                          bc3495335a256a95bd2a55bf546663f6",
+                         "dfc9425e4f968f7f0c29f0259cf5f9ae\
+                          d6851c2bb4ad8bfb860cfee0ab248292",
+                         b"foo", "f726936d19c800494e3fdaff20b276a8",
+                         "55a4cc2f70a54e04288c5f4cd1e45a7b\
+                          b520b36292911876cada7323198dd87a\
+                          8b36950b95130022907a7fb7c4e9b2d5\
+                          f6cca685a587b4b21f4b888e4e7edb0d");
+    }
+
+    #[test]
+    fn test_rfc8032_ctx_bar() {
+        check_vector_ctx("0305334e381af78f141cb666f6199f57\
+                          bc3495335a256a95bd2a55bf546663f6",
+                         "dfc9425e4f968f7f0c29f0259cf5f9ae\
+                          d6851c2bb4ad8bfb860cfee0ab248292",
+                         b"bar", "f726936d19c800494e3fdaff20b276a8",
+                         "fc60d5872fc46b3aa69f8b5b4351d580\
+                          8f92bcc044606db097abab6dbcb1aee3\
+                          216c48e8b3b66431b5b186d1d28f8ee1\
+                          5a5ca2df6668346291c2043d4eb3e90d");
+    }
+
+    #[test]
+    fn test_rfc8032_ctx_message() {
+        check_vector_ctx("0305334e381af78f141cb666f6199f57\
+                          bc3495335a256a95bd2a55bf546663f6",
+                         "dfc9425e4f968f7f0c29f0259cf5f9ae\
+                          d6851c2bb4ad8bfb860cfee0ab248292",
+                         b"foo", "508e9e6882b979fea900f62adceaca35",
+                         "8b70c1cc8310e1de20ac53ce28ae6e72\
+                          07f33c3295e03bb5c0732a1d20dc6490\
+                          8922a8b052cf99b7c4fe107a5abb5b2c\
+                          4085ae75890d02df26269d8945f84b0b");
+    }
+
+    #[test]
+    fn test_rfc8032_ctx_key() {
+        check_vector_ctx("ab9c2853ce297ddab85c993b3ae14bca\
+                          d39b2c682beabc27d6d4eb20711d6560",
+                         "0f1d1274943b91415889152e893d80e9\
+                          3275a1fc0b65fd71b4b0dda10ad7d772",
+                         b"foo", "f726936d19c800494e3fdaff20b276a8",
+                         "21655b5f1aa965996b3f97b3c849eafb\
+                          a922a0a62992f73b3d1b73106a84ad85\
+                          e9b86a7b6005ea868337ff2d20a7f5fb\
+                          d4cd10b0be49a68da2b2e0dc0ad8960f");
+    }
+
+    #[test]
+    fn test_rfc8032_ph() {
+        let (sk, pk) = keys("833fe62409237b9d62ec77587520911e\
+                             9a759cec1d19755b7da901b96dca3d42",
+                            "ec172b93ad5e563bf4932c70e1245034\
+                             c35467ef2efd4d64ebf819683467e2bf");
+        let mut expected = [0u8; 64];
+        let mut hash = Sha512::new();
+
+        expected.copy_from_slice(
+            &from_hex("98a70222f0b8121aa9d30f813d683f80\
+                       9e462b469c7ff87639499bb94e6dae41\
+                       31f85042463c2a355a2003d062adf5aa\
+                       a10b8c61e636062aaad11c2a26083406"));
+
+        assert_eq!(&expected[..], &sign_ph(&sk, b"", b"abc").unwrap()[..]);
+        assert_eq!(Ok(()), verify_ph(&pk, b"", b"abc", &expected));
+        assert_eq!(Err(Error::InvalidSignature),
+                   verify(&pk, b"abc", &expected));
+        assert_eq!(Err(Error::InvalidSignature),
+                   verify_ph(&pk, b"foo", b"abc", &expected));
+
+        // Hashing the message in pieces gives the same signature.
+        hash.update(b"a");
+        hash.update(b"bc");
+
+        let digest = hash.finish();
+
+        assert_eq!(&expected[..],
+                   &sign_prehashed(&sk, b"", &digest).unwrap()[..]);
+        assert_eq!(Ok(()), verify_prehashed(&pk, b"", &digest, &expected));
+    }
+
+    #[test]
+    fn test_context_length() {
+        let (sk, pk) = generate(&mut thread_rng());
+        let long = [0u8; 256];
+        let sig = sign(&sk, b"message");
+
+        assert_eq!(Some(Error::InvalidLength),
+                   sign_ctx(&sk, b"", b"message").err());
+        assert_eq!(Err(Error::InvalidLength),
+                   verify_ctx(&pk, b"", b"message", &sig));
+        assert_eq!(Some(Error::InvalidLength),
+                   sign_ctx(&sk, &long, b"message").err());
+        assert_eq!(Some(Error::InvalidLength),
+                   sign_ph(&sk, &long, b"message").err());
+        assert!(sign_ctx(&sk, &long[0..255], b"message").is_ok());
+        assert!(sign_ph(&sk, b"", b"message").is_ok());
+    }
+}
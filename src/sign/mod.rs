@@ -0,0 +1,3 @@
+pub mod schnorr;
+pub mod ed25519;
+pub mod ed448;
@@ -0,0 +1,352 @@
+use curve::curve1174::Curve1174;
+use curve::curve41417::Curve41417;
+use curve::e_222::E222;
+use curve::e_382::E382;
+use curve::e_521::E521;
+use curve::edwards::*;
+use curve::group::Group;
+use curve::point::*;
+use error::Error;
+use field::prime_field::PrimeField;
+use hash::hash_function::HashFunction;
+use hash::shake256::Shake256;
+use pack::Pack;
+use rand::Rng;
+use scalar::scalar_field::ScalarField;
+use std::marker::PhantomData;
+
+/// Deterministic Schnorr signatures in the style of EdDSA (RFC 8032),
+/// over any Edwards curve C with any hash function H.
+///
+/// With b the length in bytes of a compressed point, secret keys are
+/// b random bytes, public keys are compressed points, and signatures
+/// are a compressed point R followed by the scalar S, padded to b
+/// bytes.  The hash is always asked for 2b bytes of output, so an
+/// extendable-output function such as SHAKE256 suits every curve.
+///
+/// The secret scalar is derived from the hash of the secret key as in
+/// RFC 8032: the low bits that would select a point of small order
+/// are cleared, the top bit of a field element is set, and any bits
+/// above it are cleared.
+pub struct Schnorr<C, H> {
+    phantom: PhantomData<(C, H)>
+}
+
+/// Schnorr signatures over E-222 with SHAKE256.
+pub type E222Schnorr = Schnorr<E222, Shake256>;
+
+/// Schnorr signatures over Curve1174 with SHAKE256.
+pub type Curve1174Schnorr = Schnorr<Curve1174, Shake256>;
+
+/// Schnorr signatures over E-382 with SHAKE256.
+pub type E382Schnorr = Schnorr<E382, Shake256>;
+
+/// Schnorr signatures over Curve41417 with SHAKE256.
+pub type Curve41417Schnorr = Schnorr<Curve41417, Shake256>;
+
+/// Schnorr signatures over E-521 with SHAKE256.
+pub type E521Schnorr = Schnorr<E521, Shake256>;
+
+impl<C, H> Schnorr<C, H>
+    where C: EdwardsCurve + Group<EdwardsExtended<C>>,
+          H: HashFunction {
+    /// Get the number of bytes in a secret or public key.
+    pub fn key_len() -> usize {
+        EdwardsCompressed::<C>::nbytes()
+    }
+
+    /// Get the number of bytes in a signature.
+    pub fn signature_len() -> usize {
+        2 * Self::key_len()
+    }
+
+    /// Hash the concatenation of the parts, producing 2b bytes.
+    fn hash(parts: &[&[u8]]) -> Vec<u8> {
+        let mut hash = H::new();
+        let mut out = vec![0u8; Self::signature_len()];
+
+        for part in parts {
+            hash.update(part);
+        }
+
+        hash.finish_into(&mut out);
+
+        out
+    }
+
+    /// Hash the concatenation of the parts, and reduce the result
+    /// modulo the group order.
+    fn hash_scalar(parts: &[&[u8]]) -> C::Scalar {
+        C::Scalar::from_bytes_wide(&Self::hash(parts))
+    }
+
+    /// Expand a secret key into the secret scalar and the prefix used
+    /// to derive nonces.
+    fn expand(secret: &[u8]) -> (C::Scalar, Vec<u8>) {
+        let len = Self::key_len();
+        let h = Self::hash(&[secret]);
+        let mut s = h[0..len].to_vec();
+        let top = C::Field::nbits() - 1;
+        let cofactor_bits = C::cofactor().trailing_zeros() as usize;
+
+        s[0] &= (0xff << cofactor_bits) as u8;
+
+        for i in top..8 * len {
+            s[i / 8] &= !(1 << (i % 8));
+        }
+
+        s[top / 8] |= 1 << (top % 8);
+
+        (C::Scalar::unpacked(&s), h[len..].to_vec())
+    }
+
+    /// Generate a random secret key, and return it along with the
+    /// corresponding public key.
+    pub fn generate<R: Rng>(rng: &mut R) -> (Vec<u8>, Vec<u8>) {
+        let mut secret = vec![0u8; Self::key_len()];
+
+        rng.fill_bytes(&mut secret);
+
+        let public = Self::public_key(&secret);
+
+        (secret, public)
+    }
+
+    /// Get the public key corresponding to a secret key.  This
+    /// panics if the secret key has the wrong length.
+    pub fn public_key(secret: &[u8]) -> Vec<u8> {
+        assert_eq!(Self::key_len(), secret.len());
+
+        let (s, _) = Self::expand(secret);
+
+        EdwardsCompressed::compressed(C::base_mult(&s)).bytes().to_vec()
+    }
+
+    /// Sign a message.  The signature is deterministic.  This panics
+    /// if the secret key has the wrong length.
+    pub fn sign(secret: &[u8], msg: &[u8]) -> Vec<u8> {
+        Self::sign_with_prefix(&[], secret, msg)
+    }
+
+    /// Verify a signature on a message made with sign.
+    pub fn verify(public: &[u8], msg: &[u8], sig: &[u8])
+                  -> Result<(), Error> {
+        Self::verify_with_prefix(&[], public, msg, sig)
+    }
+
+    /// Sign a message, with dom prepended to the input of every hash
+    /// but the one that expands the secret key.  This is how the
+    /// variants of EdDSA in RFC 8032 separate their domains.
+    pub fn sign_with_prefix(dom: &[u8], secret: &[u8], msg: &[u8])
+                            -> Vec<u8> {
+        assert_eq!(Self::key_len(), secret.len());
+
+        let (s, prefix) = Self::expand(secret);
+        let a = EdwardsCompressed::compressed(C::base_mult(&s));
+        let r = Self::hash_scalar(&[dom, &prefix, msg]);
+        let big_r = EdwardsCompressed::compressed(C::base_mult(&r));
+        let k = Self::hash_scalar(&[dom, big_r.bytes(), a.bytes(), msg]);
+        let len = Self::key_len();
+        let slen = C::Scalar::nbytes() as usize;
+        let mut out = vec![0u8; Self::signature_len()];
+
+        out[0..len].copy_from_slice(big_r.bytes());
+        (r + (k * s)).pack(&mut out[len..len + slen]);
+
+        out
+    }
+
+    /// Verify a signature made with sign_with_prefix.
+    ///
+    /// This rejects encodings of S that are not less than the group
+    /// order, and non-canonical encodings of the points R and A.  It
+    /// checks the cofactored equation [h][S]B = [h]R + [h][k]A, so
+    /// any signature accepted by the cofactorless check is also
+    /// accepted.
+    pub fn verify_with_prefix(dom: &[u8], public: &[u8], msg: &[u8],
+                              sig: &[u8]) -> Result<(), Error> {
+        let len = Self::key_len();
+        let slen = C::Scalar::nbytes() as usize;
+
+        if public.len() != len || sig.len() != Self::signature_len() {
+            return Err(Error::InvalidLength);
+        }
+
+        if sig[len + slen..].iter().any(|b| *b != 0) {
+            return Err(Error::NonCanonical);
+        }
+
+        let a = EdwardsCompressed::<C>::from_bytes(public).decompressed()?;
+        let big_r = EdwardsCompressed::<C>::from_bytes(&sig[0..len])
+                            .decompressed()?;
+        let s = C::Scalar::unpacked_checked(&sig[len..len + slen])?;
+        let k = Self::hash_scalar(&[dom, &sig[0..len], public, msg]);
+        let mut ka = a;
+
+        // Compute [S]B - [k]A - R, which must have small order.
+        ka.scalar_mult(&(-k));
+
+        let mut check = C::base_mult(&s) + ka + (-big_r);
+
+        for _ in 0..C::cofactor().trailing_zeros() {
+            check.double();
+        }
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use error::Error;
+    use rand::thread_rng;
+    use sign::schnorr::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len() / 2).map(|i| u8::from_str_radix(&s[2 * i .. 2 * i + 2],
+                                                    16).unwrap())
+                        .collect()
+    }
+
+    fn check_vector<C, H>(secret: &str, public: &str, sig: &str)
+        where C: EdwardsCurve + Group<EdwardsExtended<C>>,
+              H: HashFunction {
+        let sk = from_hex(secret);
+        let pk = from_hex(public);
+        let expected = from_hex(sig);
+
+        assert_eq!(pk, Schnorr::<C, H>::public_key(&sk));
+        assert_eq!(expected, Schnorr::<C, H>::sign(&sk, b"abc"));
+        assert_eq!(Ok(()), Schnorr::<C, H>::verify(&pk, b"abc", &expected));
+    }
+
+    fn check_sign_verify<C, H>()
+        where C: EdwardsCurve + Group<EdwardsExtended<C>>,
+              H: HashFunction {
+        let (sk, pk) = Schnorr::<C, H>::generate(&mut thread_rng());
+        let (_, other) = Schnorr::<C, H>::generate(&mut thread_rng());
+        let sig = Schnorr::<C, H>::sign(&sk, b"message");
+        let dom_sig = Schnorr::<C, H>::sign_with_prefix(b"dom", &sk,
+                                                         b"message");
+        let len = Schnorr::<C, H>::key_len();
+
+        assert_eq!(Schnorr::<C, H>::signature_len(), sig.len());
+        assert_eq!(Ok(()), Schnorr::<C, H>::verify(&pk, b"message", &sig));
+        assert_eq!(Err(Error::InvalidSignature),
+                   Schnorr::<C, H>::verify(&pk, b"massage", &sig));
+        assert_eq!(Err(Error::InvalidSignature),
+                   Schnorr::<C, H>::verify(&other, b"message", &sig));
+        assert_eq!(Err(Error::InvalidLength),
+                   Schnorr::<C, H>::verify(&pk, b"message", &sig[1..]));
+
+        // The prefix separates signatures into domains.
+        assert_eq!(Ok(()),
+                   Schnorr::<C, H>::verify_with_prefix(b"dom", &pk,
+                                                       b"message", &dom_sig));
+        assert_eq!(Err(Error::InvalidSignature),
+                   Schnorr::<C, H>::verify(&pk, b"message", &dom_sig));
+
+        // S must be reduced.
+        let mut bad = sig.clone();
+
+        bad[2 * len - 1] = 0xff;
+        assert_eq!(Err(Error::NonCanonical),
+                   Schnorr::<C, H>::verify(&pk, b"message", &bad));
+    }
+
+    #[test]
+    fn test_e222() {
+        check_vector::<E222, Shake256>(
+            "05223f5c7996b3d0ed0a2744617e9bb8\
+             d5f20f2c496683a0bddaf714",
+            "12aab6ef25598f6fcabe34cc716133a7\
+             32746815557cabcdd96cd707",
+            "db5fb9e096c6a1f549a3dddeb8c2cf6d\
+             587e2afb0a762f6e9dab85b4ff0b70f7\
+             892f6e76ffadb116af1d12ac46e3cb61\
+             02ab1ba5846de703");
+        check_sign_verify::<E222, Shake256>();
+    }
+
+    #[test]
+    fn test_curve1174() {
+        check_vector::<Curve1174, Shake256>(
+            "092643607d9ab7d4f10e2b4865829fbc\
+             d9f613304d6a87a4c1defb1835526f8c",
+            "ff8ecfcc160f63ab1d1f9eeca2f44e58\
+             07ed9875610a48ba1ecc9c120ced9484",
+            "50cd35e3e6eae76c91771b712e0cb2ae\
+             1ef84e3ec3c5b6a9712f1878a5428604\
+             4bfc7b15e7afd7511f57b21120af4b14\
+             80277f5de5cc62b1e18ea75dbf3ece01");
+        check_sign_verify::<Curve1174, Shake256>();
+    }
+
+    #[test]
+    fn test_e382() {
+        check_vector::<E382, Shake256>(
+            "05223f5c7996b3d0ed0a2744617e9bb8\
+             d5f20f2c496683a0bddaf714314e6b88\
+             a5c2dffc193653708daac7e4011e3b58",
+            "9a7752c32c0ba78926536f5f910b03ed\
+             60b7aefb70f1506c185760ee1ceee476\
+             35c8a1d7bc8314e297b6680ad2cbfa11",
+            "f9f5087b6a859ae063add8875b9c938e\
+             cf25b901524e050f69c409cd5a33a863\
+             63bae3a70c5943fde5bb23f67968a52e\
+             7a0ffb9158030a8de22fa3f15de2b1c5\
+             7916e9c53667bb477dc5d2afb912d390\
+             01117a1a9483c2f69e1aa3613bfb5e0c");
+        check_sign_verify::<E382, Shake256>();
+    }
+
+    #[test]
+    fn test_curve41417() {
+        check_vector::<Curve41417, Shake256>(
+            "0a2744617e9bb8d5f20f2c496683a0bd\
+             daf714314e6b88a5c2dffc193653708d\
+             aac7e4011e3b587592afcce90623405d\
+             7a97b4d1",
+            "10275cf50dfd669d805c5de6df6b72a3\
+             f38dc733cbea864fbec353b3a2137503\
+             77d79c523eace93f80e0eefd07b0105d\
+             9a1b2f00",
+            "241d58bc8aa59d659d87132dca07f54c\
+             ae4a6b1caf448d4aa6c18de0947d9391\
+             c49bcae195956ea5531bf5b7d0a4bfca\
+             75aebca7e771ce3a3303a92c658af90d\
+             15d9300c5cdce27c22cfe79a7520b6fb\
+             6821638d64a77de469abb8f48ee202cc\
+             eb5aa7e79b417807");
+        check_sign_verify::<Curve41417, Shake256>();
+    }
+
+    #[test]
+    fn test_e521() {
+        check_vector::<E521, Shake256>(
+            "05223f5c7996b3d0ed0a2744617e9bb8\
+             d5f20f2c496683a0bddaf714314e6b88\
+             a5c2dffc193653708daac7e4011e3b58\
+             7592afcce90623405d7a97b4d1ee0b28\
+             4562",
+            "4b2fdb384c1489ce3d9d783295d0cc07\
+             324def4f6f44e3afd9cfa07cda2f3900\
+             1d290dbb28199dee84673d87b90c38bb\
+             bc44f7364fc843b9060eaef1602753bb\
+             2b80",
+            "af4e3984680eaf49c58bd660895d8aa7\
+             84507a2dfeb302b3653021a39b6e3d2d\
+             0523dcaa81f16ff6e4859c91ca379d6a\
+             c21a10a4556f7c083fcc1c46ea8a6c75\
+             ce011a8a92e571ad9bce212e6e7ad1da\
+             d047580515440e10ca231b483535e00f\
+             0a21e1d6ae05bdec1da07ff21ba69010\
+             f472663ddfbab83f0ec8b63373825b9c\
+             6c4f1900");
+        check_sign_verify::<E521, Shake256>();
+    }
+}